@@ -4,6 +4,14 @@ use utoipa::{Modify, OpenApi};
 pub const HEALTH_TAG: &str = "Health";
 pub const AUTH_TAG: &str = "Auth";
 pub const USER_TAG: &str = "User";
+pub const STREAMER_TAG: &str = "Streamer";
+pub const SUBSCRIPTION_TAG: &str = "Subscription";
+pub const NOTIFICATION_TAG: &str = "Notification";
+pub const EVENT_TAG: &str = "Event";
+pub const FEED_TAG: &str = "Feed";
+pub const SHARE_TAG: &str = "Share";
+pub const MEDIA_TAG: &str = "Media";
+pub const ADMIN_TAG: &str = "Admin";
 
 #[derive(OpenApi)]
 #[openapi(
@@ -14,7 +22,15 @@ pub const USER_TAG: &str = "User";
     modifiers(&SecurityAddon),
     tags(
         (name = AUTH_TAG, description = "Authentication endpoints"),
-        (name = USER_TAG, description = "User management endpoints")
+        (name = USER_TAG, description = "User management endpoints"),
+        (name = STREAMER_TAG, description = "Tracked streamer endpoints"),
+        (name = SUBSCRIPTION_TAG, description = "Subscription endpoints of the current user"),
+        (name = NOTIFICATION_TAG, description = "Notification channel endpoints of the current user"),
+        (name = EVENT_TAG, description = "Real-time streamer events of the current user"),
+        (name = FEED_TAG, description = "Atom and RSS feeds of the current user"),
+        (name = SHARE_TAG, description = "Public status pages of subscription groups"),
+        (name = MEDIA_TAG, description = "Cached streamer avatars and cover images"),
+        (name = ADMIN_TAG, description = "Operator endpoints, for the users listed in [admin]")
     ),
 )]
 pub struct ApiDoc;
@@ -1,9 +1,13 @@
+mod public_url;
+
 use crate::error::AppError;
 use axum::Json;
 use axum::extract::{FromRequest, Request};
 use serde::de::DeserializeOwned;
 use validator::Validate;
 
+pub use public_url::PublicUrl;
+
 /// Wrapper extractor that automatically validates JSON payloads using `validator::Validate`.
 pub struct ValidatedJson<T>(pub T);
 
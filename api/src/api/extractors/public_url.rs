@@ -0,0 +1,47 @@
+use crate::AppState;
+use axum::extract::FromRequestParts;
+use axum::http::header::HOST;
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, HeaderName};
+use std::convert::Infallible;
+use std::sync::Arc;
+
+const X_FORWARDED_HOST: HeaderName = HeaderName::from_static("x-forwarded-host");
+const X_FORWARDED_PROTO: HeaderName = HeaderName::from_static("x-forwarded-proto");
+
+/// Base URL under which clients reach this server, without a trailing slash.
+///
+/// Uses `application.public_url` when configured, otherwise the (forwarded) host
+/// and scheme of the request.
+pub struct PublicUrl(pub String);
+
+impl FromRequestParts<Arc<AppState>> for PublicUrl {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        if let Some(url) = &state.config.application.public_url {
+            return Ok(Self(url.trim_end_matches('/').to_string()));
+        }
+
+        let host = header(&parts.headers, &X_FORWARDED_HOST)
+            .or_else(|| header(&parts.headers, &HOST))
+            .map(str::to_string)
+            .unwrap_or_else(|| state.config.server_address());
+        let scheme = header(&parts.headers, &X_FORWARDED_PROTO).unwrap_or("http");
+        Ok(Self(format!("{scheme}://{host}")))
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &HeaderName) -> Option<&'a str> {
+    headers
+        .get(name)?
+        .to_str()
+        .ok()?
+        .split(',')
+        .next()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
@@ -0,0 +1,114 @@
+use crate::AppState;
+use crate::api::ValidatedJson;
+use crate::api::doc::STREAMER_TAG;
+use crate::api::handlers::pagination::{Pagination, PaginationQuery};
+use crate::dto::{CreateCreatorRequest, CreatorDto, PagedResponse, UpdateCreatorRequest};
+use crate::error::AppResult;
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = STREAMER_TAG,
+    request_body = CreateCreatorRequest,
+    responses(
+         (status = 201, description = "Create a creator and link their rooms on different platforms", body = CreatorDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(payload): ValidatedJson<CreateCreatorRequest>,
+) -> AppResult<(StatusCode, Json<CreatorDto>)> {
+    state
+        .services
+        .creator
+        .create(payload)
+        .await
+        .map(|creator| (StatusCode::CREATED, Json(creator)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = STREAMER_TAG,
+    responses(
+         (status = 200, description = "List creators with their linked rooms by page", body = PagedResponse<CreatorDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<PaginationQuery>,
+) -> AppResult<Json<PagedResponse<CreatorDto>>> {
+    let Pagination { page, page_size } = params.into_pagination()?;
+    state.services.creator.list(page, page_size).await.map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = STREAMER_TAG,
+    responses(
+         (status = 200, description = "Find creator by id", body = CreatorDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn find_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<CreatorDto>> {
+    state.services.creator.find(id).await.map(Json)
+}
+
+#[utoipa::path(
+    patch,
+    path = "/{id}",
+    tag = STREAMER_TAG,
+    request_body = UpdateCreatorRequest,
+    responses(
+         (status = 200, description = "Rename a creator or replace their linked rooms", body = CreatorDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateCreatorRequest>,
+) -> AppResult<Json<CreatorDto>> {
+    state.services.creator.update(id, payload).await.map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = STREAMER_TAG,
+    responses(
+         (status = 204, description = "Delete a creator; the rooms stay tracked and become unlinked")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    state
+        .services
+        .creator
+        .delete(id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
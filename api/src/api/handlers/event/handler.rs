@@ -0,0 +1,111 @@
+use crate::AppState;
+use crate::api::doc::EVENT_TAG;
+use crate::api::middleware::AuthContext;
+use crate::event::{EventEnvelope, StreamerEvent};
+use axum::Extension;
+use axum::extract::State;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::{Stream, StreamExt};
+use tracing::debug;
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = EVENT_TAG,
+    params(
+        ("access_token" = Option<String>, Query, description = "Access token for clients that cannot send the Authorization header")
+    ),
+    responses(
+         (status = 200, description = "Server-sent live, offline, title and category change events of subscribed streamers. A `lagged` event carries the number of events the client missed.", content_type = "text/event-stream", body = StreamerEvent)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn stream(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let user_id = auth.user_id();
+    let events =
+        BroadcastStream::new(state.events.subscribe()).filter_map(move |item| match item {
+            Ok(envelope) => envelope.is_for(user_id).then(|| {
+                Event::default()
+                    .event(envelope.event.kind.name())
+                    .json_data(&envelope.event)
+            }),
+            Err(BroadcastStreamRecvError::Lagged(missed)) => Some(Ok(Event::default()
+                .event("lagged")
+                .data(missed.to_string()))),
+        });
+
+    Sse::new(events).keep_alive(
+        KeepAlive::new().interval(Duration::from_secs(state.config.events.keep_alive_secs)),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = EVENT_TAG,
+    params(
+        ("access_token" = Option<String>, Query, description = "Access token for clients that cannot send the Authorization header")
+    ),
+    responses(
+         (status = 101, description = "WebSocket variant of the event stream; every text frame is a JSON encoded event", body = StreamerEvent)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn websocket(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    let receiver = state.events.subscribe();
+    let user_id = auth.user_id();
+    upgrade
+        .on_upgrade(move |socket| forward_events(socket, receiver, user_id))
+        .into_response()
+}
+
+async fn forward_events(
+    mut socket: WebSocket,
+    mut receiver: Receiver<Arc<EventEnvelope>>,
+    user_id: i32,
+) {
+    loop {
+        tokio::select! {
+            received = receiver.recv() => {
+                let payload = match received {
+                    Ok(envelope) if envelope.is_for(user_id) => {
+                        serde_json::to_string(&envelope.event)
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(missed)) => {
+                        serde_json::to_string(&serde_json::json!({ "type": "lagged", "missed": missed }))
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                let Ok(payload) = payload else { continue };
+                if socket.send(Message::Text(payload.into())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+    debug!(user_id, "event websocket closed");
+}
@@ -0,0 +1,112 @@
+use crate::AppState;
+use crate::api::PublicUrl;
+use crate::api::doc::FEED_TAG;
+use crate::api::middleware::AuthContext;
+use crate::domain::feed::FeedFormat;
+use crate::dto::{FeedQuery, FeedTokenDto};
+use crate::error::{AppError, AppResult};
+use axum::extract::{Path, Query, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = FEED_TAG,
+    responses(
+         (status = 200, description = "Feed URLs of the current user, created on first use", body = FeedTokenDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn token(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    PublicUrl(base_url): PublicUrl,
+) -> AppResult<Json<FeedTokenDto>> {
+    state
+        .services
+        .feed
+        .token(auth.user_id(), &base_url)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/rotate",
+    tag = FEED_TAG,
+    responses(
+         (status = 200, description = "Replace the feed token; previously shared feed URLs stop working", body = FeedTokenDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn rotate(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    PublicUrl(base_url): PublicUrl,
+) -> AppResult<Json<FeedTokenDto>> {
+    state
+        .services
+        .feed
+        .rotate_token(auth.user_id(), &base_url)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/feeds/{file}",
+    tag = FEED_TAG,
+    params(
+        ("file" = String, Path, description = "`{token}.atom`, `{token}.rss` or `{token}.ics`"),
+        FeedQuery
+    ),
+    responses(
+         (status = 200, description = "Atom or RSS feed of recent go-live and title change events, or an iCalendar of past and predicted live sessions", content_type = "application/atom+xml", body = String),
+         (status = 404, description = "Unknown feed token")
+    )
+)]
+pub async fn render(
+    State(state): State<Arc<AppState>>,
+    Path(file): Path<String>,
+    Query(query): Query<FeedQuery>,
+    PublicUrl(base_url): PublicUrl,
+) -> AppResult<impl IntoResponse> {
+    let (token, extension) = file.rsplit_once('.').ok_or_else(|| {
+        AppError::BadRequest("Feed must end with .atom, .rss or .ics".to_string())
+    })?;
+
+    let (content_type, body) = match FeedFormat::from_extension(extension) {
+        Some(format) => (
+            format.content_type(),
+            state.services.feed.render(token, format, &base_url).await?,
+        ),
+        None if extension == "ics" => (
+            "text/calendar; charset=utf-8",
+            state
+                .services
+                .calendar
+                .render(token, query.min_confidence)
+                .await?,
+        ),
+        None => {
+            return Err(AppError::BadRequest(
+                "Feed must end with .atom, .rss or .ics".to_string(),
+            ));
+        }
+    };
+
+    Ok((
+        [
+            (CONTENT_TYPE, content_type),
+            (CACHE_CONTROL, "private, max-age=60"),
+        ],
+        body,
+    ))
+}
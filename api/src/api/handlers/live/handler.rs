@@ -0,0 +1,34 @@
+use crate::AppState;
+use crate::api::doc::SUBSCRIPTION_TAG;
+use crate::api::middleware::AuthContext;
+use crate::dto::{LiveQuery, LiveStreamerDto, SubscriptionFilter};
+use crate::error::AppResult;
+use axum::extract::{Query, State};
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = SUBSCRIPTION_TAG,
+    params(LiveQuery, SubscriptionFilter),
+    responses(
+         (status = 200, description = "Currently live subscribed streamers with their live status and uptime", body = Vec<LiveStreamerDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<LiveQuery>,
+    Query(filter): Query<SubscriptionFilter>,
+) -> AppResult<Json<Vec<LiveStreamerDto>>> {
+    state
+        .services
+        .subscription
+        .live(auth.user_id(), &filter, query.sort.unwrap_or_default())
+        .await
+        .map(Json)
+}
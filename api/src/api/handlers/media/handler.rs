@@ -0,0 +1,43 @@
+use crate::AppState;
+use crate::api::doc::MEDIA_TAG;
+use crate::dto::MediaQuery;
+use crate::error::AppResult;
+use axum::extract::{Path, Query, State};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/{kind}/{hash}",
+    tag = MEDIA_TAG,
+    params(
+        ("kind" = String, Path, description = "`avatar` or `cover`"),
+        ("hash" = String, Path, description = "SHA-256 of the image URL, as given in `avatar_proxy_url` and `cover_image_proxy_url`"),
+        MediaQuery
+    ),
+    responses(
+         (status = 200, description = "Cached copy of a streamer avatar or cover image", content_type = "image/*"),
+         (status = 404, description = "Unknown image"),
+         (status = 502, description = "The image could not be fetched from the platform")
+    )
+)]
+pub async fn get(
+    State(state): State<Arc<AppState>>,
+    Path((kind, hash)): Path<(String, String)>,
+    Query(query): Query<MediaQuery>,
+) -> AppResult<Response> {
+    let media = &state.services.media;
+    let file = media.get(&kind, &hash, query.width).await?;
+    Ok((
+        [
+            (CONTENT_TYPE, file.content_type.to_string()),
+            (
+                CACHE_CONTROL,
+                format!("public, max-age={}, immutable", media.cache_max_age_secs()),
+            ),
+        ],
+        file.bytes,
+    )
+        .into_response())
+}
@@ -1,3 +1,16 @@
 pub mod auth;
+pub mod creator;
+pub mod event;
+pub mod feed;
+pub mod live;
+pub mod media;
+pub mod notification_channel;
+pub mod notification_outbox;
 pub mod pagination;
+pub mod share_page;
+pub mod streamer;
+pub mod subscription;
+pub mod subscription_group;
+pub mod transfer;
 pub mod user;
+pub mod web_push;
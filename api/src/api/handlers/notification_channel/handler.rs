@@ -0,0 +1,105 @@
+use crate::AppState;
+use crate::api::ValidatedJson;
+use crate::api::doc::NOTIFICATION_TAG;
+use crate::api::middleware::AuthContext;
+use crate::dto::{CreateNotificationChannelRequest, DeliveryReportDto, NotificationChannelDto};
+use crate::error::AppResult;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = NOTIFICATION_TAG,
+    request_body = CreateNotificationChannelRequest,
+    responses(
+         (status = 201, description = "Add a notification channel for the current user", body = NotificationChannelDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<CreateNotificationChannelRequest>,
+) -> AppResult<(StatusCode, Json<NotificationChannelDto>)> {
+    state
+        .services
+        .notification_channel
+        .create(auth.user_id(), payload)
+        .await
+        .map(|channel| (StatusCode::CREATED, Json(channel)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = NOTIFICATION_TAG,
+    responses(
+         (status = 200, description = "List notification channels of the current user", body = Vec<NotificationChannelDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+) -> AppResult<Json<Vec<NotificationChannelDto>>> {
+    state
+        .services
+        .notification_channel
+        .list(auth.user_id())
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = NOTIFICATION_TAG,
+    responses(
+         (status = 204, description = "Remove a notification channel")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    state
+        .services
+        .notification_channel
+        .delete(id, auth.user_id())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    post,
+    path = "/test",
+    tag = NOTIFICATION_TAG,
+    responses(
+         (status = 200, description = "Send a test notification to every enabled channel and report each outcome", body = DeliveryReportDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn test(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+) -> AppResult<Json<DeliveryReportDto>> {
+    state
+        .services
+        .notification
+        .send_test(auth.user_id())
+        .await
+        .map(|report| Json(report.into()))
+}
@@ -0,0 +1,75 @@
+use crate::AppState;
+use crate::api::doc::ADMIN_TAG;
+use crate::api::handlers::pagination::{Pagination, PaginationQuery};
+use crate::dto::{NotificationOutboxDto, NotificationOutboxQuery, PagedResponse};
+use crate::error::AppResult;
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = ADMIN_TAG,
+    params(NotificationOutboxQuery),
+    responses(
+         (status = 200, description = "List queued notifications, newest first", body = PagedResponse<NotificationOutboxDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<PaginationQuery>,
+    Query(query): Query<NotificationOutboxQuery>,
+) -> AppResult<Json<PagedResponse<NotificationOutboxDto>>> {
+    let Pagination { page, page_size } = params.into_pagination()?;
+    state
+        .services
+        .notification
+        .list_outbox(query.status, page, page_size)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = ADMIN_TAG,
+    responses(
+         (status = 200, description = "Find a queued notification by id", body = NotificationOutboxDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn find_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> AppResult<Json<NotificationOutboxDto>> {
+    state.services.notification.find_outbox(id).await.map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/replay",
+    tag = ADMIN_TAG,
+    responses(
+         (status = 200, description = "Retry a dead or pending notification now, with a fresh set of attempts", body = NotificationOutboxDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn replay(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+) -> AppResult<Json<NotificationOutboxDto>> {
+    state
+        .services
+        .notification
+        .replay_outbox(id)
+        .await
+        .map(Json)
+}
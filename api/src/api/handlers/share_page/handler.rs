@@ -0,0 +1,178 @@
+use crate::AppState;
+use crate::api::doc::SHARE_TAG;
+use crate::api::middleware::AuthContext;
+use crate::api::{PublicUrl, ValidatedJson};
+use crate::domain::share_page::SharedPage;
+use crate::dto::{CreateSharePageRequest, SharePageDto, SharedPageQuery};
+use crate::error::AppResult;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
+use axum::response::{IntoResponse, Response};
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = SHARE_TAG,
+    request_body = CreateSharePageRequest,
+    responses(
+         (status = 201, description = "Publish a public page of a subscription group", body = SharePageDto),
+         (status = 404, description = "Unknown group")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    PublicUrl(base_url): PublicUrl,
+    ValidatedJson(payload): ValidatedJson<CreateSharePageRequest>,
+) -> AppResult<(StatusCode, Json<SharePageDto>)> {
+    state
+        .services
+        .share_page
+        .create(auth.user_id(), payload, &base_url)
+        .await
+        .map(|page| (StatusCode::CREATED, Json(page)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = SHARE_TAG,
+    responses(
+         (status = 200, description = "List share pages of the current user, including expired ones", body = Vec<SharePageDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    PublicUrl(base_url): PublicUrl,
+) -> AppResult<Json<Vec<SharePageDto>>> {
+    state
+        .services
+        .share_page
+        .list(auth.user_id(), &base_url)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = SHARE_TAG,
+    responses(
+         (status = 200, description = "Find share page by id", body = SharePageDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn find_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    PublicUrl(base_url): PublicUrl,
+    Path(id): Path<i32>,
+) -> AppResult<Json<SharePageDto>> {
+    state
+        .services
+        .share_page
+        .find(id, auth.user_id(), &base_url)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/{id}/rotate",
+    tag = SHARE_TAG,
+    responses(
+         (status = 200, description = "Replace the slug; previously shared URLs stop working", body = SharePageDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn rotate(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    PublicUrl(base_url): PublicUrl,
+    Path(id): Path<i32>,
+) -> AppResult<Json<SharePageDto>> {
+    state
+        .services
+        .share_page
+        .rotate_slug(id, auth.user_id(), &base_url)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = SHARE_TAG,
+    responses(
+         (status = 204, description = "Unpublish a share page")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    state
+        .services
+        .share_page
+        .delete(id, auth.user_id())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/share/{file}",
+    tag = SHARE_TAG,
+    params(
+        ("file" = String, Path, description = "`{slug}` for an HTML page or `{slug}.json`"),
+        SharedPageQuery
+    ),
+    responses(
+         (status = 200, description = "Streamers of the shared group, live ones first", body = SharedPage),
+         (status = 404, description = "Unknown or expired share page")
+    )
+)]
+pub async fn render(
+    State(state): State<Arc<AppState>>,
+    Path(file): Path<String>,
+    Query(query): Query<SharedPageQuery>,
+) -> AppResult<Response> {
+    let (slug, json) = match file.strip_suffix(".json") {
+        Some(slug) => (slug, true),
+        None => (file.as_str(), false),
+    };
+
+    let rendered = state.services.share_page.render(slug, query.live).await?;
+    let cache_control = format!("public, max-age={}", rendered.max_age_secs);
+    let response = if json {
+        ([(CACHE_CONTROL, cache_control)], Json(rendered.page)).into_response()
+    } else {
+        (
+            [
+                (CONTENT_TYPE, "text/html; charset=utf-8".to_string()),
+                (CACHE_CONTROL, cache_control),
+            ],
+            rendered.page.to_html(rendered.max_age_secs),
+        )
+            .into_response()
+    };
+    Ok(response)
+}
@@ -0,0 +1,157 @@
+use crate::AppState;
+use crate::api::ValidatedJson;
+use crate::api::doc::STREAMER_TAG;
+use crate::api::handlers::pagination::{Pagination, PaginationQuery};
+use crate::dto::{
+    CreateStreamerRequest, LiveStateTransitionDto, PagedResponse, StreamerChangeDto,
+    StreamerChangesQuery, StreamerDto, ViewerSeriesDto, ViewerSeriesQuery,
+};
+use crate::error::AppResult;
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = STREAMER_TAG,
+    request_body = CreateStreamerRequest,
+    responses(
+         (status = 201, description = "Resolve a room on its platform and track the streamer", body = StreamerDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    ValidatedJson(payload): ValidatedJson<CreateStreamerRequest>,
+) -> AppResult<(StatusCode, Json<StreamerDto>)> {
+    state
+        .services
+        .streamer
+        .create(payload)
+        .await
+        .map(|streamer| (StatusCode::CREATED, Json(streamer)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = STREAMER_TAG,
+    responses(
+         (status = 200, description = "List tracked streamers by page", body = PagedResponse<StreamerDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Query(params): Query<PaginationQuery>,
+) -> AppResult<Json<PagedResponse<StreamerDto>>> {
+    let Pagination { page, page_size } = params.into_pagination()?;
+    state
+        .services
+        .streamer
+        .list(page, page_size)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = STREAMER_TAG,
+    responses(
+         (status = 200, description = "Find streamer by id", body = StreamerDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn find_by_id(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<StreamerDto>> {
+    state.services.streamer.find_by_id(id).await.map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/viewers",
+    tag = STREAMER_TAG,
+    params(ViewerSeriesQuery),
+    responses(
+         (status = 200, description = "Viewer count series with min/max/avg per bucket", body = ViewerSeriesDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn viewer_series(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(query): Query<ViewerSeriesQuery>,
+) -> AppResult<Json<ViewerSeriesDto>> {
+    let streamer = state.services.streamer.find_by_id(id).await?;
+    state
+        .services
+        .viewer_stats
+        .series(streamer.id, query)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/changes",
+    tag = STREAMER_TAG,
+    params(StreamerChangesQuery),
+    responses(
+         (status = 200, description = "History of title, category, cover, name and avatar changes, newest first", body = PagedResponse<StreamerChangeDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn changes(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(params): Query<PaginationQuery>,
+    Query(query): Query<StreamerChangesQuery>,
+) -> AppResult<Json<PagedResponse<StreamerChangeDto>>> {
+    let Pagination { page, page_size } = params.into_pagination()?;
+    state
+        .services
+        .streamer
+        .changes(id, query.field, page, page_size)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}/live-states",
+    tag = STREAMER_TAG,
+    responses(
+         (status = 200, description = "Log of live state transitions, including unconfirmed ones, newest first", body = PagedResponse<LiveStateTransitionDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn live_states(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(params): Query<PaginationQuery>,
+) -> AppResult<Json<PagedResponse<LiveStateTransitionDto>>> {
+    let Pagination { page, page_size } = params.into_pagination()?;
+    state
+        .services
+        .streamer
+        .live_states(id, page, page_size)
+        .await
+        .map(Json)
+}
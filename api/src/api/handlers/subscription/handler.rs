@@ -0,0 +1,242 @@
+use crate::AppState;
+use crate::api::ValidatedJson;
+use crate::api::doc::SUBSCRIPTION_TAG;
+use crate::api::handlers::pagination::{Pagination, PaginationQuery};
+use crate::api::middleware::AuthContext;
+use crate::dto::{
+    AssignSubscriptionGroupRequest, CreateSubscriptionRequest, PagedResponse, SubscriptionDto,
+    SubscriptionFilter, TagDto, UpdateSubscriptionRequest, UpdateSubscriptionRulesRequest,
+    UpdateSubscriptionTagsRequest,
+};
+use crate::error::AppResult;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = SUBSCRIPTION_TAG,
+    request_body = CreateSubscriptionRequest,
+    responses(
+         (status = 201, description = "Subscribe to a streamer", body = SubscriptionDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<CreateSubscriptionRequest>,
+) -> AppResult<(StatusCode, Json<SubscriptionDto>)> {
+    state
+        .services
+        .subscription
+        .create(auth.user_id(), payload)
+        .await
+        .map(|subscription| (StatusCode::CREATED, Json(subscription)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = SUBSCRIPTION_TAG,
+    params(SubscriptionFilter),
+    responses(
+         (status = 200, description = "List subscriptions of the current user, optionally narrowed down to a group or tag", body = PagedResponse<SubscriptionDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Query(params): Query<PaginationQuery>,
+    Query(filter): Query<SubscriptionFilter>,
+) -> AppResult<Json<PagedResponse<SubscriptionDto>>> {
+    let Pagination { page, page_size } = params.into_pagination()?;
+    state
+        .services
+        .subscription
+        .list(auth.user_id(), &filter, page, page_size)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = SUBSCRIPTION_TAG,
+    responses(
+         (status = 200, description = "Find subscription by id", body = SubscriptionDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn find_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<SubscriptionDto>> {
+    state
+        .services
+        .subscription
+        .find(id, auth.user_id())
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    patch,
+    path = "/{id}",
+    tag = SUBSCRIPTION_TAG,
+    request_body = UpdateSubscriptionRequest,
+    responses(
+         (status = 200, description = "Toggle title and category change notifications", body = SubscriptionDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateSubscriptionRequest>,
+) -> AppResult<Json<SubscriptionDto>> {
+    state
+        .services
+        .subscription
+        .update(id, auth.user_id(), payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/rules",
+    tag = SUBSCRIPTION_TAG,
+    request_body = UpdateSubscriptionRulesRequest,
+    responses(
+         (status = 200, description = "Replace the notification rules of a subscription", body = SubscriptionDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update_rules(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateSubscriptionRulesRequest>,
+) -> AppResult<Json<SubscriptionDto>> {
+    state
+        .services
+        .subscription
+        .update_rules(id, auth.user_id(), payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/group",
+    tag = SUBSCRIPTION_TAG,
+    request_body = AssignSubscriptionGroupRequest,
+    responses(
+         (status = 200, description = "Move a subscription into a group, or out of its group", body = SubscriptionDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update_group(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<AssignSubscriptionGroupRequest>,
+) -> AppResult<Json<SubscriptionDto>> {
+    state
+        .services
+        .subscription
+        .update_group(id, auth.user_id(), payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    put,
+    path = "/{id}/tags",
+    tag = SUBSCRIPTION_TAG,
+    request_body = UpdateSubscriptionTagsRequest,
+    responses(
+         (status = 200, description = "Replace the tags of a subscription", body = SubscriptionDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update_tags(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateSubscriptionTagsRequest>,
+) -> AppResult<Json<SubscriptionDto>> {
+    state
+        .services
+        .subscription
+        .update_tags(id, auth.user_id(), payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = SUBSCRIPTION_TAG,
+    responses(
+         (status = 200, description = "Tags used on the subscriptions of the current user", body = Vec<TagDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn tags(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+) -> AppResult<Json<Vec<TagDto>>> {
+    state
+        .services
+        .subscription
+        .tags(auth.user_id())
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = SUBSCRIPTION_TAG,
+    responses(
+         (status = 204, description = "Unsubscribe from a streamer")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    state
+        .services
+        .subscription
+        .delete(id, auth.user_id())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,135 @@
+use crate::AppState;
+use crate::api::ValidatedJson;
+use crate::api::doc::SUBSCRIPTION_TAG;
+use crate::api::middleware::AuthContext;
+use crate::dto::{
+    CreateSubscriptionGroupRequest, SubscriptionGroupDto, UpdateSubscriptionGroupRequest,
+};
+use crate::error::AppResult;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    post,
+    path = "/",
+    tag = SUBSCRIPTION_TAG,
+    request_body = CreateSubscriptionGroupRequest,
+    responses(
+         (status = 201, description = "Create a subscription group", body = SubscriptionGroupDto),
+         (status = 409, description = "A group with the same name already exists")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn create(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<CreateSubscriptionGroupRequest>,
+) -> AppResult<(StatusCode, Json<SubscriptionGroupDto>)> {
+    state
+        .services
+        .subscription_group
+        .create(auth.user_id(), payload)
+        .await
+        .map(|group| (StatusCode::CREATED, Json(group)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/",
+    tag = SUBSCRIPTION_TAG,
+    responses(
+         (status = 200, description = "List subscription groups of the current user", body = Vec<SubscriptionGroupDto>)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn list(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+) -> AppResult<Json<Vec<SubscriptionGroupDto>>> {
+    state
+        .services
+        .subscription_group
+        .list(auth.user_id())
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    get,
+    path = "/{id}",
+    tag = SUBSCRIPTION_TAG,
+    responses(
+         (status = 200, description = "Find subscription group by id", body = SubscriptionGroupDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn find_by_id(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+) -> AppResult<Json<SubscriptionGroupDto>> {
+    state
+        .services
+        .subscription_group
+        .find(id, auth.user_id())
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    patch,
+    path = "/{id}",
+    tag = SUBSCRIPTION_TAG,
+    request_body = UpdateSubscriptionGroupRequest,
+    responses(
+         (status = 200, description = "Rename a group or replace the rules its subscriptions inherit", body = SubscriptionGroupDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn update(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+    ValidatedJson(payload): ValidatedJson<UpdateSubscriptionGroupRequest>,
+) -> AppResult<Json<SubscriptionGroupDto>> {
+    state
+        .services
+        .subscription_group
+        .update(id, auth.user_id(), payload)
+        .await
+        .map(Json)
+}
+
+#[utoipa::path(
+    delete,
+    path = "/{id}",
+    tag = SUBSCRIPTION_TAG,
+    responses(
+         (status = 204, description = "Delete a group; its subscriptions become ungrouped")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn delete(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Path(id): Path<i32>,
+) -> AppResult<StatusCode> {
+    state
+        .services
+        .subscription_group
+        .delete(id, auth.user_id())
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
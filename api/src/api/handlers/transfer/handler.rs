@@ -0,0 +1,68 @@
+use crate::AppState;
+use crate::api::doc::SUBSCRIPTION_TAG;
+use crate::api::middleware::AuthContext;
+use crate::domain::transfer::TransferDocument;
+use crate::dto::{ImportQuery, ImportReportDto};
+use crate::error::AppResult;
+use axum::extract::{Query, State};
+use axum::http::header::CONTENT_DISPOSITION;
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/export",
+    tag = SUBSCRIPTION_TAG,
+    responses(
+         (status = 200, description = "Subscriptions and notification channels of the current user as a versioned document", body = TransferDocument)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn export(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+) -> AppResult<impl IntoResponse> {
+    let document = state.services.transfer.export(auth.user_id()).await?;
+    Ok((
+        [(
+            CONTENT_DISPOSITION,
+            "attachment; filename=\"fusion-subscriptions.json\"",
+        )],
+        Json(document),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/import",
+    tag = SUBSCRIPTION_TAG,
+    params(ImportQuery),
+    request_body(
+        content = String,
+        description = "An export document, or a plain-text list of room URLs (one per line)",
+        content_type = "text/plain"
+    ),
+    responses(
+         (status = 200, description = "Per-entry outcome of the import; existing subscriptions and channels are never modified", body = ImportReportDto),
+         (status = 400, description = "The body is neither a supported export document nor a URL list")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn import(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    Query(query): Query<ImportQuery>,
+    body: String,
+) -> AppResult<Json<ImportReportDto>> {
+    state
+        .services
+        .transfer
+        .import(auth.user_id(), &body, query.dry_run)
+        .await
+        .map(Json)
+}
@@ -0,0 +1,78 @@
+use crate::AppState;
+use crate::api::ValidatedJson;
+use crate::api::doc::NOTIFICATION_TAG;
+use crate::api::middleware::AuthContext;
+use crate::dto::{
+    NotificationChannelDto, PushSubscriptionRequest, RemovePushSubscriptionRequest, WebPushKeyDto,
+};
+use crate::error::AppResult;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::{Extension, Json};
+use std::sync::Arc;
+
+#[utoipa::path(
+    get,
+    path = "/key",
+    tag = NOTIFICATION_TAG,
+    responses(
+         (status = 200, description = "VAPID public key to subscribe browsers with", body = WebPushKeyDto),
+         (status = 400, description = "Web Push is not configured")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn key(State(state): State<Arc<AppState>>) -> AppResult<Json<WebPushKeyDto>> {
+    state.services.notification_channel.web_push_key().map(Json)
+}
+
+#[utoipa::path(
+    post,
+    path = "/subscriptions",
+    tag = NOTIFICATION_TAG,
+    request_body = PushSubscriptionRequest,
+    responses(
+         (status = 201, description = "Register a browser push subscription for the current user", body = NotificationChannelDto)
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn subscribe(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<PushSubscriptionRequest>,
+) -> AppResult<(StatusCode, Json<NotificationChannelDto>)> {
+    state
+        .services
+        .notification_channel
+        .subscribe_web_push(auth.user_id(), payload)
+        .await
+        .map(|channel| (StatusCode::CREATED, Json(channel)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/subscriptions",
+    tag = NOTIFICATION_TAG,
+    request_body = RemovePushSubscriptionRequest,
+    responses(
+         (status = 204, description = "Remove a browser push subscription")
+    ),
+    security(
+        ("bearerAuth" = [])
+    )
+)]
+pub async fn unsubscribe(
+    State(state): State<Arc<AppState>>,
+    Extension(auth): Extension<AuthContext>,
+    ValidatedJson(payload): ValidatedJson<RemovePushSubscriptionRequest>,
+) -> AppResult<StatusCode> {
+    state
+        .services
+        .notification_channel
+        .unsubscribe_web_push(auth.user_id(), &payload.endpoint)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
@@ -1,15 +1,21 @@
 use crate::AppState;
 use crate::error::AppError;
 use crate::utils::jwt::JwtClaims;
-use axum::extract::{Request, State};
+use axum::extract::{Query, Request, State};
 use axum::http::{HeaderMap, header::AUTHORIZATION};
 use axum::middleware::Next;
 use axum::response::{IntoResponse, Response};
+use serde::Deserialize;
 use std::sync::Arc;
 use tracing::warn;
 
 const BEARER_PREFIX: &str = "Bearer ";
 
+#[derive(Deserialize)]
+struct TokenQuery {
+    access_token: Option<String>,
+}
+
 #[derive(Clone, Debug)]
 pub struct AuthContext {
     pub claims: JwtClaims,
@@ -32,8 +38,65 @@ pub async fn require_auth(
     }
 }
 
+/// Like [`require_auth`], but also accepts the token as `?access_token=` for clients
+/// that cannot set headers, such as the browser `EventSource` and `WebSocket` APIs.
+pub async fn require_auth_or_query(
+    State(state): State<Arc<AppState>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if request.headers().contains_key(AUTHORIZATION) {
+        return require_auth(State(state), request, next).await;
+    }
+
+    let token = match extract_query_token(&request) {
+        Ok(token) => token,
+        Err(err) => return err.into_response(),
+    };
+    match authenticate_token(&state, &token, &mut request) {
+        Ok(_) => next.run(request).await,
+        Err(err) => err.into_response(),
+    }
+}
+
+/// Only lets through the users listed in `[admin] usernames`. Layer it inside
+/// [`require_auth`], which identifies the user.
+pub async fn require_admin(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(user_id) = request
+        .extensions()
+        .get::<AuthContext>()
+        .map(AuthContext::user_id)
+    else {
+        return AppError::Unauthorized("Missing authentication".into()).into_response();
+    };
+    match authorize_admin(&state, user_id).await {
+        Ok(_) => next.run(request).await,
+        Err(err) => err.into_response(),
+    }
+}
+
+async fn authorize_admin(state: &AppState, user_id: i32) -> Result<(), AppError> {
+    let user = state.services.user.find_by_id(user_id).await?;
+    if !state.config.admin.is_admin(&user.username) {
+        return Err(AppError::Forbidden("Administrator access required".into()));
+    }
+    Ok(())
+}
+
 fn authenticate(state: Arc<AppState>, request: &mut Request) -> Result<(), AppError> {
-    let token = extract_bearer_token(request.headers())?;
+    let token = extract_bearer_token(request.headers())?.to_string();
+    authenticate_token(&state, &token, request)
+}
+
+fn authenticate_token(
+    state: &AppState,
+    token: &str,
+    request: &mut Request,
+) -> Result<(), AppError> {
     let claims = state.jwt.decode_access_token(token).map_err(|err| {
         warn!(?err, "failed to decode access token");
         AppError::Unauthorized("Invalid or expired access token".into())
@@ -66,6 +129,17 @@ fn extract_bearer_token(headers: &HeaderMap) -> Result<&str, AppError> {
     Ok(token)
 }
 
+fn extract_query_token(request: &Request) -> Result<String, AppError> {
+    Query::<TokenQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.access_token)
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| {
+            AppError::Unauthorized("Missing Authorization header or access_token".into())
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -98,4 +172,20 @@ mod tests {
         let err = extract_bearer_token(&headers).expect_err("should fail");
         assert!(matches!(err, AppError::Unauthorized(_)));
     }
+
+    #[test]
+    fn extracts_query_token() {
+        let request = Request::builder()
+            .uri("/api/v1/me/events?access_token=abc.def.ghi")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        assert_eq!(extract_query_token(&request).unwrap(), "abc.def.ghi");
+
+        let request = Request::builder()
+            .uri("/api/v1/me/events?access_token=")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let err = extract_query_token(&request).expect_err("should fail");
+        assert!(matches!(err, AppError::Unauthorized(_)));
+    }
 }
@@ -4,6 +4,6 @@ mod trace_middleware;
 
 #[allow(unused_imports)]
 pub use auth_middleware::AuthContext;
-pub use auth_middleware::require_auth;
+pub use auth_middleware::{require_admin, require_auth, require_auth_or_query};
 pub use error_middleware::error_handler;
 pub use trace_middleware::trace;
@@ -4,4 +4,4 @@ mod handlers;
 mod middleware;
 pub mod routes;
 
-pub use extractors::ValidatedJson;
+pub use extractors::{PublicUrl, ValidatedJson};
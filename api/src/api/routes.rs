@@ -19,11 +19,18 @@ use utoipa_swagger_ui::SwaggerUi;
 pub fn create_router(state: Arc<AppState>) -> Router {
     let (mut router, api) = OpenApiRouter::with_openapi(ApiDoc::openapi())
         .routes(routes!(health_check))
+        .routes(routes!(handlers::feed::render))
+        .routes(routes!(handlers::share_page::render))
         .nest(
             "/api/v1",
             OpenApiRouter::new()
                 .nest("/auth", auth_routes())
-                .nest("/user", user_routes(state.clone())),
+                .nest("/user", user_routes(state.clone()))
+                .nest("/streamers", streamer_routes(state.clone()))
+                .nest("/creators", creator_routes(state.clone()))
+                .nest("/media", media_routes())
+                .nest("/me", me_routes(state.clone()))
+                .nest("/admin", admin_routes(state.clone())),
         )
         .split_for_parts();
 
@@ -54,6 +61,119 @@ fn user_routes(state: Arc<AppState>) -> OpenApiRouter<Arc<AppState>> {
         .layer(from_fn_with_state(state, middleware::require_auth))
 }
 
+fn streamer_routes(state: Arc<AppState>) -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(handlers::streamer::create))
+        .routes(routes!(handlers::streamer::list))
+        .routes(routes!(handlers::streamer::find_by_id))
+        .routes(routes!(handlers::streamer::viewer_series))
+        .routes(routes!(handlers::streamer::changes))
+        .routes(routes!(handlers::streamer::live_states))
+        .layer(from_fn_with_state(state, middleware::require_auth))
+}
+
+fn creator_routes(state: Arc<AppState>) -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(handlers::creator::create))
+        .routes(routes!(handlers::creator::list))
+        .routes(routes!(handlers::creator::find_by_id))
+        .routes(routes!(handlers::creator::update))
+        .routes(routes!(handlers::creator::delete))
+        .layer(from_fn_with_state(state, middleware::require_auth))
+}
+
+/// Public, so the images work in `<img>` tags that cannot send a token.
+fn media_routes() -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new().routes(routes!(handlers::media::get))
+}
+
+fn me_routes(state: Arc<AppState>) -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new()
+        .nest(
+            "/subscriptions",
+            OpenApiRouter::new()
+                .routes(routes!(handlers::subscription::create))
+                .routes(routes!(handlers::subscription::list))
+                .routes(routes!(handlers::subscription::find_by_id))
+                .routes(routes!(handlers::subscription::update))
+                .routes(routes!(handlers::subscription::update_rules))
+                .routes(routes!(handlers::subscription::update_group))
+                .routes(routes!(handlers::subscription::update_tags))
+                .routes(routes!(handlers::subscription::delete)),
+        )
+        .nest(
+            "/groups",
+            OpenApiRouter::new()
+                .routes(routes!(handlers::subscription_group::create))
+                .routes(routes!(handlers::subscription_group::list))
+                .routes(routes!(handlers::subscription_group::find_by_id))
+                .routes(routes!(handlers::subscription_group::update))
+                .routes(routes!(handlers::subscription_group::delete)),
+        )
+        .nest(
+            "/tags",
+            OpenApiRouter::new().routes(routes!(handlers::subscription::tags)),
+        )
+        .nest(
+            "/live",
+            OpenApiRouter::new().routes(routes!(handlers::live::list)),
+        )
+        .nest(
+            "/feed",
+            OpenApiRouter::new()
+                .routes(routes!(handlers::feed::token))
+                .routes(routes!(handlers::feed::rotate)),
+        )
+        .nest(
+            "/shares",
+            OpenApiRouter::new()
+                .routes(routes!(handlers::share_page::create))
+                .routes(routes!(handlers::share_page::list))
+                .routes(routes!(handlers::share_page::find_by_id))
+                .routes(routes!(handlers::share_page::rotate))
+                .routes(routes!(handlers::share_page::delete)),
+        )
+        .routes(routes!(handlers::transfer::export))
+        .routes(routes!(handlers::transfer::import))
+        .nest(
+            "/channels",
+            OpenApiRouter::new()
+                .routes(routes!(handlers::notification_channel::create))
+                .routes(routes!(handlers::notification_channel::list))
+                .routes(routes!(handlers::notification_channel::delete))
+                .routes(routes!(handlers::notification_channel::test)),
+        )
+        .nest(
+            "/web-push",
+            OpenApiRouter::new()
+                .routes(routes!(handlers::web_push::key))
+                .routes(routes!(handlers::web_push::subscribe))
+                .routes(routes!(handlers::web_push::unsubscribe)),
+        )
+        .layer(from_fn_with_state(state.clone(), middleware::require_auth))
+        .nest("/events", event_routes(state))
+}
+
+fn event_routes(state: Arc<AppState>) -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new()
+        .routes(routes!(handlers::event::stream))
+        .routes(routes!(handlers::event::websocket))
+        .layer(from_fn_with_state(state, middleware::require_auth_or_query))
+}
+
+fn admin_routes(state: Arc<AppState>) -> OpenApiRouter<Arc<AppState>> {
+    OpenApiRouter::new()
+        .nest(
+            "/outbox",
+            OpenApiRouter::new()
+                .routes(routes!(handlers::notification_outbox::list))
+                .routes(routes!(handlers::notification_outbox::find_by_id))
+                .routes(routes!(handlers::notification_outbox::replay)),
+        )
+        .layer(from_fn_with_state(state.clone(), middleware::require_admin))
+        .layer(from_fn_with_state(state, middleware::require_auth))
+}
+
 fn auth_routes() -> OpenApiRouter<Arc<AppState>> {
     OpenApiRouter::new()
         .routes(routes!(handlers::auth::register))
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AdminConfig {
+    /// Users allowed on the `/api/v1/admin` endpoints.
+    #[serde(default)]
+    pub usernames: Vec<String>,
+}
+
+impl AdminConfig {
+    pub fn is_admin(&self, username: &str) -> bool {
+        self.usernames.iter().any(|admin| admin == username)
+    }
+}
@@ -4,4 +4,8 @@ use serde::Deserialize;
 pub struct ApplicationConfig {
     pub name: String,
     pub version: String,
+    /// Externally reachable base URL, e.g. `https://fusion.example.com`, used in links
+    /// handed out to other clients. Derived from the request headers when unset.
+    #[serde(default)]
+    pub public_url: Option<String>,
 }
@@ -0,0 +1,100 @@
+use chrono_tz::Tz;
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CalendarConfig {
+    /// Time zone used to detect weekly start-time patterns.
+    #[serde(default = "default_timezone")]
+    pub timezone: String,
+
+    /// How far back finished sessions are exported.
+    #[serde(default = "default_history_days")]
+    pub history_days: u32,
+
+    /// How many weeks of history feed the predictions.
+    #[serde(default = "default_lookback_weeks")]
+    pub lookback_weeks: u32,
+
+    /// How far ahead predicted sessions are exported.
+    #[serde(default = "default_horizon_days")]
+    pub horizon_days: u32,
+
+    /// Predictions below this confidence (0.0 - 1.0) are left out. Can be raised
+    /// per calendar with `?min_confidence=`.
+    #[serde(default = "default_min_confidence")]
+    pub min_confidence: f64,
+
+    #[serde(default = "default_min_sessions")]
+    pub min_sessions: usize,
+
+    #[serde(default = "default_tolerance_minutes")]
+    pub tolerance_minutes: i64,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        Self {
+            timezone: default_timezone(),
+            history_days: default_history_days(),
+            lookback_weeks: default_lookback_weeks(),
+            horizon_days: default_horizon_days(),
+            min_confidence: default_min_confidence(),
+            min_sessions: default_min_sessions(),
+            tolerance_minutes: default_tolerance_minutes(),
+        }
+    }
+}
+
+impl CalendarConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.tz()?;
+
+        if self.lookback_weeks == 0 {
+            anyhow::bail!("Calendar lookback must be at least one week");
+        }
+
+        if !(0.0..=1.0).contains(&self.min_confidence) {
+            anyhow::bail!("Calendar min confidence must be between 0 and 1");
+        }
+
+        if !(0..=12 * 60).contains(&self.tolerance_minutes) {
+            anyhow::bail!("Calendar tolerance must be between 0 and 720 minutes");
+        }
+
+        Ok(())
+    }
+
+    pub fn tz(&self) -> anyhow::Result<Tz> {
+        self.timezone
+            .parse()
+            .map_err(|_| anyhow::anyhow!("Unknown calendar timezone: {}", self.timezone))
+    }
+}
+
+fn default_timezone() -> String {
+    "Asia/Shanghai".to_string()
+}
+
+fn default_history_days() -> u32 {
+    90
+}
+
+fn default_lookback_weeks() -> u32 {
+    8
+}
+
+fn default_horizon_days() -> u32 {
+    14
+}
+
+fn default_min_confidence() -> f64 {
+    0.6
+}
+
+fn default_min_sessions() -> usize {
+    3
+}
+
+fn default_tolerance_minutes() -> i64 {
+    90
+}
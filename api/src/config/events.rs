@@ -0,0 +1,42 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EventsConfig {
+    /// Events buffered per listener before slow listeners start missing events.
+    #[serde(default = "default_channel_capacity")]
+    pub channel_capacity: usize,
+
+    #[serde(default = "default_keep_alive")]
+    pub keep_alive_secs: u64,
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            channel_capacity: default_channel_capacity(),
+            keep_alive_secs: default_keep_alive(),
+        }
+    }
+}
+
+impl EventsConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.channel_capacity == 0 {
+            anyhow::bail!("Event channel capacity must be greater than 0");
+        }
+
+        if self.keep_alive_secs == 0 {
+            anyhow::bail!("Event keep-alive interval must be greater than 0");
+        }
+
+        Ok(())
+    }
+}
+
+fn default_channel_capacity() -> usize {
+    256
+}
+
+fn default_keep_alive() -> u64 {
+    15
+}
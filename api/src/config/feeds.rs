@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeedsConfig {
+    #[serde(default = "default_max_entries")]
+    pub max_entries: u64,
+}
+
+impl Default for FeedsConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: default_max_entries(),
+        }
+    }
+}
+
+impl FeedsConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_entries == 0 || self.max_entries > 500 {
+            anyhow::bail!("Feed max entries must be between 1 and 500");
+        }
+
+        Ok(())
+    }
+}
+
+fn default_max_entries() -> u64 {
+    50
+}
@@ -0,0 +1,94 @@
+use crate::domain::live_state::{Confirmation, LiveStateThresholds};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LiveStateConfig {
+    /// Consistent live polls that confirm a room went live.
+    #[serde(default = "default_live_polls")]
+    pub live_polls: u32,
+
+    /// Seconds after the first live poll that confirm a room went live, 0 to
+    /// only count polls.
+    #[serde(default)]
+    pub live_secs: u32,
+
+    /// Consistent offline polls that confirm a room went offline.
+    #[serde(default = "default_offline_polls")]
+    pub offline_polls: u32,
+
+    /// Seconds after the first offline poll that confirm a room went offline, 0
+    /// to only count polls.
+    #[serde(default = "default_offline_secs")]
+    pub offline_secs: u32,
+
+    /// Overrides per platform, keyed by lowercase platform name.
+    #[serde(default)]
+    pub platforms: HashMap<String, LiveStateOverride>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LiveStateOverride {
+    pub live_polls: Option<u32>,
+    pub live_secs: Option<u32>,
+    pub offline_polls: Option<u32>,
+    pub offline_secs: Option<u32>,
+}
+
+impl Default for LiveStateConfig {
+    fn default() -> Self {
+        Self {
+            live_polls: default_live_polls(),
+            live_secs: 0,
+            offline_polls: default_offline_polls(),
+            offline_secs: default_offline_secs(),
+            platforms: HashMap::new(),
+        }
+    }
+}
+
+impl LiveStateConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        let mut polls = [self.live_polls, self.offline_polls].into_iter().chain(
+            self.platforms
+                .values()
+                .flat_map(|platform| [platform.live_polls, platform.offline_polls])
+                .flatten(),
+        );
+        if polls.any(|polls| polls == 0) {
+            anyhow::bail!("Live state changes must be confirmed by at least one poll");
+        }
+
+        Ok(())
+    }
+
+    pub fn thresholds(&self, platform: &str) -> LiveStateThresholds {
+        let platform = self
+            .platforms
+            .get(&platform.to_lowercase())
+            .cloned()
+            .unwrap_or_default();
+        LiveStateThresholds {
+            going_live: Confirmation {
+                polls: platform.live_polls.unwrap_or(self.live_polls),
+                secs: platform.live_secs.unwrap_or(self.live_secs),
+            },
+            going_offline: Confirmation {
+                polls: platform.offline_polls.unwrap_or(self.offline_polls),
+                secs: platform.offline_secs.unwrap_or(self.offline_secs),
+            },
+        }
+    }
+}
+
+fn default_live_polls() -> u32 {
+    2
+}
+
+fn default_offline_polls() -> u32 {
+    3
+}
+
+fn default_offline_secs() -> u32 {
+    90
+}
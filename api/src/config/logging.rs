@@ -651,7 +651,7 @@ fn prune_old_files(base_path: &Path, max_files: usize) -> io::Result<()> {
         files.push((modified, entry.path()));
     }
 
-    files.sort_by(|a, b| b.0.cmp(&a.0));
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
 
     for (_, path) in files.into_iter().skip(max_files) {
         let _ = fs::remove_file(path);
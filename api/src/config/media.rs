@@ -0,0 +1,78 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MediaConfig {
+    /// Directory holding the cached images, created on first use.
+    #[serde(default = "default_cache_dir")]
+    pub cache_dir: String,
+
+    /// Largest upstream image accepted, in bytes.
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: u64,
+
+    #[serde(default = "default_fetch_timeout_secs")]
+    pub fetch_timeout_secs: u64,
+
+    /// `max-age` of proxied images. Their URLs change with the image, so this can be long.
+    #[serde(default = "default_cache_max_age_secs")]
+    pub cache_max_age_secs: u64,
+
+    /// Widths a client may ask for with `?width=`, to keep the number of cached
+    /// variants bounded.
+    #[serde(default = "default_widths")]
+    pub widths: Vec<u32>,
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            cache_dir: default_cache_dir(),
+            max_bytes: default_max_bytes(),
+            fetch_timeout_secs: default_fetch_timeout_secs(),
+            cache_max_age_secs: default_cache_max_age_secs(),
+            widths: default_widths(),
+        }
+    }
+}
+
+impl MediaConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.cache_dir.trim().is_empty() {
+            anyhow::bail!("Media cache dir must not be empty");
+        }
+
+        if self.max_bytes == 0 {
+            anyhow::bail!("Media max bytes must be greater than zero");
+        }
+
+        if self.fetch_timeout_secs == 0 {
+            anyhow::bail!("Media fetch timeout must be at least one second");
+        }
+
+        if self.widths.iter().any(|width| !(16..=2048).contains(width)) {
+            anyhow::bail!("Media widths must be between 16 and 2048 pixels");
+        }
+
+        Ok(())
+    }
+}
+
+fn default_cache_dir() -> String {
+    "data/media".to_string()
+}
+
+fn default_max_bytes() -> u64 {
+    5 * 1024 * 1024
+}
+
+fn default_fetch_timeout_secs() -> u64 {
+    10
+}
+
+fn default_cache_max_age_secs() -> u64 {
+    7 * 24 * 3600
+}
+
+fn default_widths() -> Vec<u32> {
+    vec![64, 160, 320, 640]
+}
@@ -1,20 +1,44 @@
+mod admin;
 mod application;
+mod calendar;
 mod database;
 mod environment;
+mod events;
+mod feeds;
 mod job;
 mod jwt;
+mod live_state;
 pub mod logging;
+mod media;
+mod notification;
+mod polling;
 mod server;
 mod settings;
+mod shares;
+mod viewer_stats;
 
 #[allow(unused_imports)]
 pub use self::{
+    admin::AdminConfig,
     application::ApplicationConfig,
+    calendar::CalendarConfig,
     database::DatabaseConfig,
     environment::{AppEnvironment, ParseEnvironmentError},
+    events::EventsConfig,
+    feeds::FeedsConfig,
     jwt::JwtConfig,
+    live_state::LiveStateConfig,
     logging::{LogLevel, LoggingConfig},
+    media::MediaConfig,
+    notification::{
+        BarkConfig, ChatWebhookConfig, DeliveryConfig, DingTalkConfig, EmailConfig, FeishuConfig,
+        GotifyConfig, NotificationConfig, NtfyConfig, OutboxConfig, PushoverConfig, SmtpSecurity,
+        TelegramConfig, WeComConfig, WebPushConfig, WebhookConfig,
+    },
+    polling::PollingConfig,
     server::ServerConfig,
+    shares::SharesConfig,
+    viewer_stats::ViewerStatsConfig,
 };
 pub use settings::Config;
 
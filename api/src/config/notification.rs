@@ -0,0 +1,751 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use lettre::message::Mailbox;
+use p256::SecretKey;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::notification::NotificationChannel;
+
+/// Settings of the notification providers. A provider whose credentials are
+/// left empty is not registered, and its channels fail to deliver.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct NotificationConfig {
+    #[serde(default)]
+    pub delivery: DeliveryConfig,
+    #[serde(default)]
+    pub outbox: OutboxConfig,
+    #[serde(default)]
+    pub bark: BarkConfig,
+    #[serde(default)]
+    pub telegram: TelegramConfig,
+    #[serde(default)]
+    pub email: EmailConfig,
+    #[serde(default)]
+    pub webhook: WebhookConfig,
+    #[serde(default)]
+    pub discord: ChatWebhookConfig,
+    #[serde(default)]
+    pub slack: ChatWebhookConfig,
+    #[serde(default)]
+    pub dingtalk: DingTalkConfig,
+    #[serde(default)]
+    pub feishu: FeishuConfig,
+    #[serde(default)]
+    pub wecom: WeComConfig,
+    #[serde(default)]
+    pub ntfy: NtfyConfig,
+    #[serde(default)]
+    pub gotify: GotifyConfig,
+    #[serde(default)]
+    pub pushover: PushoverConfig,
+    #[serde(default)]
+    pub web_push: WebPushConfig,
+}
+
+impl NotificationConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        self.delivery.validate()?;
+        self.outbox.validate()?;
+        self.bark.validate()?;
+        self.telegram.validate()?;
+        self.email.validate()?;
+        self.webhook.validate()?;
+        self.discord.validate()?;
+        self.slack.validate()?;
+        self.dingtalk.validate()?;
+        self.feishu.validate()?;
+        self.wecom.validate()?;
+        self.ntfy.validate()?;
+        self.gotify.validate()?;
+        self.pushover.validate()?;
+        self.web_push.validate()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeliveryConfig {
+    /// How many sends may run at once, across all notifications.
+    #[serde(default = "default_delivery_concurrency")]
+    pub concurrency: usize,
+
+    /// Longest a provider may take for one address, retries included.
+    #[serde(default = "default_delivery_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Per-channel overrides of `timeout_secs`, keyed by channel name.
+    #[serde(default)]
+    pub timeouts: HashMap<String, u64>,
+}
+
+impl Default for DeliveryConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: default_delivery_concurrency(),
+            timeout_secs: default_delivery_timeout_secs(),
+            timeouts: HashMap::new(),
+        }
+    }
+}
+
+impl DeliveryConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.concurrency == 0 {
+            anyhow::bail!("Notification delivery concurrency must be at least one");
+        }
+
+        if self.timeout_secs == 0 {
+            anyhow::bail!("Notification delivery timeout must be at least one second");
+        }
+
+        for (channel, timeout_secs) in &self.timeouts {
+            if channel.parse::<NotificationChannel>().is_err() {
+                anyhow::bail!("Unknown notification channel in delivery timeouts: {channel}");
+            }
+            if *timeout_secs == 0 {
+                anyhow::bail!(
+                    "Notification delivery timeout of {channel} must be at least one second"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutboxConfig {
+    /// Attempts before an entry is given up on and left for an admin to replay.
+    #[serde(default = "default_outbox_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Wait after the first failed attempt, doubled after each further one.
+    #[serde(default = "default_outbox_base_backoff_secs")]
+    pub base_backoff_secs: u64,
+
+    #[serde(default = "default_outbox_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+
+    /// Entries taken per dispatcher run.
+    #[serde(default = "default_outbox_batch_size")]
+    pub batch_size: u64,
+
+    /// How long a claimed entry is held before another run may take it over,
+    /// e.g. after a restart in the middle of sending.
+    #[serde(default = "default_outbox_lease_secs")]
+    pub lease_secs: u64,
+}
+
+impl Default for OutboxConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_outbox_max_attempts(),
+            base_backoff_secs: default_outbox_base_backoff_secs(),
+            max_backoff_secs: default_outbox_max_backoff_secs(),
+            batch_size: default_outbox_batch_size(),
+            lease_secs: default_outbox_lease_secs(),
+        }
+    }
+}
+
+impl OutboxConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.max_attempts == 0 {
+            anyhow::bail!("Notification outbox max attempts must be at least one");
+        }
+
+        if self.base_backoff_secs == 0 {
+            anyhow::bail!("Notification outbox backoff must be at least one second");
+        }
+
+        if self.max_backoff_secs < self.base_backoff_secs {
+            anyhow::bail!("Notification outbox max backoff must not be below the base backoff");
+        }
+
+        if !(1..=1000).contains(&self.batch_size) {
+            anyhow::bail!("Notification outbox batch size must be between 1 and 1000");
+        }
+
+        if self.lease_secs == 0 {
+            anyhow::bail!("Notification outbox lease must be at least one second");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BarkConfig {
+    /// Server of channels that name neither a server nor a device url.
+    #[serde(default = "default_bark_base_url")]
+    pub base_url: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Whether channels without an encryption key may push through the
+    /// public relay at api.day.app, which then sees the stream data.
+    #[serde(default)]
+    pub allow_plaintext_relay: bool,
+}
+
+impl Default for BarkConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_bark_base_url(),
+            timeout_secs: default_timeout_secs(),
+            allow_plaintext_relay: false,
+        }
+    }
+}
+
+impl BarkConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_endpoint("Bark", &self.base_url, self.timeout_secs)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TelegramConfig {
+    /// Token issued by @BotFather; the provider is disabled while empty.
+    #[serde(default)]
+    pub bot_token: String,
+
+    /// Bot API endpoint, replaceable by a self-hosted Bot API server.
+    #[serde(default = "default_telegram_base_url")]
+    pub base_url: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// How often a request rejected with 429 is retried after its `retry_after`.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Longest `retry_after` waited for; longer ones fail the delivery instead.
+    #[serde(default = "default_max_retry_after_secs")]
+    pub max_retry_after_secs: u64,
+}
+
+impl Default for TelegramConfig {
+    fn default() -> Self {
+        Self {
+            bot_token: String::new(),
+            base_url: default_telegram_base_url(),
+            timeout_secs: default_timeout_secs(),
+            max_retries: default_max_retries(),
+            max_retry_after_secs: default_max_retry_after_secs(),
+        }
+    }
+}
+
+impl TelegramConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.bot_token.trim().is_empty()
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.base_url.starts_with("https://") && !self.base_url.starts_with("http://") {
+            anyhow::bail!("Telegram base url must be an http(s) url");
+        }
+
+        if self.timeout_secs == 0 {
+            anyhow::bail!("Telegram timeout must be at least one second");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpSecurity {
+    /// Plain connection upgraded with STARTTLS, which the server must offer.
+    #[default]
+    Starttls,
+    /// TLS from the first byte (SMTPS).
+    Tls,
+    /// No encryption, for relays on a trusted network only.
+    None,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmailConfig {
+    /// SMTP server; the provider is disabled while empty.
+    #[serde(default)]
+    pub host: String,
+
+    /// Defaults to 587 for `starttls`, 465 for `tls` and 25 for `none`.
+    #[serde(default)]
+    pub port: Option<u16>,
+
+    #[serde(default)]
+    pub security: SmtpSecurity,
+
+    /// Logs in when set.
+    #[serde(default)]
+    pub username: String,
+
+    #[serde(default)]
+    pub password: String,
+
+    /// Sender, e.g. `Fusion <noreply@example.com>`.
+    #[serde(default = "default_email_from")]
+    pub from: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for EmailConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: None,
+            security: SmtpSecurity::default(),
+            username: String::new(),
+            password: String::new(),
+            from: default_email_from(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl EmailConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.host.trim().is_empty()
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(match self.security {
+            SmtpSecurity::Starttls => 587,
+            SmtpSecurity::Tls => 465,
+            SmtpSecurity::None => 25,
+        })
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        if self.from.parse::<Mailbox>().is_err() {
+            anyhow::bail!("Email sender {:?} is not a valid address", self.from);
+        }
+
+        if self.timeout_secs == 0 {
+            anyhow::bail!("Email timeout must be at least one second");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    /// Limit for the whole request, response included.
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    #[serde(default = "default_connect_timeout_secs")]
+    pub connect_timeout_secs: u64,
+
+    /// Retries after network errors, 429 and 5xx responses. Retries keep the
+    /// idempotency id of the first attempt.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Wait before the first retry, doubled for every further one.
+    #[serde(default = "default_retry_backoff_secs")]
+    pub retry_backoff_secs: u64,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            connect_timeout_secs: default_connect_timeout_secs(),
+            max_retries: default_max_retries(),
+            retry_backoff_secs: default_retry_backoff_secs(),
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.timeout_secs == 0 || self.connect_timeout_secs == 0 {
+            anyhow::bail!("Webhook timeouts must be at least one second");
+        }
+
+        if self.connect_timeout_secs > self.timeout_secs {
+            anyhow::bail!("Webhook connect timeout must not exceed the request timeout");
+        }
+
+        if self.max_retries > 10 {
+            anyhow::bail!("Webhook max retries must be at most 10");
+        }
+
+        Ok(())
+    }
+}
+
+/// Settings of the Discord and Slack incoming webhooks.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChatWebhookConfig {
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// How often a request rejected with 429 is retried once the platform allows it.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+
+    /// Longest wait the platform may ask for; longer ones fail the delivery instead.
+    #[serde(default = "default_max_retry_after_secs")]
+    pub max_retry_after_secs: u64,
+}
+
+impl Default for ChatWebhookConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+            max_retries: default_max_retries(),
+            max_retry_after_secs: default_max_retry_after_secs(),
+        }
+    }
+}
+
+impl ChatWebhookConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.timeout_secs == 0 {
+            anyhow::bail!("Chat webhook timeout must be at least one second");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DingTalkConfig {
+    /// Open API endpoint, replaceable by a test double.
+    #[serde(default = "default_dingtalk_base_url")]
+    pub base_url: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for DingTalkConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_dingtalk_base_url(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl DingTalkConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_endpoint("DingTalk", &self.base_url, self.timeout_secs)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeishuConfig {
+    /// Open platform endpoint of the `feishu` channel.
+    #[serde(default = "default_feishu_base_url")]
+    pub base_url: String,
+
+    /// Endpoint of the `lark` channel, the international edition of Feishu.
+    #[serde(default = "default_lark_base_url")]
+    pub lark_base_url: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for FeishuConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_feishu_base_url(),
+            lark_base_url: default_lark_base_url(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl FeishuConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_endpoint("Feishu", &self.base_url, self.timeout_secs)?;
+        validate_endpoint("Lark", &self.lark_base_url, self.timeout_secs)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WeComConfig {
+    #[serde(default = "default_wecom_base_url")]
+    pub base_url: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for WeComConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_wecom_base_url(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl WeComConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_endpoint("WeCom", &self.base_url, self.timeout_secs)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NtfyConfig {
+    /// Server of destinations that name a bare topic instead of a topic url.
+    #[serde(default = "default_ntfy_base_url")]
+    pub base_url: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for NtfyConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_ntfy_base_url(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl NtfyConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_endpoint("ntfy", &self.base_url, self.timeout_secs)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GotifyConfig {
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for GotifyConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl GotifyConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.timeout_secs == 0 {
+            anyhow::bail!("Gotify timeout must be at least one second");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PushoverConfig {
+    /// Application token; the provider is disabled while empty.
+    #[serde(default)]
+    pub app_token: String,
+
+    #[serde(default = "default_pushover_base_url")]
+    pub base_url: String,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for PushoverConfig {
+    fn default() -> Self {
+        Self {
+            app_token: String::new(),
+            base_url: default_pushover_base_url(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl PushoverConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.app_token.trim().is_empty()
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_endpoint("Pushover", &self.base_url, self.timeout_secs)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebPushConfig {
+    /// Base64url VAPID private key from `fusion vapid-keys`; Web Push is
+    /// disabled while empty. The public key is derived from it.
+    #[serde(default)]
+    pub vapid_private_key: String,
+
+    /// Contact push services reach out to about abuse, `mailto:` or `https:`.
+    #[serde(default = "default_web_push_subject")]
+    pub subject: String,
+
+    /// How long push services hold a message for an offline browser.
+    #[serde(default = "default_web_push_ttl_secs")]
+    pub ttl_secs: u32,
+
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for WebPushConfig {
+    fn default() -> Self {
+        Self {
+            vapid_private_key: String::new(),
+            subject: default_web_push_subject(),
+            ttl_secs: default_web_push_ttl_secs(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl WebPushConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.vapid_private_key.trim().is_empty()
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+
+        let key = URL_SAFE_NO_PAD
+            .decode(self.vapid_private_key.trim())
+            .ok()
+            .and_then(|key| SecretKey::from_slice(&key).ok());
+        if key.is_none() {
+            anyhow::bail!("Web Push VAPID private key must be a base64url P-256 private key");
+        }
+
+        if !self.subject.starts_with("mailto:") && !self.subject.starts_with("https://") {
+            anyhow::bail!("Web Push subject must be a mailto: or https: url");
+        }
+
+        if self.timeout_secs == 0 {
+            anyhow::bail!("Web Push timeout must be at least one second");
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_endpoint(name: &str, base_url: &str, timeout_secs: u64) -> anyhow::Result<()> {
+    if !base_url.starts_with("https://") && !base_url.starts_with("http://") {
+        anyhow::bail!("{name} base url must be an http(s) url");
+    }
+
+    if timeout_secs == 0 {
+        anyhow::bail!("{name} timeout must be at least one second");
+    }
+
+    Ok(())
+}
+
+fn default_delivery_concurrency() -> usize {
+    8
+}
+
+fn default_delivery_timeout_secs() -> u64 {
+    120
+}
+
+fn default_outbox_max_attempts() -> u32 {
+    8
+}
+
+fn default_outbox_base_backoff_secs() -> u64 {
+    30
+}
+
+fn default_outbox_max_backoff_secs() -> u64 {
+    3600
+}
+
+fn default_outbox_batch_size() -> u64 {
+    50
+}
+
+fn default_outbox_lease_secs() -> u64 {
+    600
+}
+
+fn default_bark_base_url() -> String {
+    "https://api.day.app".to_string()
+}
+
+fn default_telegram_base_url() -> String {
+    "https://api.telegram.org".to_string()
+}
+
+fn default_dingtalk_base_url() -> String {
+    "https://oapi.dingtalk.com".to_string()
+}
+
+fn default_feishu_base_url() -> String {
+    "https://open.feishu.cn".to_string()
+}
+
+fn default_lark_base_url() -> String {
+    "https://open.larksuite.com".to_string()
+}
+
+fn default_wecom_base_url() -> String {
+    "https://qyapi.weixin.qq.com".to_string()
+}
+
+fn default_ntfy_base_url() -> String {
+    "https://ntfy.sh".to_string()
+}
+
+fn default_pushover_base_url() -> String {
+    "https://api.pushover.net".to_string()
+}
+
+fn default_web_push_subject() -> String {
+    "mailto:admin@localhost".to_string()
+}
+
+fn default_web_push_ttl_secs() -> u32 {
+    3600
+}
+
+fn default_email_from() -> String {
+    "Fusion <noreply@localhost>".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+fn default_connect_timeout_secs() -> u64 {
+    5
+}
+
+fn default_retry_backoff_secs() -> u64 {
+    2
+}
+
+fn default_max_retries() -> u32 {
+    3
+}
+
+fn default_max_retry_after_secs() -> u64 {
+    60
+}
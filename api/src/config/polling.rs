@@ -0,0 +1,105 @@
+use crate::domain::polling::PollingSettings;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PollingConfig {
+    #[serde(default = "default_min_interval_secs")]
+    pub min_interval_secs: u32,
+
+    #[serde(default = "default_max_interval_secs")]
+    pub max_interval_secs: u32,
+
+    /// Interval for rooms that are live, still bounded by min and max.
+    #[serde(default = "default_live_interval_secs")]
+    pub live_interval_secs: u32,
+
+    /// Subscriber count from which a room counts as fully popular.
+    #[serde(default = "default_popular_subscribers")]
+    pub popular_subscribers: u64,
+
+    /// How many weeks of live sessions estimate the chance of a room being live
+    /// at the current hour.
+    #[serde(default = "default_lookback_weeks")]
+    pub lookback_weeks: u32,
+
+    /// Requests per minute allowed for platforms missing from `budgets`.
+    #[serde(default = "default_budget_per_minute")]
+    pub default_budget_per_minute: u32,
+
+    /// Requests per minute per platform, keyed by lowercase platform name.
+    #[serde(default)]
+    pub budgets: HashMap<String, u32>,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            min_interval_secs: default_min_interval_secs(),
+            max_interval_secs: default_max_interval_secs(),
+            live_interval_secs: default_live_interval_secs(),
+            popular_subscribers: default_popular_subscribers(),
+            lookback_weeks: default_lookback_weeks(),
+            default_budget_per_minute: default_budget_per_minute(),
+            budgets: HashMap::new(),
+        }
+    }
+}
+
+impl PollingConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.min_interval_secs == 0 {
+            anyhow::bail!("Polling min interval must be at least one second");
+        }
+
+        if self.max_interval_secs < self.min_interval_secs {
+            anyhow::bail!("Polling max interval must not be below the min interval");
+        }
+
+        if self.default_budget_per_minute == 0 || self.budgets.values().any(|budget| *budget == 0) {
+            anyhow::bail!("Polling budgets must allow at least one request per minute");
+        }
+
+        Ok(())
+    }
+
+    pub fn settings(&self) -> PollingSettings {
+        PollingSettings {
+            min_interval_secs: self.min_interval_secs,
+            max_interval_secs: self.max_interval_secs,
+            live_interval_secs: self.live_interval_secs,
+            popular_subscribers: self.popular_subscribers,
+        }
+    }
+
+    pub fn budget_per_minute(&self, platform: &str) -> u32 {
+        self.budgets
+            .get(&platform.to_lowercase())
+            .copied()
+            .unwrap_or(self.default_budget_per_minute)
+    }
+}
+
+fn default_min_interval_secs() -> u32 {
+    30
+}
+
+fn default_max_interval_secs() -> u32 {
+    600
+}
+
+fn default_live_interval_secs() -> u32 {
+    60
+}
+
+fn default_popular_subscribers() -> u64 {
+    50
+}
+
+fn default_lookback_weeks() -> u32 {
+    4
+}
+
+fn default_budget_per_minute() -> u32 {
+    60
+}
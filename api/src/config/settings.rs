@@ -1,6 +1,9 @@
 use super::{
-    application::ApplicationConfig, database::DatabaseConfig, environment::AppEnvironment,
-    jwt::JwtConfig, logging::LoggingConfig, server::ServerConfig,
+    admin::AdminConfig, application::ApplicationConfig, calendar::CalendarConfig,
+    database::DatabaseConfig, environment::AppEnvironment, events::EventsConfig,
+    feeds::FeedsConfig, jwt::JwtConfig, live_state::LiveStateConfig, logging::LoggingConfig,
+    media::MediaConfig, notification::NotificationConfig, polling::PollingConfig,
+    server::ServerConfig, shares::SharesConfig, viewer_stats::ViewerStatsConfig,
 };
 use crate::config::job::JobConfig;
 use anyhow::Context;
@@ -20,6 +23,26 @@ pub struct Config {
     pub jwt: JwtConfig,
     #[serde(default)]
     pub jobs: HashMap<String, JobConfig>,
+    #[serde(default)]
+    pub viewer_stats: ViewerStatsConfig,
+    #[serde(default)]
+    pub events: EventsConfig,
+    #[serde(default)]
+    pub feeds: FeedsConfig,
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+    #[serde(default)]
+    pub shares: SharesConfig,
+    #[serde(default)]
+    pub polling: PollingConfig,
+    #[serde(default)]
+    pub live_state: LiveStateConfig,
+    #[serde(default)]
+    pub media: MediaConfig,
+    #[serde(default)]
+    pub notification: NotificationConfig,
+    #[serde(default)]
+    pub admin: AdminConfig,
 }
 
 impl Config {
@@ -77,6 +100,15 @@ impl Config {
 
         self.logging.validate()?;
         self.jwt.validate()?;
+        self.viewer_stats.validate()?;
+        self.events.validate()?;
+        self.feeds.validate()?;
+        self.calendar.validate()?;
+        self.shares.validate()?;
+        self.polling.validate()?;
+        self.live_state.validate()?;
+        self.media.validate()?;
+        self.notification.validate()?;
 
         for (name, job_cfg) in self.jobs.iter() {
             job_cfg
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SharesConfig {
+    /// `max-age` sent with public share pages, so embeds and CDNs can cache them.
+    #[serde(default = "default_cache_max_age_secs")]
+    pub cache_max_age_secs: u64,
+}
+
+impl Default for SharesConfig {
+    fn default() -> Self {
+        Self {
+            cache_max_age_secs: default_cache_max_age_secs(),
+        }
+    }
+}
+
+impl SharesConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.cache_max_age_secs > 3600 {
+            anyhow::bail!("Share page cache max age must be at most 3600 seconds");
+        }
+
+        Ok(())
+    }
+}
+
+fn default_cache_max_age_secs() -> u64 {
+    60
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ViewerStatsConfig {
+    #[serde(default = "default_sample_resolution")]
+    pub sample_resolution_secs: u32,
+
+    #[serde(default = "default_downsample_after")]
+    pub downsample_after_hours: u32,
+
+    #[serde(default = "default_downsample_resolution")]
+    pub downsample_resolution_secs: u32,
+}
+
+impl Default for ViewerStatsConfig {
+    fn default() -> Self {
+        Self {
+            sample_resolution_secs: default_sample_resolution(),
+            downsample_after_hours: default_downsample_after(),
+            downsample_resolution_secs: default_downsample_resolution(),
+        }
+    }
+}
+
+impl ViewerStatsConfig {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.sample_resolution_secs == 0 {
+            anyhow::bail!("Viewer sample resolution must be greater than 0");
+        }
+
+        if self.downsample_resolution_secs < self.sample_resolution_secs
+            || !self
+                .downsample_resolution_secs
+                .is_multiple_of(self.sample_resolution_secs)
+        {
+            anyhow::bail!(
+                "Viewer downsample resolution must be a multiple of the sample resolution ({}s)",
+                self.sample_resolution_secs
+            );
+        }
+
+        if self.downsample_resolution_secs > i32::MAX as u32 {
+            anyhow::bail!("Viewer downsample resolution is too large");
+        }
+
+        Ok(())
+    }
+}
+
+fn default_sample_resolution() -> u32 {
+    60
+}
+
+fn default_downsample_after() -> u32 {
+    48
+}
+
+fn default_downsample_resolution() -> u32 {
+    15 * 60
+}
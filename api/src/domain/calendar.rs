@@ -0,0 +1,160 @@
+use chrono::NaiveDateTime;
+
+/// Maximum line length in octets before content lines are folded (RFC 5545 §3.1).
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: String,
+    pub url: String,
+    /// UTC start time.
+    pub starts_at: NaiveDateTime,
+    /// UTC end time.
+    pub ends_at: NaiveDateTime,
+    /// Predicted events are exported as `TENTATIVE`.
+    pub tentative: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Calendar {
+    pub name: String,
+    pub description: String,
+    pub generated_at: NaiveDateTime,
+    pub events: Vec<CalendarEvent>,
+}
+
+impl Calendar {
+    pub fn to_ics(&self) -> String {
+        let mut lines = vec![
+            "BEGIN:VCALENDAR".to_string(),
+            "VERSION:2.0".to_string(),
+            "PRODID:-//Fusion//Live Sessions//EN".to_string(),
+            "CALSCALE:GREGORIAN".to_string(),
+            "METHOD:PUBLISH".to_string(),
+            format!("X-WR-CALNAME:{}", escape(&self.name)),
+            format!("X-WR-CALDESC:{}", escape(&self.description)),
+            "REFRESH-INTERVAL;VALUE=DURATION:PT1H".to_string(),
+            "X-PUBLISHED-TTL:PT1H".to_string(),
+        ];
+
+        for event in &self.events {
+            lines.push("BEGIN:VEVENT".to_string());
+            lines.push(format!("UID:{}", escape(&event.uid)));
+            lines.push(format!("DTSTAMP:{}", timestamp(self.generated_at)));
+            lines.push(format!("DTSTART:{}", timestamp(event.starts_at)));
+            lines.push(format!("DTEND:{}", timestamp(event.ends_at)));
+            lines.push(format!("SUMMARY:{}", escape(&event.summary)));
+            if !event.description.is_empty() {
+                lines.push(format!("DESCRIPTION:{}", escape(&event.description)));
+            }
+            if !event.url.is_empty() {
+                lines.push(format!("URL:{}", event.url));
+            }
+            lines.push(
+                if event.tentative {
+                    "STATUS:TENTATIVE"
+                } else {
+                    "STATUS:CONFIRMED"
+                }
+                .to_string(),
+            );
+            lines.push("TRANSP:TRANSPARENT".to_string());
+            lines.push("END:VEVENT".to_string());
+        }
+        lines.push("END:VCALENDAR".to_string());
+
+        lines.iter().map(|line| fold(line)).collect()
+    }
+}
+
+fn timestamp(time: NaiveDateTime) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes a TEXT property value.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Folds a content line into CRLF-terminated chunks of at most 75 octets,
+/// never splitting a UTF-8 character.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts towards the limit.
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn escapes_text_values() {
+        assert_eq!(escape("a;b,c\\d\r\ne"), "a\\;b\\,c\\\\d\\ne");
+    }
+
+    #[test]
+    fn folds_long_lines_on_character_boundaries() {
+        let line = format!("SUMMARY:{}", "直播".repeat(30));
+        let folded = fold(&line);
+
+        assert!(folded.ends_with("\r\n"));
+        for chunk in folded.trim_end_matches("\r\n").split("\r\n") {
+            assert!(chunk.len() <= MAX_LINE_OCTETS);
+        }
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+    }
+
+    #[test]
+    fn renders_events() {
+        let start = NaiveDate::from_ymd_opt(2025, 12, 29)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let calendar = Calendar {
+            name: "Fusion".to_string(),
+            description: String::new(),
+            generated_at: start,
+            events: vec![CalendarEvent {
+                uid: "predicted-1-20251229@fusion".to_string(),
+                summary: "alice (predicted)".to_string(),
+                description: String::new(),
+                url: "https://live.bilibili.com/1".to_string(),
+                starts_at: start,
+                ends_at: start + chrono::Duration::hours(2),
+                tentative: true,
+            }],
+        };
+
+        let ics = calendar.to_ics();
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.contains("DTSTART:20251229T120000Z\r\n"));
+        assert!(ics.contains("DTEND:20251229T140000Z\r\n"));
+        assert!(ics.contains("STATUS:TENTATIVE\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+}
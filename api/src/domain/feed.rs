@@ -0,0 +1,277 @@
+use chrono::NaiveDateTime;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedFormat {
+    Atom,
+    Rss,
+}
+
+impl FeedFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension {
+            "atom" => Some(FeedFormat::Atom),
+            "rss" => Some(FeedFormat::Rss),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "atom",
+            FeedFormat::Rss => "rss",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            FeedFormat::Atom => "application/atom+xml; charset=utf-8",
+            FeedFormat::Rss => "application/rss+xml; charset=utf-8",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    /// Stable, globally unique id of the entry.
+    pub id: String,
+    pub title: String,
+    pub author: String,
+    pub link: String,
+    /// Plain-text lines rendered into the entry summary.
+    pub summary: Vec<String>,
+    pub image: Option<String>,
+    pub published: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub title: String,
+    pub description: String,
+    pub self_url: String,
+    pub site_url: String,
+    pub updated: NaiveDateTime,
+    pub entries: Vec<FeedEntry>,
+}
+
+impl Feed {
+    pub fn render(&self, format: FeedFormat) -> String {
+        match format {
+            FeedFormat::Atom => self.to_atom(),
+            FeedFormat::Rss => self.to_rss(),
+        }
+    }
+
+    pub fn to_atom(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        let _ = writeln!(xml, "  <id>{}</id>", escape(&self.self_url));
+        let _ = writeln!(xml, "  <title>{}</title>", escape(&self.title));
+        let _ = writeln!(xml, "  <subtitle>{}</subtitle>", escape(&self.description));
+        let _ = writeln!(xml, "  <updated>{}</updated>", rfc3339(self.updated));
+        let _ = writeln!(
+            xml,
+            "  <link rel=\"self\" href=\"{}\"/>",
+            escape(&self.self_url)
+        );
+        let _ = writeln!(xml, "  <link href=\"{}\"/>", escape(&self.site_url));
+
+        for entry in &self.entries {
+            xml.push_str("  <entry>\n");
+            let _ = writeln!(xml, "    <id>{}</id>", escape(&entry.id));
+            let _ = writeln!(xml, "    <title>{}</title>", escape(&entry.title));
+            let _ = writeln!(
+                xml,
+                "    <author><name>{}</name></author>",
+                escape(&entry.author)
+            );
+            let _ = writeln!(xml, "    <link href=\"{}\"/>", escape(&entry.link));
+            if let Some(image) = &entry.image {
+                let _ = writeln!(
+                    xml,
+                    "    <link rel=\"enclosure\" type=\"{}\" href=\"{}\"/>",
+                    image_type(image),
+                    escape(image)
+                );
+            }
+            let _ = writeln!(
+                xml,
+                "    <published>{}</published>",
+                rfc3339(entry.published)
+            );
+            let _ = writeln!(xml, "    <updated>{}</updated>", rfc3339(entry.published));
+            let _ = writeln!(
+                xml,
+                "    <summary type=\"html\">{}</summary>",
+                escape(&summary_html(entry))
+            );
+            xml.push_str("  </entry>\n");
+        }
+
+        xml.push_str("</feed>\n");
+        xml
+    }
+
+    pub fn to_rss(&self) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n");
+        xml.push_str("  <channel>\n");
+        let _ = writeln!(xml, "    <title>{}</title>", escape(&self.title));
+        let _ = writeln!(xml, "    <link>{}</link>", escape(&self.site_url));
+        let _ = writeln!(
+            xml,
+            "    <description>{}</description>",
+            escape(&self.description)
+        );
+        let _ = writeln!(
+            xml,
+            "    <atom:link rel=\"self\" type=\"application/rss+xml\" href=\"{}\"/>",
+            escape(&self.self_url)
+        );
+        let _ = writeln!(
+            xml,
+            "    <lastBuildDate>{}</lastBuildDate>",
+            rfc2822(self.updated)
+        );
+
+        for entry in &self.entries {
+            xml.push_str("    <item>\n");
+            let _ = writeln!(xml, "      <title>{}</title>", escape(&entry.title));
+            let _ = writeln!(xml, "      <link>{}</link>", escape(&entry.link));
+            let _ = writeln!(
+                xml,
+                "      <guid isPermaLink=\"false\">{}</guid>",
+                escape(&entry.id)
+            );
+            let _ = writeln!(xml, "      <pubDate>{}</pubDate>", rfc2822(entry.published));
+            let _ = writeln!(
+                xml,
+                "      <description>{}</description>",
+                escape(&summary_html(entry))
+            );
+            if let Some(image) = &entry.image {
+                let _ = writeln!(
+                    xml,
+                    "      <enclosure url=\"{}\" type=\"{}\" length=\"0\"/>",
+                    escape(image),
+                    image_type(image)
+                );
+            }
+            xml.push_str("    </item>\n");
+        }
+
+        xml.push_str("  </channel>\n</rss>\n");
+        xml
+    }
+}
+
+fn summary_html(entry: &FeedEntry) -> String {
+    let mut html: String = entry
+        .summary
+        .iter()
+        .filter(|line| !line.is_empty())
+        .map(|line| format!("<p>{}</p>", escape(line)))
+        .collect();
+    if let Some(image) = &entry.image {
+        let _ = write!(html, "<p><img src=\"{}\" alt=\"\"/></p>", escape(image));
+    }
+    html
+}
+
+fn image_type(url: &str) -> &'static str {
+    let path = url
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(url)
+        .to_ascii_lowercase();
+    if path.ends_with(".png") {
+        "image/png"
+    } else if path.ends_with(".webp") {
+        "image/webp"
+    } else if path.ends_with(".gif") {
+        "image/gif"
+    } else {
+        "image/jpeg"
+    }
+}
+
+fn rfc3339(time: NaiveDateTime) -> String {
+    time.and_utc()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+fn rfc2822(time: NaiveDateTime) -> String {
+    time.and_utc().to_rfc2822()
+}
+
+/// Escapes text for use in XML element content and attribute values.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if (c as u32) < 0x20 && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn feed() -> Feed {
+        let published = NaiveDate::from_ymd_opt(2025, 12, 27)
+            .unwrap()
+            .and_hms_opt(12, 30, 0)
+            .unwrap();
+        Feed {
+            title: "Fusion".to_string(),
+            description: "Live events".to_string(),
+            self_url: "https://fusion.example.com/feeds/abc.atom".to_string(),
+            site_url: "https://fusion.example.com".to_string(),
+            updated: published,
+            entries: vec![FeedEntry {
+                id: "tag:fusion,2025:live-session/1".to_string(),
+                title: "Tom & Jerry is live".to_string(),
+                author: "Tom & Jerry".to_string(),
+                link: "https://live.bilibili.com/1?a=1&b=2".to_string(),
+                summary: vec!["<Chase> night".to_string()],
+                image: Some("https://i0.hdslb.com/cover.png".to_string()),
+                published,
+            }],
+        }
+    }
+
+    #[test]
+    fn escapes_markup() {
+        assert_eq!(
+            escape("<a href=\"x\">Tom & 'Jerry'</a>\u{1}"),
+            "&lt;a href=&quot;x&quot;&gt;Tom &amp; &apos;Jerry&apos;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn renders_atom() {
+        let xml = feed().to_atom();
+        assert!(xml.contains("<title>Tom &amp; Jerry is live</title>"));
+        assert!(xml.contains("<link href=\"https://live.bilibili.com/1?a=1&amp;b=2\"/>"));
+        assert!(xml.contains("type=\"image/png\" href=\"https://i0.hdslb.com/cover.png\""));
+        assert!(xml.contains("<published>2025-12-27T12:30:00Z</published>"));
+        assert!(xml.contains("&lt;p&gt;&amp;lt;Chase&amp;gt; night&lt;/p&gt;"));
+    }
+
+    #[test]
+    fn renders_rss() {
+        let xml = feed().to_rss();
+        assert!(xml.contains("<pubDate>Sat, 27 Dec 2025 12:30:00 +0000</pubDate>"));
+        assert!(xml.contains("<guid isPermaLink=\"false\">tag:fusion,2025:live-session/1</guid>"));
+        assert!(xml.contains("<enclosure url=\"https://i0.hdslb.com/cover.png\""));
+    }
+}
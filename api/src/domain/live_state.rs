@@ -0,0 +1,313 @@
+use chrono::NaiveDateTime;
+use entity::streamer::Model;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Live state of a room. The `Maybe*` states hold a change the polls have seen
+/// but not confirmed yet; until then the room keeps its previous confirmed state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveState {
+    Offline,
+    MaybeLive,
+    Live,
+    MaybeOffline,
+}
+
+impl LiveState {
+    pub const ALL: [LiveState; 4] = [
+        LiveState::Offline,
+        LiveState::MaybeLive,
+        LiveState::Live,
+        LiveState::MaybeOffline,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LiveState::Offline => "offline",
+            LiveState::MaybeLive => "maybe_live",
+            LiveState::Live => "live",
+            LiveState::MaybeOffline => "maybe_offline",
+        }
+    }
+
+    /// The confirmed live flag of the state.
+    pub fn is_live(&self) -> bool {
+        matches!(self, LiveState::Live | LiveState::MaybeOffline)
+    }
+
+    /// Whether a change is waiting for confirmation.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, LiveState::MaybeLive | LiveState::MaybeOffline)
+    }
+}
+
+impl fmt::Display for LiveState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LiveState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|state| state.as_str() == s)
+            .ok_or_else(|| format!("unknown live state: {s}"))
+    }
+}
+
+/// When a pending change counts as confirmed: after `polls` consistent polls,
+/// or once it has been seen for `secs` seconds, whichever comes first. A
+/// `secs` of zero only counts polls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Confirmation {
+    pub polls: u32,
+    pub secs: u32,
+}
+
+impl Confirmation {
+    fn is_met(&self, polls: u32, since: NaiveDateTime, now: NaiveDateTime) -> bool {
+        polls >= self.polls
+            || (self.secs > 0 && (now - since).num_seconds() >= i64::from(self.secs))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiveStateThresholds {
+    pub going_live: Confirmation,
+    pub going_offline: Confirmation,
+}
+
+/// Persisted progress of the state machine for one room.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiveTracker {
+    pub state: LiveState,
+    /// When the current state was entered. For a confirmed change, this is when
+    /// the change was first seen.
+    pub since: Option<NaiveDateTime>,
+    /// Consistent polls seen in a pending state.
+    pub polls: u32,
+}
+
+/// Confirmed change of the live flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LiveEdge {
+    /// The room went live at `since`, when the first poll saw it live.
+    WentLive { since: NaiveDateTime },
+    /// The room went offline at `since`, when the first poll saw it offline.
+    WentOffline { since: NaiveDateTime },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LiveStep {
+    pub tracker: LiveTracker,
+    pub edge: Option<LiveEdge>,
+}
+
+impl LiveTracker {
+    pub fn new(state: LiveState, since: Option<NaiveDateTime>, polls: u32) -> Self {
+        Self {
+            state,
+            since,
+            polls,
+        }
+    }
+
+    /// Progress stored on the streamer row. Rows with an unknown state fall back
+    /// to their confirmed live flag.
+    pub fn of(streamer: &Model) -> Self {
+        let state = streamer.live_state.parse().unwrap_or(if streamer.is_live {
+            LiveState::Live
+        } else {
+            LiveState::Offline
+        });
+        Self::new(
+            state,
+            streamer.live_state_since,
+            u32::try_from(streamer.live_state_polls).unwrap_or(0),
+        )
+    }
+
+    /// Feeds one poll into the state machine.
+    pub fn observe(
+        &self,
+        observed_live: bool,
+        now: NaiveDateTime,
+        thresholds: &LiveStateThresholds,
+    ) -> LiveStep {
+        let (state, polls) = match (self.state, observed_live) {
+            (LiveState::Offline, false) | (LiveState::Live, true) => return self.stay(),
+            (LiveState::Offline, true) => (LiveState::MaybeLive, 1),
+            (LiveState::Live, false) => (LiveState::MaybeOffline, 1),
+            (LiveState::MaybeLive, true) | (LiveState::MaybeOffline, false) => {
+                (self.state, self.polls + 1)
+            }
+            (LiveState::MaybeLive, false) => return Self::settle(LiveState::Offline, now),
+            (LiveState::MaybeOffline, true) => return Self::settle(LiveState::Live, now),
+        };
+
+        let since = if self.state == state {
+            self.since.unwrap_or(now)
+        } else {
+            now
+        };
+        let confirmation = match state {
+            LiveState::MaybeLive => thresholds.going_live,
+            _ => thresholds.going_offline,
+        };
+        if !confirmation.is_met(polls, since, now) {
+            return LiveStep {
+                tracker: Self::new(state, Some(since), polls),
+                edge: None,
+            };
+        }
+
+        let (state, edge) = match state {
+            LiveState::MaybeLive => (LiveState::Live, LiveEdge::WentLive { since }),
+            _ => (LiveState::Offline, LiveEdge::WentOffline { since }),
+        };
+        LiveStep {
+            tracker: Self::new(state, Some(since), 0),
+            edge: Some(edge),
+        }
+    }
+
+    fn stay(&self) -> LiveStep {
+        LiveStep {
+            tracker: *self,
+            edge: None,
+        }
+    }
+
+    /// Drops a pending change that the latest poll contradicted.
+    fn settle(state: LiveState, now: NaiveDateTime) -> LiveStep {
+        LiveStep {
+            tracker: Self::new(state, Some(now), 0),
+            edge: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn start() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, 31)
+            .unwrap()
+            .and_hms_opt(20, 0, 0)
+            .unwrap()
+    }
+
+    fn thresholds() -> LiveStateThresholds {
+        LiveStateThresholds {
+            going_live: Confirmation { polls: 2, secs: 0 },
+            going_offline: Confirmation { polls: 3, secs: 90 },
+        }
+    }
+
+    /// Runs `(seconds from start, observed live)` polls and returns the edges
+    /// with their offsets and the final state.
+    fn run(
+        initial: LiveState,
+        polls: &[(i64, bool)],
+        thresholds: &LiveStateThresholds,
+    ) -> (Vec<(i64, LiveEdge)>, LiveState) {
+        let mut tracker = LiveTracker::new(initial, None, 0);
+        let mut edges = Vec::new();
+        for (secs, live) in polls {
+            let step = tracker.observe(*live, start() + Duration::seconds(*secs), thresholds);
+            if let Some(edge) = step.edge {
+                edges.push((*secs, edge));
+            }
+            tracker = step.tracker;
+        }
+        (edges, tracker.state)
+    }
+
+    fn at(secs: i64) -> NaiveDateTime {
+        start() + Duration::seconds(secs)
+    }
+
+    #[test]
+    fn confirms_going_live_after_consistent_polls() {
+        let (edges, state) = run(
+            LiveState::Offline,
+            &[(0, false), (30, true), (60, true), (90, true)],
+            &thresholds(),
+        );
+
+        assert_eq!(edges, vec![(60, LiveEdge::WentLive { since: at(30) })]);
+        assert_eq!(state, LiveState::Live);
+    }
+
+    #[test]
+    fn ignores_short_drops_and_blips() {
+        // A live stream that drops for 30 seconds.
+        let (edges, state) = run(
+            LiveState::Live,
+            &[(0, true), (30, false), (60, false), (90, true), (120, true)],
+            &thresholds(),
+        );
+        assert!(edges.is_empty());
+        assert_eq!(state, LiveState::Live);
+
+        // An offline room that shows up live for a single poll.
+        let (edges, state) = run(
+            LiveState::Offline,
+            &[(0, true), (30, false), (60, true), (90, false)],
+            &thresholds(),
+        );
+        assert!(edges.is_empty());
+        assert_eq!(state, LiveState::Offline);
+    }
+
+    #[test]
+    fn confirms_going_offline_after_polls_or_time() {
+        let (edges, state) = run(
+            LiveState::Live,
+            &[(0, false), (15, false), (30, false)],
+            &thresholds(),
+        );
+        assert_eq!(edges, vec![(30, LiveEdge::WentOffline { since: at(0) })]);
+        assert_eq!(state, LiveState::Offline);
+
+        // Sparse polls confirm on time before enough polls are seen.
+        let (edges, _) = run(LiveState::Live, &[(0, false), (120, false)], &thresholds());
+        assert_eq!(edges, vec![(120, LiveEdge::WentOffline { since: at(0) })]);
+    }
+
+    #[test]
+    fn single_poll_threshold_confirms_immediately() {
+        let immediate = LiveStateThresholds {
+            going_live: Confirmation { polls: 1, secs: 0 },
+            going_offline: Confirmation { polls: 1, secs: 0 },
+        };
+        let (edges, state) = run(LiveState::Offline, &[(0, true), (30, false)], &immediate);
+
+        assert_eq!(
+            edges,
+            vec![
+                (0, LiveEdge::WentLive { since: at(0) }),
+                (30, LiveEdge::WentOffline { since: at(30) }),
+            ]
+        );
+        assert_eq!(state, LiveState::Offline);
+    }
+
+    #[test]
+    fn parses_stored_states() {
+        for state in LiveState::ALL {
+            assert_eq!(state.as_str().parse::<LiveState>(), Ok(state));
+        }
+        assert!("unknown".parse::<LiveState>().is_err());
+        assert!(LiveState::MaybeOffline.is_live());
+        assert!(!LiveState::MaybeLive.is_live());
+    }
+}
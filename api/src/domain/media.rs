@@ -0,0 +1,144 @@
+use crate::error::Entity;
+use entity::media_asset::Model;
+use image::ImageFormat;
+use image::imageops::FilterType;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::Cursor;
+use std::str::FromStr;
+
+impl Entity for Model {
+    const NAME: &'static str = "media_asset";
+}
+
+/// Kind of remote image served through the media proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaKind {
+    Avatar,
+    Cover,
+}
+
+impl MediaKind {
+    pub const ALL: [MediaKind; 2] = [MediaKind::Avatar, MediaKind::Cover];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MediaKind::Avatar => "avatar",
+            MediaKind::Cover => "cover",
+        }
+    }
+}
+
+impl fmt::Display for MediaKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MediaKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == s)
+            .ok_or_else(|| format!("unknown media kind: {s}"))
+    }
+}
+
+/// Key of a remote image in the cache: the hex SHA-256 of its URL.
+pub fn hash(url: &str) -> String {
+    format!("{:x}", Sha256::digest(url.as_bytes()))
+}
+
+/// Path of the proxied copy of `url`, `None` when the platform gave no image.
+pub fn proxy_path(kind: MediaKind, url: &str) -> Option<String> {
+    if url.is_empty() {
+        return None;
+    }
+    Some(format!("/api/v1/media/{kind}/{}", hash(url)))
+}
+
+/// Content type of a supported image, detected from its leading bytes rather
+/// than trusted from the upstream headers.
+pub fn sniff_content_type(bytes: &[u8]) -> Option<&'static str> {
+    match image::guess_format(bytes).ok()? {
+        ImageFormat::Jpeg => Some("image/jpeg"),
+        ImageFormat::Png => Some("image/png"),
+        ImageFormat::Gif => Some("image/gif"),
+        ImageFormat::WebP => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Scales the image down to `width`, keeping its aspect ratio. JPEGs stay
+/// JPEGs, anything else becomes a PNG. Returns `None` when the image is not
+/// wider than `width`.
+pub fn resize(bytes: &[u8], width: u32) -> anyhow::Result<Option<Vec<u8>>> {
+    let format = image::guess_format(bytes)?;
+    let image = image::load_from_memory_with_format(bytes, format)?;
+    if image.width() <= width {
+        return Ok(None);
+    }
+
+    let height = (u64::from(image.height()) * u64::from(width) / u64::from(image.width())).max(1);
+    let resized = image.resize_exact(width, height as u32, FilterType::Lanczos3);
+    let mut encoded = Cursor::new(Vec::new());
+    match format {
+        // JPEG has no alpha channel.
+        ImageFormat::Jpeg => resized
+            .to_rgb8()
+            .write_to(&mut encoded, ImageFormat::Jpeg)?,
+        _ => resized.write_to(&mut encoded, ImageFormat::Png)?,
+    }
+    Ok(Some(encoded.into_inner()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageBuffer, Rgba};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = ImageBuffer::from_pixel(width, height, Rgba([200u8, 40, 40, 255]));
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn hashes_urls_into_proxy_paths() {
+        let url = "https://i0.hdslb.com/bfs/face/a.jpg";
+
+        assert_eq!(hash(url).len(), 64);
+        assert_eq!(hash(url), hash(url));
+        assert_ne!(hash(url), hash("https://i0.hdslb.com/bfs/face/b.jpg"));
+        assert_eq!(
+            proxy_path(MediaKind::Cover, url),
+            Some(format!("/api/v1/media/cover/{}", hash(url)))
+        );
+        assert_eq!(proxy_path(MediaKind::Avatar, ""), None);
+        assert_eq!("avatar".parse(), Ok(MediaKind::Avatar));
+        assert!("banner".parse::<MediaKind>().is_err());
+    }
+
+    #[test]
+    fn sniffs_supported_images_only() {
+        assert_eq!(sniff_content_type(&png(2, 2)), Some("image/png"));
+        assert_eq!(
+            sniff_content_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0, 0x10]),
+            Some("image/jpeg")
+        );
+        assert_eq!(sniff_content_type(b"GIF89a......"), Some("image/gif"));
+        assert_eq!(sniff_content_type(b"<!DOCTYPE html>"), None);
+    }
+
+    #[test]
+    fn resizes_only_wider_images() {
+        let resized = resize(&png(400, 200), 100).unwrap().unwrap();
+        let image = image::load_from_memory(&resized).unwrap();
+        assert_eq!((image.width(), image.height()), (100, 50));
+
+        assert!(resize(&png(80, 80), 100).unwrap().is_none());
+    }
+}
@@ -1 +1,19 @@
+pub mod calendar;
+mod creator;
+pub mod feed;
+pub mod live_state;
+pub mod media;
+mod notification_channel;
+pub mod notification_outbox;
+pub mod notification_rules;
+pub mod polling;
+pub mod schedule;
+pub mod share_page;
+mod streamer;
+pub mod streamer_changes;
+mod subscription;
+mod subscription_group;
+pub mod tags;
+pub mod transfer;
 mod user;
+pub mod viewer_series;
@@ -0,0 +1,6 @@
+use crate::error::Entity;
+use entity::notification_channel::Model;
+
+impl Entity for Model {
+    const NAME: &'static str = "notification_channel";
+}
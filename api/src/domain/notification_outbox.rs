@@ -0,0 +1,245 @@
+use crate::error::Entity;
+use entity::live_session::Model as LiveSessionModel;
+use entity::notification_outbox::Model;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+impl Entity for Model {
+    const NAME: &'static str = "notification_outbox";
+}
+
+/// Detection event an outbox entry notifies about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxEvent {
+    /// The streamer went live; the entry points at the opened session.
+    Live,
+    /// Title, category or announcement changes; the entry lists the change ids.
+    Change,
+}
+
+impl OutboxEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxEvent::Live => "live",
+            OutboxEvent::Change => "change",
+        }
+    }
+}
+
+impl FromStr for OutboxEvent {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "live" => Ok(OutboxEvent::Live),
+            "change" => Ok(OutboxEvent::Change),
+            _ => Err(format!("unknown outbox event: {s}")),
+        }
+    }
+}
+
+/// Delivery state of an outbox entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OutboxStatus {
+    /// Waiting for its first or next attempt.
+    Pending,
+    /// Nothing is left to send.
+    Delivered,
+    /// Gave up after the maximum number of attempts, or every recipient
+    /// failed for good before anyone was reached; can be replayed.
+    Dead,
+}
+
+impl OutboxStatus {
+    pub const ALL: [OutboxStatus; 3] = [
+        OutboxStatus::Pending,
+        OutboxStatus::Delivered,
+        OutboxStatus::Dead,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Delivered => "delivered",
+            OutboxStatus::Dead => "dead",
+        }
+    }
+}
+
+impl fmt::Display for OutboxStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutboxStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|status| status.as_str() == s)
+            .ok_or_else(|| format!("unknown outbox status: {s}"))
+    }
+}
+
+/// A user the entry still has to reach, stored once the event was resolved so
+/// retries send the same message and skip the channels that already got it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutboxRecipient {
+    pub subscription_id: i32,
+    pub user_id: i32,
+    pub title: String,
+    pub body: String,
+    pub url: Option<String>,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    /// Channels left to try, `None` for every enabled channel of the user.
+    pub channel_ids: Option<Vec<i32>>,
+}
+
+/// Delay before the next attempt after `attempts` failed ones: `base` doubled
+/// per attempt, capped at `max`.
+pub fn backoff(attempts: u32, base: Duration, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    base.saturating_mul(factor).min(max)
+}
+
+/// State an entry is left in after `attempts` attempts. `retrying` tells
+/// whether recipients are left to try again, `reached` whether the
+/// notification got to anyone so far, and `failed` whether a recipient failed
+/// for good.
+pub fn status_after(
+    attempts: u32,
+    max_attempts: u32,
+    retrying: bool,
+    reached: bool,
+    failed: bool,
+) -> OutboxStatus {
+    if retrying {
+        if attempts >= max_attempts {
+            OutboxStatus::Dead
+        } else {
+            OutboxStatus::Pending
+        }
+    } else if failed && !reached {
+        OutboxStatus::Dead
+    } else {
+        OutboxStatus::Delivered
+    }
+}
+
+/// Whether the creator was already live on another of its rooms when `session`
+/// started, going by the sessions of those rooms. Sessions that started at the
+/// same time go by id, so one of them still notifies.
+pub fn creator_already_live(session: &LiveSessionModel, sessions: &[LiveSessionModel]) -> bool {
+    sessions.iter().any(|other| {
+        other.streamer_id != session.streamer_id
+            && (other.started_at, other.id) < (session.started_at, session.id)
+            && other
+                .ended_at
+                .is_none_or(|ended_at| ended_at > session.started_at)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    fn at(minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, 31)
+            .unwrap()
+            .and_hms_opt(20, minute, 0)
+            .unwrap()
+    }
+
+    fn session(
+        id: i32,
+        streamer_id: i32,
+        started_at: u32,
+        ended_at: Option<u32>,
+    ) -> LiveSessionModel {
+        LiveSessionModel {
+            id,
+            streamer_id,
+            started_at: at(started_at),
+            ended_at: ended_at.map(at),
+            title: String::new(),
+            category: String::new(),
+            cover_image: String::new(),
+        }
+    }
+
+    #[test]
+    fn doubles_backoff_up_to_the_cap() {
+        let base = Duration::from_secs(30);
+        let max = Duration::from_secs(3600);
+
+        assert_eq!(backoff(1, base, max), Duration::from_secs(30));
+        assert_eq!(backoff(2, base, max), Duration::from_secs(60));
+        assert_eq!(backoff(4, base, max), Duration::from_secs(240));
+        assert_eq!(backoff(8, base, max), max);
+        assert_eq!(backoff(100, base, max), max);
+    }
+
+    #[test]
+    fn notifies_the_first_room_of_a_creator_to_go_live() {
+        // Both rooms went live before the dispatcher ran.
+        let first = session(1, 10, 0, None);
+        let second = session(2, 11, 1, None);
+        let sessions = [first.clone(), second.clone()];
+
+        assert!(!creator_already_live(&first, &sessions));
+        assert!(creator_already_live(&second, &sessions));
+
+        let same_time = session(3, 12, 0, None);
+        assert!(creator_already_live(
+            &same_time,
+            &[first.clone(), same_time.clone()]
+        ));
+        assert!(!creator_already_live(&first, &[first.clone(), same_time]));
+    }
+
+    #[test]
+    fn ignores_sessions_that_ended_before_the_start() {
+        let ended = session(1, 10, 0, Some(5));
+        let later = session(2, 11, 5, None);
+        assert!(!creator_already_live(&later, &[ended, later.clone()]));
+
+        let overlapping = session(3, 10, 0, Some(6));
+        assert!(creator_already_live(&later, &[overlapping, later.clone()]));
+        // The room's own earlier sessions do not count.
+        assert!(!creator_already_live(&later, &[session(4, 11, 0, None)]));
+    }
+
+    #[test]
+    fn settles_entries_by_what_reached_the_recipients() {
+        assert_eq!(status_after(1, 5, true, true, false), OutboxStatus::Pending);
+        assert_eq!(status_after(5, 5, true, true, false), OutboxStatus::Dead);
+        assert_eq!(
+            status_after(1, 5, false, true, true),
+            OutboxStatus::Delivered
+        );
+        // Nobody to send to, such as subscribers without enabled channels.
+        assert_eq!(
+            status_after(1, 5, false, false, false),
+            OutboxStatus::Delivered
+        );
+        // Every recipient rejected the notification.
+        assert_eq!(status_after(1, 5, false, false, true), OutboxStatus::Dead);
+    }
+
+    #[test]
+    fn parses_statuses() {
+        for status in OutboxStatus::ALL {
+            assert_eq!(status.as_str().parse::<OutboxStatus>(), Ok(status));
+        }
+        assert!("failed".parse::<OutboxStatus>().is_err());
+    }
+}
@@ -0,0 +1,365 @@
+use chrono::{NaiveDateTime, NaiveTime, TimeZone};
+use chrono_tz::Tz;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use utoipa::ToSchema;
+
+/// Filters evaluated for a subscription before a notification is sent.
+///
+/// Every rule is optional; an empty rule set lets every notification through.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct NotificationRules {
+    /// IANA timezone used for quiet hours and "first live of the day", e.g. `Asia/Shanghai`.
+    /// Defaults to UTC.
+    #[schema(example = "Asia/Shanghai")]
+    pub timezone: Option<String>,
+    pub quiet_hours: Option<QuietHours>,
+    /// Regexes matched against the stream title; at least one must match when set.
+    pub title_include: Vec<String>,
+    /// Regexes matched against the stream title; none may match.
+    pub title_exclude: Vec<String>,
+    /// Categories (case-insensitive) the stream must be in when set.
+    pub category_allowlist: Vec<String>,
+    /// Minimum number of seconds between two notifications for the subscription.
+    pub min_gap_secs: Option<u64>,
+    /// Only notify for the first live session of the day.
+    pub first_live_of_day: bool,
+}
+
+/// Local time window during which notifications are suppressed.
+///
+/// The window wraps around midnight when `start` is later than `end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct QuietHours {
+    #[schema(value_type = String, example = "23:00:00")]
+    pub start: NaiveTime,
+    #[schema(value_type = String, example = "08:00:00")]
+    pub end: NaiveTime,
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+}
+
+/// Facts about the event and the subscription the rules are evaluated against.
+/// All timestamps are UTC.
+#[derive(Debug, Clone, Copy)]
+pub struct RuleContext<'a> {
+    pub now: NaiveDateTime,
+    pub title: &'a str,
+    pub category: &'a str,
+    pub last_notified_at: Option<NaiveDateTime>,
+    /// Start of the streamer's previous live session, if any.
+    pub previous_live_started_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SkipReason {
+    QuietHours,
+    TitleNotIncluded,
+    TitleExcluded,
+    CategoryNotAllowed,
+    MinGap,
+    NotFirstLiveOfDay,
+}
+
+impl Display for SkipReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let reason = match self {
+            SkipReason::QuietHours => "quiet hours",
+            SkipReason::TitleNotIncluded => "title does not match include patterns",
+            SkipReason::TitleExcluded => "title matches exclude patterns",
+            SkipReason::CategoryNotAllowed => "category is not allowed",
+            SkipReason::MinGap => "minimum gap not reached",
+            SkipReason::NotFirstLiveOfDay => "not the first live of the day",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+impl NotificationRules {
+    /// Checks that the timezone and regexes can be used for evaluation.
+    pub fn validate(&self) -> Result<(), String> {
+        self.timezone()?;
+        for pattern in self.title_include.iter().chain(self.title_exclude.iter()) {
+            Regex::new(pattern)
+                .map_err(|err| format!("invalid title pattern {pattern:?}: {err}"))?;
+        }
+        Ok(())
+    }
+
+    /// Fills every rule the subscription leaves unset from its group's rules.
+    ///
+    /// A rule is unset when it is `None`, an empty list or `false`, so a subscription
+    /// can tighten its group's rules but not switch an enabled group rule off.
+    pub fn inherit(&self, group: &NotificationRules) -> NotificationRules {
+        fn list(own: &[String], group: &[String]) -> Vec<String> {
+            if own.is_empty() { group } else { own }.to_vec()
+        }
+
+        NotificationRules {
+            timezone: self.timezone.clone().or_else(|| group.timezone.clone()),
+            quiet_hours: self.quiet_hours.or(group.quiet_hours),
+            title_include: list(&self.title_include, &group.title_include),
+            title_exclude: list(&self.title_exclude, &group.title_exclude),
+            category_allowlist: list(&self.category_allowlist, &group.category_allowlist),
+            min_gap_secs: self.min_gap_secs.or(group.min_gap_secs),
+            first_live_of_day: self.first_live_of_day || group.first_live_of_day,
+        }
+    }
+
+    /// Returns `Ok(())` when the notification may be sent, or the first rule that blocked it.
+    ///
+    /// Invalid timezones or patterns are treated as absent so that a broken rule never
+    /// silences a subscription; they are rejected by [`NotificationRules::validate`] on save.
+    pub fn evaluate(&self, ctx: &RuleContext<'_>) -> Result<(), SkipReason> {
+        let tz = self.timezone().unwrap_or(Tz::UTC);
+
+        if let Some(quiet_hours) = &self.quiet_hours
+            && quiet_hours.contains(local(tz, ctx.now).time())
+        {
+            return Err(SkipReason::QuietHours);
+        }
+
+        if let (Some(gap), Some(last)) = (self.min_gap_secs, ctx.last_notified_at)
+            && (ctx.now - last).num_seconds() < i64::try_from(gap).unwrap_or(i64::MAX)
+        {
+            return Err(SkipReason::MinGap);
+        }
+
+        if self.first_live_of_day
+            && let Some(previous) = ctx.previous_live_started_at
+            && local(tz, previous).date() == local(tz, ctx.now).date()
+        {
+            return Err(SkipReason::NotFirstLiveOfDay);
+        }
+
+        if !self.category_allowlist.is_empty()
+            && !self
+                .category_allowlist
+                .iter()
+                .any(|category| category.eq_ignore_ascii_case(ctx.category.trim()))
+        {
+            return Err(SkipReason::CategoryNotAllowed);
+        }
+
+        let include = compile(&self.title_include);
+        if !include.is_empty() && !include.iter().any(|regex| regex.is_match(ctx.title)) {
+            return Err(SkipReason::TitleNotIncluded);
+        }
+
+        if compile(&self.title_exclude)
+            .iter()
+            .any(|regex| regex.is_match(ctx.title))
+        {
+            return Err(SkipReason::TitleExcluded);
+        }
+
+        Ok(())
+    }
+
+    fn timezone(&self) -> Result<Tz, String> {
+        match self.timezone.as_deref() {
+            Some(name) => name
+                .parse::<Tz>()
+                .map_err(|_| format!("unknown timezone {name:?}")),
+            None => Ok(Tz::UTC),
+        }
+    }
+}
+
+fn local(tz: Tz, at: NaiveDateTime) -> NaiveDateTime {
+    tz.from_utc_datetime(&at).naive_local()
+}
+
+fn compile(patterns: &[String]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .filter_map(|pattern| Regex::new(pattern).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn utc(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 12, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn ctx(now: NaiveDateTime) -> RuleContext<'static> {
+        RuleContext {
+            now,
+            title: "Ranked grind",
+            category: "League of Legends",
+            last_notified_at: None,
+            previous_live_started_at: None,
+        }
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn empty_rules_allow_everything() {
+        assert_eq!(
+            NotificationRules::default().evaluate(&ctx(utc(1, 3, 0))),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn quiet_hours_wrap_midnight_in_user_timezone() {
+        let rules = NotificationRules {
+            timezone: Some("Asia/Shanghai".into()),
+            quiet_hours: Some(QuietHours {
+                start: time(23, 0),
+                end: time(8, 0),
+            }),
+            ..Default::default()
+        };
+
+        // 16:30 UTC is 00:30 in Shanghai.
+        assert_eq!(
+            rules.evaluate(&ctx(utc(1, 16, 30))),
+            Err(SkipReason::QuietHours)
+        );
+        // 02:00 UTC is 10:00 in Shanghai.
+        assert_eq!(rules.evaluate(&ctx(utc(1, 2, 0))), Ok(()));
+    }
+
+    #[test]
+    fn title_include_and_exclude_patterns() {
+        let rules = NotificationRules {
+            title_include: vec!["(?i)ranked".into(), "tournament".into()],
+            title_exclude: vec!["(?i)grind".into()],
+            ..Default::default()
+        };
+        assert_eq!(
+            rules.evaluate(&ctx(utc(1, 12, 0))),
+            Err(SkipReason::TitleExcluded)
+        );
+
+        let mut event = ctx(utc(1, 12, 0));
+        event.title = "Just chatting";
+        assert_eq!(rules.evaluate(&event), Err(SkipReason::TitleNotIncluded));
+
+        event.title = "Ranked with friends";
+        assert_eq!(rules.evaluate(&event), Ok(()));
+    }
+
+    #[test]
+    fn category_allowlist_is_case_insensitive() {
+        let mut rules = NotificationRules {
+            category_allowlist: vec!["league of legends".into()],
+            ..Default::default()
+        };
+        assert_eq!(rules.evaluate(&ctx(utc(1, 12, 0))), Ok(()));
+
+        rules.category_allowlist = vec!["Valorant".into()];
+        assert_eq!(
+            rules.evaluate(&ctx(utc(1, 12, 0))),
+            Err(SkipReason::CategoryNotAllowed)
+        );
+    }
+
+    #[test]
+    fn min_gap_between_notifications() {
+        let rules = NotificationRules {
+            min_gap_secs: Some(3600),
+            ..Default::default()
+        };
+        let mut event = ctx(utc(1, 12, 0));
+        event.last_notified_at = Some(utc(1, 11, 30));
+        assert_eq!(rules.evaluate(&event), Err(SkipReason::MinGap));
+
+        event.last_notified_at = Some(utc(1, 11, 0));
+        assert_eq!(rules.evaluate(&event), Ok(()));
+    }
+
+    #[test]
+    fn first_live_of_day_uses_local_date() {
+        let rules = NotificationRules {
+            timezone: Some("Asia/Shanghai".into()),
+            first_live_of_day: true,
+            ..Default::default()
+        };
+        let mut event = ctx(utc(2, 1, 0));
+        // 2025-12-01 17:00 UTC is already 2025-12-02 in Shanghai.
+        event.previous_live_started_at = Some(utc(1, 17, 0));
+        assert_eq!(rules.evaluate(&event), Err(SkipReason::NotFirstLiveOfDay));
+
+        // 2025-12-01 15:00 UTC is 23:00 on 2025-12-01 in Shanghai.
+        event.previous_live_started_at = Some(utc(1, 15, 0));
+        assert_eq!(rules.evaluate(&event), Ok(()));
+    }
+
+    #[test]
+    fn inherits_unset_rules_from_group() {
+        let group = NotificationRules {
+            timezone: Some("Asia/Shanghai".to_string()),
+            quiet_hours: Some(QuietHours {
+                start: time(23, 0),
+                end: time(8, 0),
+            }),
+            title_exclude: vec!["rerun".to_string()],
+            min_gap_secs: Some(600),
+            ..Default::default()
+        };
+        let own = NotificationRules {
+            title_exclude: vec!["replay".to_string()],
+            min_gap_secs: Some(60),
+            first_live_of_day: true,
+            ..Default::default()
+        };
+
+        let effective = own.inherit(&group);
+        assert_eq!(effective.timezone.as_deref(), Some("Asia/Shanghai"));
+        assert_eq!(effective.quiet_hours, group.quiet_hours);
+        assert_eq!(effective.title_exclude, vec!["replay".to_string()]);
+        assert_eq!(effective.min_gap_secs, Some(60));
+        assert!(effective.first_live_of_day);
+        assert_eq!(NotificationRules::default().inherit(&group), group);
+    }
+
+    #[test]
+    fn validate_rejects_bad_timezone_and_patterns() {
+        let rules = NotificationRules {
+            timezone: Some("Mars/Olympus".into()),
+            ..Default::default()
+        };
+        assert!(rules.validate().is_err());
+
+        let rules = NotificationRules {
+            title_exclude: vec!["(unclosed".into()],
+            ..Default::default()
+        };
+        assert!(rules.validate().is_err());
+    }
+
+    #[test]
+    fn deserializes_partial_rules() {
+        let rules: NotificationRules = serde_json::from_value(serde_json::json!({
+            "quiet_hours": { "start": "23:00:00", "end": "07:30:00" },
+            "min_gap_secs": 600
+        }))
+        .unwrap();
+
+        assert_eq!(rules.min_gap_secs, Some(600));
+        assert!(rules.title_include.is_empty());
+        assert!(!rules.first_live_of_day);
+    }
+}
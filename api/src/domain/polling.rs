@@ -0,0 +1,159 @@
+use crate::domain::schedule::PastSession;
+use chrono::{Duration, NaiveDateTime};
+
+#[derive(Debug, Clone, Copy)]
+pub struct PollingSettings {
+    pub min_interval_secs: u32,
+    pub max_interval_secs: u32,
+    /// Interval for rooms that are live, to pick up title changes and the end of
+    /// the stream in time.
+    pub live_interval_secs: u32,
+    /// Subscriber count from which a room counts as fully popular.
+    pub popular_subscribers: u64,
+}
+
+/// What is known about a room when its next poll is scheduled.
+#[derive(Debug, Clone, Copy)]
+pub struct PollingInput {
+    pub subscribers: u64,
+    /// Share of recent weeks in which the room was live around this time of week.
+    pub live_likelihood: f64,
+    pub is_live: bool,
+    /// A live state change waits for confirming polls.
+    pub confirming: bool,
+}
+
+/// Seconds until the room should be polled again.
+///
+/// Offline rooms move from `max_interval_secs` towards `min_interval_secs` on a
+/// geometric scale as they become more likely to go live or more popular.
+/// Popularity alone gets a room half way; a room that usually streams at this
+/// hour is polled at the minimum, as is a room whose live state change waits to
+/// be confirmed. `budget_factor` stretches the result when the platform would
+/// otherwise be asked for more than its rate budget.
+pub fn interval_secs(input: &PollingInput, settings: &PollingSettings, budget_factor: f64) -> u32 {
+    let min = f64::from(settings.min_interval_secs.max(1));
+    let max = f64::from(settings.max_interval_secs).max(min);
+
+    let base = if input.confirming {
+        min
+    } else if input.is_live {
+        f64::from(settings.live_interval_secs)
+    } else {
+        let popularity = if settings.popular_subscribers == 0 {
+            1.0
+        } else {
+            ((1.0 + input.subscribers as f64).ln()
+                / (1.0 + settings.popular_subscribers as f64).ln())
+            .min(1.0)
+        };
+        let likelihood = input.live_likelihood.clamp(0.0, 1.0);
+        let urgency = 1.0 - (1.0 - likelihood) * (1.0 - 0.5 * popularity);
+        min * (max / min).powf(1.0 - urgency)
+    };
+
+    (base * budget_factor.max(1.0)).clamp(min, max).round() as u32
+}
+
+/// How much intervals on a platform must be stretched so the expected request
+/// rate stays within its budget.
+pub fn budget_factor(demand_per_minute: f64, budget_per_minute: u32) -> f64 {
+    if budget_per_minute == 0 {
+        return 1.0;
+    }
+    (demand_per_minute / f64::from(budget_per_minute)).max(1.0)
+}
+
+/// Share of the last `lookback_weeks` in which the room was live during the hour
+/// starting at the same time of week as `now`.
+pub fn live_likelihood(sessions: &[PastSession], now: NaiveDateTime, lookback_weeks: u32) -> f64 {
+    if lookback_weeks == 0 {
+        return 0.0;
+    }
+
+    let hits = (1..=i64::from(lookback_weeks))
+        .filter(|weeks_ago| {
+            let start = now - Duration::weeks(*weeks_ago);
+            let end = start + Duration::hours(1);
+            sessions.iter().any(|session| {
+                session.started_at < end && session.ended_at.is_none_or(|ended| ended > start)
+            })
+        })
+        .count();
+    hits as f64 / f64::from(lookback_weeks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn settings() -> PollingSettings {
+        PollingSettings {
+            min_interval_secs: 30,
+            max_interval_secs: 600,
+            live_interval_secs: 60,
+            popular_subscribers: 50,
+        }
+    }
+
+    fn input(subscribers: u64, live_likelihood: f64, is_live: bool) -> PollingInput {
+        PollingInput {
+            subscribers,
+            live_likelihood,
+            is_live,
+            confirming: false,
+        }
+    }
+
+    #[test]
+    fn scales_interval_between_bounds() {
+        let settings = settings();
+
+        assert_eq!(interval_secs(&input(0, 0.0, false), &settings, 1.0), 600);
+        assert_eq!(interval_secs(&input(3, 1.0, false), &settings, 1.0), 30);
+        assert_eq!(interval_secs(&input(0, 0.0, true), &settings, 1.0), 60);
+        let confirming = PollingInput {
+            confirming: true,
+            ..input(0, 0.0, true)
+        };
+        assert_eq!(interval_secs(&confirming, &settings, 1.0), 30);
+
+        let popular = interval_secs(&input(500, 0.0, false), &settings, 1.0);
+        let dormant = interval_secs(&input(1, 0.0, false), &settings, 1.0);
+        assert_eq!(popular, 134);
+        assert!(dormant > popular && dormant < 600);
+    }
+
+    #[test]
+    fn stretches_interval_for_platform_budget() {
+        let settings = settings();
+
+        assert_eq!(budget_factor(30.0, 60), 1.0);
+        assert_eq!(budget_factor(180.0, 60), 3.0);
+        assert_eq!(interval_secs(&input(0, 0.0, true), &settings, 3.0), 180);
+        assert_eq!(interval_secs(&input(0, 0.0, false), &settings, 3.0), 600);
+    }
+
+    #[test]
+    fn estimates_likelihood_from_same_hour_of_week() {
+        // 2026-01-19 20:00 is a Monday.
+        let now = NaiveDate::from_ymd_opt(2026, 1, 19)
+            .unwrap()
+            .and_hms_opt(20, 0, 0)
+            .unwrap();
+        let sessions: Vec<PastSession> = [1, 2, 4]
+            .into_iter()
+            .map(|weeks_ago| {
+                let started_at = now - Duration::weeks(weeks_ago) + Duration::minutes(20);
+                PastSession {
+                    started_at,
+                    ended_at: Some(started_at + Duration::hours(2)),
+                }
+            })
+            .collect();
+
+        assert_eq!(live_likelihood(&sessions, now, 4), 0.75);
+        assert_eq!(live_likelihood(&sessions, now + Duration::hours(3), 4), 0.0);
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::{Datelike, Duration, NaiveDateTime, TimeZone, Timelike, Weekday};
+use chrono_tz::Tz;
+use std::collections::{BTreeMap, BTreeSet};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PastSession {
+    /// UTC start time.
+    pub started_at: NaiveDateTime,
+    /// UTC end time, `None` while the session is still running.
+    pub ended_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PredictedSession {
+    /// UTC start time.
+    pub starts_at: NaiveDateTime,
+    /// UTC end time.
+    pub ends_at: NaiveDateTime,
+    /// Share of the observed weeks in which the streamer started around this time.
+    pub confidence: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PredictionSettings {
+    pub lookback_weeks: u32,
+    pub horizon_days: u32,
+    pub min_confidence: f64,
+    /// Sessions needed on a weekday before it is considered at all.
+    pub min_sessions: usize,
+    /// How far a start may deviate from the usual start time and still count.
+    pub tolerance_minutes: i64,
+    /// Length assumed when no finished session is available.
+    pub default_duration: Duration,
+}
+
+/// Predicts upcoming sessions from weekly recurring start times.
+///
+/// Sessions are grouped by local weekday. A weekday yields a prediction when the
+/// streamer started within the tolerance of the median start time in enough of the
+/// observed weeks to reach `min_confidence`.
+pub fn predict(
+    sessions: &[PastSession],
+    tz: Tz,
+    now: NaiveDateTime,
+    settings: &PredictionSettings,
+) -> Vec<PredictedSession> {
+    let window_start = now - Duration::weeks(i64::from(settings.lookback_weeks));
+    let local = |time: NaiveDateTime| tz.from_utc_datetime(&time).naive_local();
+
+    let recent: Vec<&PastSession> = sessions
+        .iter()
+        .filter(|session| session.started_at >= window_start && session.started_at <= now)
+        .collect();
+    let Some(earliest) = recent.iter().map(|session| session.started_at).min() else {
+        return Vec::new();
+    };
+    let observed_weeks = ((now - earliest).num_days() / 7 + 1)
+        .clamp(1, i64::from(settings.lookback_weeks.max(1))) as f64;
+
+    let window_start_date = local(window_start).date();
+    let mut by_weekday: BTreeMap<u32, Vec<(i64, i64, Option<Duration>)>> = BTreeMap::new();
+    for session in recent {
+        let start = local(session.started_at);
+        let week = (start.date() - window_start_date).num_days() / 7;
+        let minute = i64::from(start.hour() * 60 + start.minute());
+        let duration = session
+            .ended_at
+            .map(|ended_at| ended_at - session.started_at);
+        by_weekday
+            .entry(start.weekday().num_days_from_monday())
+            .or_default()
+            .push((week, minute, duration));
+    }
+
+    let mut slots: Vec<(Weekday, i64, Duration, f64)> = Vec::new();
+    for (weekday, starts) in by_weekday {
+        if starts.len() < settings.min_sessions.max(1) {
+            continue;
+        }
+
+        let mut minutes: Vec<i64> = starts.iter().map(|(_, minute, _)| *minute).collect();
+        minutes.sort_unstable();
+        let usual = minutes[minutes.len() / 2];
+
+        let clustered: Vec<&(i64, i64, Option<Duration>)> = starts
+            .iter()
+            .filter(|(_, minute, _)| {
+                circular_distance(*minute, usual) <= settings.tolerance_minutes
+            })
+            .collect();
+        let weeks: BTreeSet<i64> = clustered.iter().map(|(week, _, _)| *week).collect();
+        let confidence = (weeks.len() as f64 / observed_weeks).min(1.0);
+        if confidence < settings.min_confidence {
+            continue;
+        }
+
+        let mut durations: Vec<Duration> = clustered
+            .iter()
+            .filter_map(|(_, _, duration)| *duration)
+            .filter(|duration| *duration > Duration::zero())
+            .collect();
+        durations.sort_unstable();
+        let duration = durations
+            .get(durations.len() / 2)
+            .copied()
+            .unwrap_or(settings.default_duration);
+
+        let weekday = Weekday::try_from(weekday as u8).unwrap_or(Weekday::Mon);
+        slots.push((weekday, usual, duration, confidence));
+    }
+
+    let today = local(now).date();
+    let mut predictions: Vec<PredictedSession> = (0..=i64::from(settings.horizon_days))
+        .map(|offset| today + Duration::days(offset))
+        .flat_map(|date| {
+            slots
+                .iter()
+                .filter(move |(weekday, ..)| date.weekday() == *weekday)
+                .filter_map(move |(_, minute, duration, confidence)| {
+                    let local_start = date.and_hms_opt(0, 0, 0)? + Duration::minutes(*minute);
+                    let starts_at = tz.from_local_datetime(&local_start).earliest()?.naive_utc();
+                    Some(PredictedSession {
+                        starts_at,
+                        ends_at: starts_at + *duration,
+                        confidence: *confidence,
+                    })
+                })
+        })
+        .filter(|prediction| prediction.starts_at > now)
+        .collect();
+    predictions.sort_by_key(|prediction| prediction.starts_at);
+    predictions
+}
+
+fn circular_distance(a: i64, b: i64) -> i64 {
+    let diff = (a - b).rem_euclid(MINUTES_PER_DAY);
+    diff.min(MINUTES_PER_DAY - diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn settings() -> PredictionSettings {
+        PredictionSettings {
+            lookback_weeks: 8,
+            horizon_days: 14,
+            min_confidence: 0.6,
+            min_sessions: 3,
+            tolerance_minutes: 90,
+            default_duration: Duration::hours(2),
+        }
+    }
+
+    fn at(date: NaiveDate, hour: u32, minute: u32) -> NaiveDateTime {
+        date.and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    // 2025-12-01 is a Monday.
+    fn monday(weeks_ago: i64) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 12, 1).unwrap() - Duration::weeks(weeks_ago)
+    }
+
+    #[test]
+    fn predicts_weekly_slot_in_local_time() {
+        // Mondays around 20:00 Beijing time (12:00 UTC) for six weeks.
+        let sessions: Vec<PastSession> = (1..=6)
+            .map(|weeks_ago| {
+                let started_at = at(monday(weeks_ago), 12, (weeks_ago as u32) * 3);
+                PastSession {
+                    started_at,
+                    ended_at: Some(started_at + Duration::hours(3)),
+                }
+            })
+            .collect();
+        let now = at(monday(0), 8, 0);
+
+        let predictions = predict(&sessions, chrono_tz::Asia::Shanghai, now, &settings());
+
+        assert_eq!(predictions.len(), 3);
+        assert_eq!(predictions[0].starts_at, at(monday(0), 12, 12));
+        assert_eq!(predictions[0].ends_at, at(monday(0), 15, 12));
+        assert_eq!(predictions[1].starts_at, at(monday(-1), 12, 12));
+        assert!((predictions[0].confidence - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn skips_irregular_weekdays() {
+        // Five Mondays out of eight observed weeks, at scattered times.
+        let sessions =
+            [(8, 10), (5, 20), (1, 14), (7, 20), (2, 12)].map(|(weeks_ago, hour)| PastSession {
+                started_at: at(monday(weeks_ago), hour, 0),
+                ended_at: None,
+            });
+        let now = at(monday(0), 0, 0);
+
+        assert!(predict(&sessions, chrono_tz::UTC, now, &settings()).is_empty());
+    }
+
+    #[test]
+    fn matches_start_times_across_midnight() {
+        assert_eq!(circular_distance(23 * 60 + 50, 10), 20);
+        assert_eq!(circular_distance(600, 660), 60);
+    }
+}
@@ -0,0 +1,203 @@
+use crate::domain::feed::escape;
+use crate::error::Entity;
+use chrono::NaiveDateTime;
+use entity::share_page::Model;
+use serde::Serialize;
+use std::fmt::Write;
+use utoipa::ToSchema;
+
+impl Entity for Model {
+    const NAME: &'static str = "share_page";
+}
+
+/// Public view of a shared group. Holds nothing that identifies the owner.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SharedPage {
+    #[schema(example = "Our partners")]
+    pub title: String,
+    pub generated_at: NaiveDateTime,
+    pub expires_at: Option<NaiveDateTime>,
+    pub live_count: usize,
+    /// Live streamers first, by viewers.
+    pub streamers: Vec<SharedStreamer>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct SharedStreamer {
+    pub name: String,
+    #[schema(example = "Bilibili")]
+    pub platform: String,
+    pub room_url: String,
+    pub avatar: String,
+    /// Same-origin copy of `avatar`, see `GET /api/v1/media/{kind}/{hash}`.
+    pub avatar_proxy_url: Option<String>,
+    pub is_live: bool,
+    pub title: String,
+    pub category: String,
+    pub cover_image: String,
+    /// Same-origin copy of `cover_image`, kept after the stream ends.
+    pub cover_image_proxy_url: Option<String>,
+    pub viewer_count: i64,
+    pub live_started_at: Option<NaiveDateTime>,
+}
+
+impl SharedPage {
+    /// Renders a self-contained page suitable for embedding in an iframe. A
+    /// non-zero `refresh_secs` makes the browser reload it periodically.
+    pub fn to_html(&self, refresh_secs: u64) -> String {
+        let title = escape(&self.title);
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n");
+        html.push_str("<meta charset=\"utf-8\">\n");
+        html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        html.push_str("<meta name=\"robots\" content=\"noindex\">\n");
+        if refresh_secs > 0 {
+            let _ = writeln!(
+                html,
+                "<meta http-equiv=\"refresh\" content=\"{refresh_secs}\">"
+            );
+        }
+        let _ = writeln!(html, "<title>{title}</title>");
+        html.push_str(STYLE);
+        html.push_str("</head>\n<body>\n<main>\n");
+        let _ = writeln!(html, "<h1>{title}</h1>");
+        let _ = writeln!(
+            html,
+            "<p class=\"summary\">{} of {} live</p>",
+            self.live_count,
+            self.streamers.len()
+        );
+
+        html.push_str("<ul>\n");
+        for streamer in &self.streamers {
+            let _ = writeln!(
+                html,
+                "<li class=\"{}\"><a href=\"{}\" target=\"_blank\" rel=\"noopener\">",
+                if streamer.is_live { "live" } else { "offline" },
+                escape(&streamer.room_url)
+            );
+            // Platforms refuse hot-linked images, so the page shows the proxied copy.
+            if let Some(avatar) = &streamer.avatar_proxy_url {
+                let _ = writeln!(
+                    html,
+                    "<img src=\"{}\" alt=\"\" loading=\"lazy\">",
+                    escape(avatar)
+                );
+            }
+            let _ = writeln!(
+                html,
+                "<span class=\"name\">{}</span>",
+                escape(&streamer.name)
+            );
+            if streamer.is_live {
+                let mut status = vec!["LIVE".to_string()];
+                if !streamer.category.is_empty() {
+                    status.push(escape(&streamer.category));
+                }
+                status.push(format!("{} viewers", streamer.viewer_count));
+                let _ = writeln!(html, "<span class=\"status\">{}</span>", status.join(" · "));
+                let _ = writeln!(
+                    html,
+                    "<span class=\"title\">{}</span>",
+                    escape(&streamer.title)
+                );
+            } else {
+                let _ = writeln!(
+                    html,
+                    "<span class=\"status\">Offline · {}</span>",
+                    escape(&streamer.platform)
+                );
+            }
+            html.push_str("</a></li>\n");
+        }
+        html.push_str("</ul>\n");
+
+        let _ = writeln!(
+            html,
+            "<footer>Updated {} UTC</footer>",
+            self.generated_at.format("%Y-%m-%d %H:%M")
+        );
+        html.push_str("</main>\n</body>\n</html>\n");
+        html
+    }
+}
+
+/// Seconds a response may be cached: the configured max age, but never past the
+/// expiry of the page.
+pub fn max_age(configured_secs: u64, now: NaiveDateTime, expires_at: Option<NaiveDateTime>) -> u64 {
+    match expires_at {
+        Some(expires_at) => {
+            let remaining = u64::try_from((expires_at - now).num_seconds()).unwrap_or(0);
+            configured_secs.min(remaining)
+        }
+        None => configured_secs,
+    }
+}
+
+const STYLE: &str = "<style>
+body{margin:0;font-family:system-ui,sans-serif;background:#fff;color:#222}
+main{max-width:40rem;margin:0 auto;padding:1rem}
+h1{font-size:1.25rem;margin:0 0 .25rem}
+.summary,footer{color:#777;font-size:.85rem}
+ul{list-style:none;margin:0;padding:0}
+li a{display:grid;grid-template-columns:3rem 1fr;column-gap:.75rem;padding:.5rem 0;border-bottom:1px solid #eee;color:inherit;text-decoration:none}
+img{grid-row:span 3;width:3rem;height:3rem;border-radius:50%;object-fit:cover}
+.name{font-weight:600}
+.status,.title{font-size:.85rem;color:#777}
+.live .status{color:#e0245e;font-weight:600}
+.offline{opacity:.6}
+</style>
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, NaiveDate};
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 1, 10)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn caps_max_age_at_expiry() {
+        assert_eq!(max_age(60, now(), None), 60);
+        assert_eq!(max_age(60, now(), Some(now() + Duration::seconds(20))), 20);
+        assert_eq!(max_age(60, now(), Some(now() - Duration::hours(1))), 0);
+    }
+
+    #[test]
+    fn renders_escaped_html() {
+        let page = SharedPage {
+            title: "Partners <live>".to_string(),
+            generated_at: now(),
+            expires_at: None,
+            live_count: 1,
+            streamers: vec![SharedStreamer {
+                name: "Tom & Jerry".to_string(),
+                platform: "Bilibili".to_string(),
+                room_url: "https://live.bilibili.com/1".to_string(),
+                avatar: "https://i0.hdslb.com/face.jpg".to_string(),
+                avatar_proxy_url: Some("/api/v1/media/avatar/ab12".to_string()),
+                is_live: true,
+                title: "Finals".to_string(),
+                category: "Esports".to_string(),
+                cover_image: String::new(),
+                cover_image_proxy_url: None,
+                viewer_count: 1200,
+                live_started_at: Some(now()),
+            }],
+        };
+
+        let html = page.to_html(60);
+        assert!(html.contains("<title>Partners &lt;live&gt;</title>"));
+        assert!(html.contains("<meta http-equiv=\"refresh\" content=\"60\">"));
+        assert!(html.contains("<li class=\"live\">"));
+        assert!(html.contains("<span class=\"name\">Tom &amp; Jerry</span>"));
+        assert!(html.contains("LIVE · Esports · 1200 viewers"));
+        assert!(html.contains("<img src=\"/api/v1/media/avatar/ab12\""));
+        assert!(!html.contains("hdslb.com"));
+        assert!(!page.to_html(0).contains("http-equiv"));
+    }
+}
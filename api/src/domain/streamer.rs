@@ -0,0 +1,6 @@
+use crate::error::Entity;
+use entity::streamer::Model;
+
+impl Entity for Model {
+    const NAME: &'static str = "streamer";
+}
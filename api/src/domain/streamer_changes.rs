@@ -0,0 +1,210 @@
+use entity::streamer::Model;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Streamer attribute whose changes are kept in the change history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangedField {
+    Title,
+    Category,
+    CoverImage,
+    Name,
+    Avatar,
+    Announcement,
+}
+
+impl ChangedField {
+    pub const ALL: [ChangedField; 6] = [
+        ChangedField::Title,
+        ChangedField::Category,
+        ChangedField::CoverImage,
+        ChangedField::Name,
+        ChangedField::Avatar,
+        ChangedField::Announcement,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangedField::Title => "title",
+            ChangedField::Category => "category",
+            ChangedField::CoverImage => "cover_image",
+            ChangedField::Name => "name",
+            ChangedField::Avatar => "avatar",
+            ChangedField::Announcement => "announcement",
+        }
+    }
+
+    /// Whether the change is worth notifying subscribers about: title and
+    /// category changes only while live, announcements unless cleared.
+    pub fn is_notable(&self, while_live: bool, new_value: &str) -> bool {
+        match self {
+            ChangedField::Title | ChangedField::Category => while_live,
+            ChangedField::Announcement => !new_value.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Known value of the field, `None` when the snapshot does not have one.
+    fn value<'a>(&self, model: &'a Model) -> Option<&'a str> {
+        let value = match self {
+            ChangedField::Title => &model.title,
+            ChangedField::Category => &model.category,
+            ChangedField::CoverImage => &model.cover_image,
+            ChangedField::Name => &model.name,
+            ChangedField::Avatar => &model.avatar,
+            // Rooms may have no announcement at all, so only a room that was
+            // never fetched has an unknown one.
+            ChangedField::Announcement => return model.announcement.as_deref().map(str::trim),
+        };
+        Some(value.trim()).filter(|value| !value.is_empty())
+    }
+}
+
+impl fmt::Display for ChangedField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ChangedField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.as_str() == s)
+            .ok_or_else(|| format!("unknown field: {s}"))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: ChangedField,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// Compares two snapshots of the same streamer.
+///
+/// Blank values on either side are not treated as changes: an empty old value is
+/// the state before the first poll, and an empty new value usually means the
+/// platform left the field out of its response. The announcement is the
+/// exception, as a streamer may clear it; there only a value that was never
+/// fetched is skipped.
+pub fn diff(old: &Model, new: &Model) -> Vec<FieldChange> {
+    ChangedField::ALL
+        .into_iter()
+        .filter_map(|field| {
+            let old_value = field.value(old)?;
+            let new_value = field.value(new)?;
+            (old_value != new_value).then(|| FieldChange {
+                field,
+                old_value: old_value.to_string(),
+                new_value: new_value.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+
+    fn streamer(title: &str, category: &str, name: &str) -> Model {
+        Model {
+            id: 1,
+            platform: "bilibili".to_string(),
+            room_id: "1".to_string(),
+            name: name.to_string(),
+            avatar: "https://example.com/a.png".to_string(),
+            description: String::new(),
+            room_url: String::new(),
+            is_live: true,
+            title: title.to_string(),
+            category: category.to_string(),
+            cover_image: String::new(),
+            viewer_count: 0,
+            live_started_at: None,
+            last_checked_at: None,
+            created_at: NaiveDateTime::default(),
+            updated_at: NaiveDateTime::default(),
+            creator_id: None,
+            next_check_at: None,
+            poll_interval_secs: None,
+            live_state: "offline".to_string(),
+            live_state_since: None,
+            live_state_polls: 0,
+            announcement: None,
+        }
+    }
+
+    #[test]
+    fn reports_changed_fields_only() {
+        let old = streamer("Morning chat", "Just Chatting", "alice");
+        let new = streamer("Charity event!", "Just Chatting", "alice");
+
+        assert_eq!(
+            diff(&old, &new),
+            vec![FieldChange {
+                field: ChangedField::Title,
+                old_value: "Morning chat".to_string(),
+                new_value: "Charity event!".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn ignores_blank_values() {
+        let initial = streamer("", "", "alice");
+        let polled = streamer("Morning chat", "Just Chatting", "alice");
+        assert!(diff(&initial, &polled).is_empty());
+
+        let missing = streamer("", "Just Chatting", "alice");
+        assert!(diff(&polled, &missing).is_empty());
+    }
+
+    #[test]
+    fn tracks_cleared_announcements() {
+        let unknown = streamer("Morning chat", "Just Chatting", "alice");
+        let posted = Model {
+            announcement: Some("Live Friday 8pm".to_string()),
+            ..unknown.clone()
+        };
+        let cleared = Model {
+            announcement: Some(String::new()),
+            ..unknown.clone()
+        };
+
+        assert!(diff(&unknown, &posted).is_empty());
+        assert_eq!(
+            diff(&posted, &cleared),
+            vec![FieldChange {
+                field: ChangedField::Announcement,
+                old_value: "Live Friday 8pm".to_string(),
+                new_value: String::new(),
+            }]
+        );
+        assert_eq!(diff(&cleared, &posted).len(), 1);
+    }
+
+    #[test]
+    fn notifies_live_edits_and_new_announcements() {
+        assert!(ChangedField::Title.is_notable(true, "Charity event!"));
+        assert!(!ChangedField::Category.is_notable(false, "Just Chatting"));
+        assert!(ChangedField::Announcement.is_notable(false, "Live Friday 8pm"));
+        assert!(!ChangedField::Announcement.is_notable(true, ""));
+        assert!(!ChangedField::Avatar.is_notable(true, "https://example.com/b.png"));
+    }
+
+    #[test]
+    fn parses_field_names() {
+        for field in ChangedField::ALL {
+            assert_eq!(field.as_str().parse::<ChangedField>(), Ok(field));
+        }
+        assert!("viewers".parse::<ChangedField>().is_err());
+    }
+}
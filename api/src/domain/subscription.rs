@@ -0,0 +1,6 @@
+use crate::error::Entity;
+use entity::subscription::Model;
+
+impl Entity for Model {
+    const NAME: &'static str = "subscription";
+}
@@ -0,0 +1,6 @@
+use crate::error::Entity;
+use entity::subscription_group::Model;
+
+impl Entity for Model {
+    const NAME: &'static str = "subscription_group";
+}
@@ -0,0 +1,52 @@
+/// Longest accepted tag, in characters.
+pub const MAX_TAG_LEN: usize = 32;
+/// Most tags a single subscription can carry.
+pub const MAX_TAGS: usize = 20;
+
+/// Trims, lowercases and sorts tags, dropping blanks and duplicates.
+pub fn normalize(tags: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized: Vec<String> = Vec::with_capacity(tags.len());
+    for tag in tags {
+        let tag = tag.trim().to_lowercase();
+        if tag.is_empty() || normalized.contains(&tag) {
+            continue;
+        }
+        if tag.chars().count() > MAX_TAG_LEN {
+            return Err(format!(
+                "tag {tag:?} is longer than {MAX_TAG_LEN} characters"
+            ));
+        }
+        normalized.push(tag);
+    }
+    if normalized.len() > MAX_TAGS {
+        return Err(format!("at most {MAX_TAGS} tags are allowed"));
+    }
+    normalized.sort();
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn normalizes_deduplicates_and_sorts() {
+        assert_eq!(
+            normalize(&tags(&["partners", " Esports", "", "esports ", "电竞"])),
+            Ok(tags(&["esports", "partners", "电竞"]))
+        );
+    }
+
+    #[test]
+    fn rejects_long_tags_and_too_many_tags() {
+        assert!(normalize(&tags(&[&"x".repeat(MAX_TAG_LEN + 1)])).is_err());
+        assert!(normalize(&tags(&["直".repeat(MAX_TAG_LEN).as_str()])).is_ok());
+
+        let many: Vec<String> = (0..=MAX_TAGS).map(|i| format!("tag{i}")).collect();
+        assert!(normalize(&many).is_err());
+    }
+}
@@ -0,0 +1,208 @@
+use crate::domain::notification_rules::NotificationRules;
+use chrono::NaiveDateTime;
+use live_platform::Platform;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::str::FromStr;
+use utoipa::ToSchema;
+
+/// Version written by the exporter; documents with a newer version are rejected.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Portable snapshot of a user's subscriptions and notification channels.
+///
+/// Rooms are identified by platform and room id rather than database ids, so a
+/// document exported from one instance can be imported into another.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TransferDocument {
+    #[schema(example = 1)]
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: Option<NaiveDateTime>,
+    #[serde(default)]
+    pub groups: Vec<GroupEntry>,
+    #[serde(default)]
+    pub subscriptions: Vec<SubscriptionEntry>,
+    #[serde(default)]
+    pub channels: Vec<ChannelEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GroupEntry {
+    #[schema(example = "esports")]
+    pub name: String,
+    #[serde(default)]
+    pub rules: NotificationRules,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct SubscriptionEntry {
+    #[schema(example = "Bilibili")]
+    pub platform: String,
+    #[schema(example = "7734200")]
+    pub room_id: String,
+    /// Used to resolve the room when `platform` or `room_id` is missing.
+    #[schema(example = "https://live.bilibili.com/7734200")]
+    pub room_url: String,
+    /// Streamer name at export time; informational only.
+    pub name: String,
+    /// Settings left out (e.g. in URL lists) take their defaults on creation and
+    /// are not compared against an existing subscription.
+    pub rules: Option<NotificationRules>,
+    pub notify_title_change: Option<bool>,
+    pub notify_category_change: Option<bool>,
+    pub notify_announcement_change: Option<bool>,
+    /// Name of the group the subscription belongs to.
+    pub group: Option<String>,
+    pub tags: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChannelEntry {
+    #[schema(example = "bark")]
+    pub channel: String,
+    pub destination: String,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl SubscriptionEntry {
+    pub fn from_url(url: &str) -> Self {
+        Self {
+            room_url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// The room this entry points at: explicit platform and room id win over the URL.
+    pub fn room(&self) -> Result<(Platform, String), String> {
+        let platform = self.platform.trim();
+        let room_id = self.room_id.trim();
+        if !platform.is_empty() && !room_id.is_empty() {
+            let platform = Platform::from_str(platform)
+                .map_err(|_| format!("Unsupported platform: {platform}"))?;
+            return Ok((platform, room_id.to_string()));
+        }
+
+        let url = self.room_url.trim();
+        if url.is_empty() {
+            return Err("Either platform and room_id or room_url is required".to_string());
+        }
+        Platform::parse_room_url(url).ok_or_else(|| format!("Not a supported room URL: {url}"))
+    }
+
+    /// Short description of the entry used in import reports.
+    pub fn label(&self) -> String {
+        match (self.platform.trim(), self.room_id.trim()) {
+            (platform, room_id) if !platform.is_empty() && !room_id.is_empty() => {
+                format!("{platform}/{room_id}")
+            }
+            _ => self.room_url.trim().to_string(),
+        }
+    }
+}
+
+/// Parses an import body: either a JSON export document or a plain-text list of
+/// room URLs, one per line. Blank lines and lines starting with `#` are skipped.
+pub fn parse(input: &str) -> Result<TransferDocument, String> {
+    let trimmed = input.trim_start_matches('\u{feff}').trim();
+    if trimmed.starts_with('{') {
+        let document: TransferDocument = serde_json::from_str(trimmed)
+            .map_err(|err| format!("Invalid export document: {err}"))?;
+        if document.version == 0 || document.version > FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported export version {}, expected at most {FORMAT_VERSION}",
+                document.version
+            ));
+        }
+        return Ok(document);
+    }
+
+    let subscriptions: Vec<SubscriptionEntry> = trimmed
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(SubscriptionEntry::from_url)
+        .collect();
+    if subscriptions.is_empty() {
+        return Err("Nothing to import".to_string());
+    }
+    Ok(TransferDocument {
+        version: FORMAT_VERSION,
+        exported_at: None,
+        groups: Vec::new(),
+        subscriptions,
+        channels: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_url_lists() {
+        let document = parse(
+            "# team rooms\nhttps://live.bilibili.com/7734200\n\n  https://www.douyu.com/60937  \n",
+        )
+        .unwrap();
+
+        let rooms: Vec<_> = document
+            .subscriptions
+            .iter()
+            .map(|entry| entry.room().unwrap())
+            .collect();
+        assert_eq!(
+            rooms,
+            vec![
+                (Platform::Bilibili, "7734200".to_string()),
+                (Platform::Douyu, "60937".to_string()),
+            ]
+        );
+        assert!(document.channels.is_empty());
+    }
+
+    #[test]
+    fn parses_documents() {
+        let document = parse(
+            r#"{
+                "version": 1,
+                "subscriptions": [
+                    {"platform": "bilibili", "room_id": "1", "notify_title_change": true},
+                    {"room_url": "https://www.douyu.com/60937"},
+                    {"platform": "twitch", "room_id": "x"}
+                ],
+                "channels": [{"channel": "bark", "destination": "key"}]
+            }"#,
+        )
+        .unwrap();
+
+        let subscriptions = &document.subscriptions;
+        assert_eq!(
+            subscriptions[0].room(),
+            Ok((Platform::Bilibili, "1".to_string()))
+        );
+        assert_eq!(subscriptions[0].notify_title_change, Some(true));
+        assert_eq!(subscriptions[1].rules, None);
+        assert_eq!(
+            subscriptions[1].room(),
+            Ok((Platform::Douyu, "60937".to_string()))
+        );
+        assert!(subscriptions[2].room().is_err());
+        assert!(document.channels[0].enabled);
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        assert!(parse(r#"{"version": 2}"#).is_err());
+        assert!(parse(r#"{"subscriptions": []}"#).is_err());
+        assert!(parse("\n# nothing here\n").is_err());
+    }
+}
@@ -0,0 +1,125 @@
+use chrono::{DateTime, NaiveDateTime};
+use std::collections::BTreeMap;
+
+/// Aggregated viewer counts for a single time bucket.
+///
+/// Buckets keep the sum instead of the average so they can be merged
+/// without losing precision when downsampling or re-bucketing for charts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewerBucket {
+    pub start: NaiveDateTime,
+    pub sample_count: i64,
+    pub min: i64,
+    pub max: i64,
+    pub sum: i64,
+}
+
+impl ViewerBucket {
+    pub fn single(start: NaiveDateTime, viewers: i64) -> Self {
+        Self {
+            start,
+            sample_count: 1,
+            min: viewers,
+            max: viewers,
+            sum: viewers,
+        }
+    }
+
+    pub fn merge(&mut self, other: &ViewerBucket) {
+        self.sample_count += other.sample_count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.sum += other.sum;
+    }
+
+    pub fn avg(&self) -> f64 {
+        if self.sample_count == 0 {
+            return 0.0;
+        }
+        self.sum as f64 / self.sample_count as f64
+    }
+}
+
+/// Aligns `at` to the start of the bucket of `resolution_secs` it falls into.
+pub fn align(at: NaiveDateTime, resolution_secs: i64) -> NaiveDateTime {
+    let timestamp = at.and_utc().timestamp();
+    let aligned = timestamp - timestamp.rem_euclid(resolution_secs.max(1));
+    DateTime::from_timestamp(aligned, 0)
+        .map(|t| t.naive_utc())
+        .unwrap_or(at)
+}
+
+/// Merges buckets into coarser buckets of `resolution_secs`, ordered by start time.
+pub fn rebucket<I>(buckets: I, resolution_secs: i64) -> Vec<ViewerBucket>
+where
+    I: IntoIterator<Item = ViewerBucket>,
+{
+    let mut merged: BTreeMap<NaiveDateTime, ViewerBucket> = BTreeMap::new();
+    for bucket in buckets {
+        let start = align(bucket.start, resolution_secs);
+        merged
+            .entry(start)
+            .and_modify(|existing| existing.merge(&bucket))
+            .or_insert(ViewerBucket { start, ..bucket });
+    }
+    merged.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, 12, 6)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    #[test]
+    fn aligns_to_bucket_start() {
+        assert_eq!(align(at(10, 7, 42), 60), at(10, 7, 0));
+        assert_eq!(align(at(10, 7, 42), 900), at(10, 0, 0));
+        assert_eq!(align(at(10, 15, 0), 900), at(10, 15, 0));
+    }
+
+    #[test]
+    fn rebuckets_with_min_max_and_avg() {
+        let samples = vec![
+            ViewerBucket::single(at(10, 1, 0), 100),
+            ViewerBucket::single(at(10, 2, 0), 300),
+            ViewerBucket::single(at(10, 16, 0), 50),
+        ];
+
+        let buckets = rebucket(samples, 900);
+
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].start, at(10, 0, 0));
+        assert_eq!(buckets[0].sample_count, 2);
+        assert_eq!(buckets[0].min, 100);
+        assert_eq!(buckets[0].max, 300);
+        assert_eq!(buckets[0].avg(), 200.0);
+        assert_eq!(buckets[1].start, at(10, 15, 0));
+        assert_eq!(buckets[1].avg(), 50.0);
+    }
+
+    #[test]
+    fn merging_preserves_weighted_average() {
+        let coarse = ViewerBucket {
+            start: at(10, 0, 0),
+            sample_count: 3,
+            min: 10,
+            max: 30,
+            sum: 60,
+        };
+        let fine = ViewerBucket::single(at(10, 5, 0), 100);
+
+        let buckets = rebucket([coarse, fine], 900);
+
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets[0].sample_count, 4);
+        assert_eq!(buckets[0].max, 100);
+        assert_eq!(buckets[0].avg(), 40.0);
+    }
+}
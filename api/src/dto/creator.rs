@@ -0,0 +1,50 @@
+use crate::dto::StreamerDto;
+use chrono::NaiveDateTime;
+use entity::creator::Model;
+use entity::streamer::Model as StreamerModel;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateCreatorRequest {
+    #[validate(length(min = 1, max = 64))]
+    #[schema(example = "Uzi")]
+    pub name: String,
+    /// Rooms of the creator, at most one per platform.
+    #[serde(default)]
+    #[schema(example = json!([1, 2]))]
+    pub streamer_ids: Vec<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateCreatorRequest {
+    #[validate(length(min = 1, max = 64))]
+    pub name: Option<String>,
+    /// Replaces the linked rooms; rooms left out are unlinked.
+    pub streamer_ids: Option<Vec<i32>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreatorDto {
+    pub id: i32,
+    pub name: String,
+    /// Whether the creator is live on any of the linked rooms.
+    pub is_live: bool,
+    pub streamers: Vec<StreamerDto>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl CreatorDto {
+    pub fn new(creator: Model, streamers: Vec<StreamerModel>) -> Self {
+        Self {
+            id: creator.id,
+            name: creator.name,
+            is_live: streamers.iter().any(|streamer| streamer.is_live),
+            streamers: streamers.into_iter().map(StreamerDto::from).collect(),
+            created_at: creator.created_at,
+            updated_at: creator.updated_at,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FeedTokenDto {
+    /// Secret token embedded in the feed URLs. Rotating it revokes the old URLs.
+    pub token: String,
+    #[schema(example = "https://fusion.example.com/feeds/0f6c5a8e3f6d4c0e9a5b1e0d2c7f4a91.atom")]
+    pub atom_url: String,
+    #[schema(example = "https://fusion.example.com/feeds/0f6c5a8e3f6d4c0e9a5b1e0d2c7f4a91.rss")]
+    pub rss_url: String,
+    /// Subscribable calendar of past and predicted live sessions.
+    #[schema(example = "https://fusion.example.com/feeds/0f6c5a8e3f6d4c0e9a5b1e0d2c7f4a91.ics")]
+    pub ics_url: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct FeedQuery {
+    /// Calendar only: leave out predicted sessions below this confidence (0.0 - 1.0).
+    pub min_confidence: Option<f64>,
+}
+
+impl FeedTokenDto {
+    pub fn new(token: String, base_url: &str) -> Self {
+        Self {
+            atom_url: format!("{base_url}/feeds/{token}.atom"),
+            rss_url: format!("{base_url}/feeds/{token}.rss"),
+            ics_url: format!("{base_url}/feeds/{token}.ics"),
+            token,
+        }
+    }
+}
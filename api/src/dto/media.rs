@@ -0,0 +1,9 @@
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MediaQuery {
+    /// Scale the image down to this width, one of `media.widths`.
+    pub width: Option<u32>,
+}
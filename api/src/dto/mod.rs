@@ -1,9 +1,29 @@
 mod auth;
+mod creator;
+mod feed;
+mod media;
+mod notification_channel;
+mod notification_outbox;
+mod share_page;
+mod streamer;
+mod subscription;
+mod subscription_group;
+mod transfer;
 mod user;
 
 pub use auth::*;
+pub use creator::*;
+pub use feed::*;
+pub use media::*;
+pub use notification_channel::*;
+pub use notification_outbox::*;
 use serde::{Deserialize, Serialize};
+pub use share_page::*;
 use std::num::NonZeroU64;
+pub use streamer::*;
+pub use subscription::*;
+pub use subscription_group::*;
+pub use transfer::*;
 pub use user::*;
 use utoipa::ToSchema;
 
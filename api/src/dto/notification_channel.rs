@@ -0,0 +1,123 @@
+use crate::notification::DeliveryReport;
+use chrono::NaiveDateTime;
+use entity::notification_channel::Model;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateNotificationChannelRequest {
+    #[validate(length(min = 1, max = 32))]
+    #[schema(example = "bark")]
+    pub channel: String,
+    #[validate(length(min = 1, max = 2048))]
+    pub destination: String,
+    #[serde(default)]
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationChannelDto {
+    pub id: i32,
+    pub channel: String,
+    pub destination: String,
+    pub metadata: HashMap<String, String>,
+    pub enabled: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<Model> for NotificationChannelDto {
+    fn from(value: Model) -> Self {
+        Self {
+            id: value.id,
+            channel: value.channel,
+            destination: value.destination,
+            metadata: serde_json::from_value(value.metadata).unwrap_or_default(),
+            enabled: value.enabled,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+/// A browser `PushSubscription`, as serialized by `subscription.toJSON()`.
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PushSubscriptionRequest {
+    #[validate(length(min = 1, max = 2048))]
+    pub endpoint: String,
+    /// Unused: push services answer 404 or 410 once a subscription expires.
+    #[serde(default)]
+    pub expiration_time: Option<f64>,
+    pub keys: PushSubscriptionKeys,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PushSubscriptionKeys {
+    pub p256dh: String,
+    pub auth: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct RemovePushSubscriptionRequest {
+    #[validate(length(min = 1, max = 2048))]
+    pub endpoint: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WebPushKeyDto {
+    /// Base64url VAPID public key, passed as `applicationServerKey` to
+    /// `pushManager.subscribe()`.
+    pub public_key: String,
+}
+
+/// Outcome of a notification, per channel.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeliveryReportDto {
+    pub delivered: usize,
+    pub failed: usize,
+    pub deliveries: Vec<DeliveryDto>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DeliveryDto {
+    pub channel_id: Option<i32>,
+    #[schema(example = "bark")]
+    pub channel: String,
+    pub delivered: bool,
+    /// `unconfigured`, `timeout`, `network`, `rate_limited`, `server_error`,
+    /// `gone` or `rejected`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_class: Option<String>,
+    /// Whether sending again later may succeed.
+    pub retryable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<DeliveryReport> for DeliveryReportDto {
+    fn from(report: DeliveryReport) -> Self {
+        let deliveries: Vec<DeliveryDto> = report
+            .deliveries()
+            .iter()
+            .map(|delivery| {
+                let failure = delivery.result.as_ref().err();
+                DeliveryDto {
+                    channel_id: delivery.address_id,
+                    channel: delivery.channel.as_str().to_string(),
+                    delivered: failure.is_none(),
+                    error_class: failure.map(|failure| failure.class.as_str().to_string()),
+                    retryable: failure.is_some_and(|failure| failure.is_retryable()),
+                    error: failure.map(|failure| format!("{:#}", failure.error)),
+                }
+            })
+            .collect();
+        Self {
+            delivered: report.delivered(),
+            failed: deliveries.len() - report.delivered(),
+            deliveries,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use crate::domain::notification_outbox::{OutboxRecipient, OutboxStatus};
+use chrono::NaiveDateTime;
+use entity::notification_outbox::Model;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NotificationOutboxQuery {
+    /// Only return entries in this state.
+    pub status: Option<OutboxStatus>,
+}
+
+/// A queued notification for a live or change event.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NotificationOutboxDto {
+    pub id: i64,
+    pub streamer_id: i32,
+    #[schema(example = "live")]
+    pub event: String,
+    pub live_session_id: Option<i32>,
+    pub change_ids: Vec<i64>,
+    #[schema(example = "dead")]
+    pub status: String,
+    pub attempts: i32,
+    /// When a pending entry is tried next.
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    /// Users still to reach, `null` until the first attempt worked out the
+    /// subscribers.
+    pub recipients: Option<Vec<OutboxRecipientDto>>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    /// When the notification first reached a recipient.
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct OutboxRecipientDto {
+    pub subscription_id: i32,
+    pub user_id: i32,
+    pub title: String,
+    /// Channels left to try, `null` for every enabled channel of the user.
+    pub channel_ids: Option<Vec<i32>>,
+}
+
+impl From<OutboxRecipient> for OutboxRecipientDto {
+    fn from(value: OutboxRecipient) -> Self {
+        Self {
+            subscription_id: value.subscription_id,
+            user_id: value.user_id,
+            title: value.title,
+            channel_ids: value.channel_ids,
+        }
+    }
+}
+
+impl From<Model> for NotificationOutboxDto {
+    fn from(value: Model) -> Self {
+        Self {
+            id: value.id,
+            streamer_id: value.streamer_id,
+            event: value.event,
+            live_session_id: value.live_session_id,
+            change_ids: serde_json::from_value(value.change_ids).unwrap_or_default(),
+            status: value.status,
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            last_error: value.last_error,
+            recipients: value
+                .recipients
+                .and_then(|recipients| {
+                    serde_json::from_value::<Vec<OutboxRecipient>>(recipients).ok()
+                })
+                .map(|recipients| recipients.into_iter().map(Into::into).collect()),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+            delivered_at: value.delivered_at,
+        }
+    }
+}
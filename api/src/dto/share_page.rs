@@ -0,0 +1,58 @@
+use chrono::NaiveDateTime;
+use entity::share_page::Model;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateSharePageRequest {
+    /// Group whose streamers are shown on the page.
+    #[validate(range(min = 1))]
+    pub group_id: i32,
+    /// Heading of the page; defaults to the group name.
+    #[validate(length(min = 1, max = 100))]
+    #[schema(example = "Our partners")]
+    pub title: Option<String>,
+    /// UTC time after which the page stops being served.
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SharePageDto {
+    pub id: i32,
+    pub group_id: i32,
+    pub title: String,
+    /// Unguessable part of the public URLs. Rotating it revokes the old URLs.
+    pub slug: String,
+    #[schema(example = "https://fusion.example.com/share/0f6c5a8e3f6d4c0e9a5b1e0d2c7f4a91")]
+    pub url: String,
+    #[schema(example = "https://fusion.example.com/share/0f6c5a8e3f6d4c0e9a5b1e0d2c7f4a91.json")]
+    pub json_url: String,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SharedPageQuery {
+    /// Only list streamers that are live right now.
+    #[serde(default)]
+    pub live: bool,
+}
+
+impl SharePageDto {
+    pub fn new(page: Model, base_url: &str) -> Self {
+        Self {
+            id: page.id,
+            group_id: page.group_id,
+            title: page.title,
+            url: format!("{base_url}/share/{}", page.slug),
+            json_url: format!("{base_url}/share/{}.json", page.slug),
+            slug: page.slug,
+            expires_at: page.expires_at,
+            created_at: page.created_at,
+            updated_at: page.updated_at,
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::domain::media::{self, MediaKind};
+use crate::domain::streamer_changes::ChangedField;
+use chrono::NaiveDateTime;
+use entity::live_state_transition::Model as LiveStateTransitionModel;
+use entity::streamer::Model;
+use entity::streamer_change::Model as StreamerChangeModel;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateStreamerRequest {
+    #[validate(length(min = 1, max = 32))]
+    #[schema(example = "bilibili")]
+    pub platform: String,
+    #[validate(length(min = 1, max = 64))]
+    #[schema(example = "7734200")]
+    pub room_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StreamerDto {
+    pub id: i32,
+    pub platform: String,
+    pub room_id: String,
+    pub name: String,
+    pub avatar: String,
+    /// Same-origin copy of `avatar`, see `GET /api/v1/media/{kind}/{hash}`.
+    pub avatar_proxy_url: Option<String>,
+    pub description: String,
+    pub room_url: String,
+    /// Confirmed live state; `live_state` also shows a change awaiting confirmation.
+    pub is_live: bool,
+    #[schema(example = "live")]
+    pub live_state: String,
+    pub title: String,
+    pub category: String,
+    pub cover_image: String,
+    /// Same-origin copy of `cover_image`, kept after the stream ends.
+    pub cover_image_proxy_url: Option<String>,
+    pub viewer_count: i64,
+    /// Room announcement, `null` until it was first fetched.
+    pub announcement: Option<String>,
+    pub live_started_at: Option<NaiveDateTime>,
+    pub last_checked_at: Option<NaiveDateTime>,
+    /// Creator this room is linked to, if any.
+    pub creator_id: Option<i32>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<Model> for StreamerDto {
+    fn from(value: Model) -> Self {
+        Self {
+            id: value.id,
+            platform: value.platform,
+            room_id: value.room_id,
+            name: value.name,
+            avatar_proxy_url: media::proxy_path(MediaKind::Avatar, &value.avatar),
+            avatar: value.avatar,
+            description: value.description,
+            room_url: value.room_url,
+            is_live: value.is_live,
+            live_state: value.live_state,
+            title: value.title,
+            category: value.category,
+            cover_image_proxy_url: media::proxy_path(MediaKind::Cover, &value.cover_image),
+            cover_image: value.cover_image,
+            viewer_count: value.viewer_count,
+            announcement: value.announcement,
+            live_started_at: value.live_started_at,
+            last_checked_at: value.last_checked_at,
+            creator_id: value.creator_id,
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ViewerSeriesQuery {
+    /// Start of the range (inclusive, UTC). Defaults to 24 hours before `to`.
+    pub from: Option<NaiveDateTime>,
+    /// End of the range (exclusive, UTC). Defaults to now.
+    pub to: Option<NaiveDateTime>,
+    /// Width of each returned bucket in seconds. Defaults to 300.
+    pub bucket_secs: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ViewerPointDto {
+    pub bucket_start: NaiveDateTime,
+    pub samples: i64,
+    pub min: i64,
+    pub max: i64,
+    pub avg: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ViewerSeriesDto {
+    pub streamer_id: i32,
+    pub from: NaiveDateTime,
+    pub to: NaiveDateTime,
+    pub bucket_secs: u32,
+    pub points: Vec<ViewerPointDto>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct StreamerChangesQuery {
+    /// Only return changes of this field.
+    pub field: Option<ChangedField>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StreamerChangeDto {
+    pub id: i64,
+    #[schema(example = "title")]
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+    /// Whether the change happened while the streamer was live.
+    pub while_live: bool,
+    pub changed_at: NaiveDateTime,
+}
+
+impl From<StreamerChangeModel> for StreamerChangeDto {
+    fn from(value: StreamerChangeModel) -> Self {
+        Self {
+            id: value.id,
+            field: value.field,
+            old_value: value.old_value,
+            new_value: value.new_value,
+            while_live: value.while_live,
+            changed_at: value.changed_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LiveStateTransitionDto {
+    pub id: i64,
+    #[schema(example = "live")]
+    pub from_state: String,
+    #[schema(example = "maybe_offline")]
+    pub to_state: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<LiveStateTransitionModel> for LiveStateTransitionDto {
+    fn from(value: LiveStateTransitionModel) -> Self {
+        Self {
+            id: value.id,
+            from_state: value.from_state,
+            to_state: value.to_state,
+            created_at: value.created_at,
+        }
+    }
+}
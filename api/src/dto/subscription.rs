@@ -0,0 +1,168 @@
+use crate::domain::notification_rules::NotificationRules;
+use crate::dto::StreamerDto;
+use chrono::NaiveDateTime;
+use entity::streamer::Model as StreamerModel;
+use entity::subscription::Model;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateSubscriptionRequest {
+    #[validate(range(min = 1))]
+    pub streamer_id: i32,
+    #[serde(default)]
+    pub rules: NotificationRules,
+    /// Notify when the stream title changes mid-stream.
+    #[serde(default)]
+    pub notify_title_change: bool,
+    /// Notify when the stream category changes mid-stream.
+    #[serde(default)]
+    pub notify_category_change: bool,
+    /// Notify when the streamer posts a new room announcement.
+    #[serde(default)]
+    pub notify_announcement_change: bool,
+    /// Group whose rules the subscription inherits.
+    #[validate(range(min = 1))]
+    pub group_id: Option<i32>,
+    #[serde(default)]
+    #[schema(example = json!(["esports", "partners"]))]
+    pub tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateSubscriptionRulesRequest {
+    pub rules: NotificationRules,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateSubscriptionRequest {
+    pub notify_title_change: Option<bool>,
+    pub notify_category_change: Option<bool>,
+    pub notify_announcement_change: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct AssignSubscriptionGroupRequest {
+    /// Group to move the subscription into; `null` removes it from its group.
+    #[validate(range(min = 1))]
+    pub group_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateSubscriptionTagsRequest {
+    /// Replaces all tags of the subscription.
+    #[schema(example = json!(["esports", "partners"]))]
+    pub tags: Vec<String>,
+}
+
+/// Narrows subscription listings down to a group and/or a tag.
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SubscriptionFilter {
+    pub group_id: Option<i32>,
+    pub tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TagDto {
+    pub tag: String,
+    pub subscription_count: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SubscriptionDto {
+    pub id: i32,
+    pub streamer: StreamerDto,
+    pub rules: NotificationRules,
+    pub notify_title_change: bool,
+    pub notify_category_change: bool,
+    pub notify_announcement_change: bool,
+    pub group_id: Option<i32>,
+    pub tags: Vec<String>,
+    pub last_notified_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl SubscriptionDto {
+    pub fn new(subscription: Model, streamer: StreamerModel, tags: Vec<String>) -> Self {
+        Self {
+            id: subscription.id,
+            streamer: streamer.into(),
+            rules: serde_json::from_value(subscription.rules).unwrap_or_default(),
+            notify_title_change: subscription.notify_title_change,
+            notify_category_change: subscription.notify_category_change,
+            notify_announcement_change: subscription.notify_announcement_change,
+            group_id: subscription.group_id,
+            tags,
+            last_notified_at: subscription.last_notified_at,
+            created_at: subscription.created_at,
+            updated_at: subscription.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LiveSort {
+    /// Most viewers first.
+    #[default]
+    Viewers,
+    /// Most recently started first.
+    StartedAt,
+    /// By platform, then most viewers first.
+    Platform,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct LiveQuery {
+    /// Sort order of the entries. Defaults to `viewers`.
+    pub sort: Option<LiveSort>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LiveStatusDto {
+    pub title: String,
+    pub category: String,
+    pub cover_image: String,
+    pub viewer_count: i64,
+    pub started_at: NaiveDateTime,
+    pub last_checked_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct LiveStreamerDto {
+    pub subscription_id: i32,
+    pub group_id: Option<i32>,
+    pub streamer: StreamerDto,
+    pub status: LiveStatusDto,
+    /// Seconds since the stream started.
+    pub uptime_secs: i64,
+}
+
+impl LiveStreamerDto {
+    pub fn new(
+        subscription: &Model,
+        streamer: StreamerModel,
+        started_at: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> Self {
+        let status = LiveStatusDto {
+            title: streamer.title.clone(),
+            category: streamer.category.clone(),
+            cover_image: streamer.cover_image.clone(),
+            viewer_count: streamer.viewer_count,
+            started_at,
+            last_checked_at: streamer.last_checked_at,
+        };
+        Self {
+            subscription_id: subscription.id,
+            group_id: subscription.group_id,
+            streamer: streamer.into(),
+            status,
+            uptime_secs: (now - started_at).num_seconds().max(0),
+        }
+    }
+}
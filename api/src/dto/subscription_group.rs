@@ -0,0 +1,46 @@
+use crate::domain::notification_rules::NotificationRules;
+use chrono::NaiveDateTime;
+use entity::subscription_group::Model;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct CreateSubscriptionGroupRequest {
+    #[validate(length(min = 1, max = 64))]
+    #[schema(example = "esports")]
+    pub name: String,
+    /// Rules inherited by the subscriptions of the group.
+    #[serde(default)]
+    pub rules: NotificationRules,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate, ToSchema)]
+pub struct UpdateSubscriptionGroupRequest {
+    #[validate(length(min = 1, max = 64))]
+    pub name: Option<String>,
+    pub rules: Option<NotificationRules>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SubscriptionGroupDto {
+    pub id: i32,
+    pub name: String,
+    pub rules: NotificationRules,
+    pub subscription_count: u64,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl SubscriptionGroupDto {
+    pub fn new(group: Model, subscription_count: u64) -> Self {
+        Self {
+            id: group.id,
+            name: group.name,
+            rules: serde_json::from_value(group.rules).unwrap_or_default(),
+            subscription_count,
+            created_at: group.created_at,
+            updated_at: group.updated_at,
+        }
+    }
+}
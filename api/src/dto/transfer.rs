@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// Resolve and validate everything without writing to the database.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStatus {
+    /// Created, or would be created in a dry run.
+    Created,
+    /// Already present with the same settings.
+    Exists,
+    /// Already present with different settings; left unchanged.
+    Conflict,
+    /// The entry itself is malformed.
+    Invalid,
+    /// The room could not be found on its platform.
+    Unresolved,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubscriptionImportResult {
+    /// The entry as given in the import, e.g. the room URL.
+    pub input: String,
+    pub platform: Option<String>,
+    pub room_id: Option<String>,
+    pub name: Option<String>,
+    pub status: ImportStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct GroupImportResult {
+    pub name: String,
+    pub status: ImportStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChannelImportResult {
+    pub channel: String,
+    pub destination: String,
+    pub status: ImportStatus,
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, ToSchema)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub exists: usize,
+    pub conflict: usize,
+    pub invalid: usize,
+    pub unresolved: usize,
+}
+
+impl ImportSummary {
+    fn count(&mut self, status: ImportStatus) {
+        match status {
+            ImportStatus::Created => self.created += 1,
+            ImportStatus::Exists => self.exists += 1,
+            ImportStatus::Conflict => self.conflict += 1,
+            ImportStatus::Invalid => self.invalid += 1,
+            ImportStatus::Unresolved => self.unresolved += 1,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ImportReportDto {
+    pub dry_run: bool,
+    pub summary: ImportSummary,
+    pub groups: Vec<GroupImportResult>,
+    pub subscriptions: Vec<SubscriptionImportResult>,
+    pub channels: Vec<ChannelImportResult>,
+}
+
+impl ImportReportDto {
+    pub fn new(
+        dry_run: bool,
+        groups: Vec<GroupImportResult>,
+        subscriptions: Vec<SubscriptionImportResult>,
+        channels: Vec<ChannelImportResult>,
+    ) -> Self {
+        let mut summary = ImportSummary::default();
+        groups
+            .iter()
+            .map(|result| result.status)
+            .chain(subscriptions.iter().map(|result| result.status))
+            .chain(channels.iter().map(|result| result.status))
+            .for_each(|status| summary.count(status));
+        Self {
+            dry_run,
+            summary,
+            groups,
+            subscriptions,
+            channels,
+        }
+    }
+}
@@ -40,6 +40,7 @@ impl AppError {
             AppError::Forbidden(_) => StatusCode::FORBIDDEN,
             AppError::NotFound { .. } => StatusCode::NOT_FOUND,
             AppError::Duplicate { .. } => StatusCode::CONFLICT,
+            AppError::BadGateway(_) => StatusCode::BAD_GATEWAY,
         }
     }
 
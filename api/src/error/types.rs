@@ -34,6 +34,9 @@ pub enum AppError {
         field: String,
         value: String,
     },
+
+    #[error("Bad gateway: {0}")]
+    BadGateway(String),
 }
 
 impl From<AnyhowError> for AppError {
@@ -0,0 +1,182 @@
+use chrono::NaiveDateTime;
+use entity::live_session::Model as LiveSessionModel;
+use entity::streamer::Model as StreamerModel;
+use entity::streamer_change::Model as StreamerChangeModel;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+
+/// Streamer event pushed to connected dashboards.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct StreamerEvent {
+    pub streamer_id: i32,
+    pub streamer_name: String,
+    pub platform: String,
+    pub occurred_at: NaiveDateTime,
+    #[serde(flatten)]
+    pub kind: StreamerEventKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamerEventKind {
+    Live {
+        title: String,
+        category: String,
+        started_at: NaiveDateTime,
+    },
+    Offline,
+    TitleChange {
+        old_title: String,
+        new_title: String,
+    },
+    CategoryChange {
+        old_category: String,
+        new_category: String,
+    },
+}
+
+impl StreamerEventKind {
+    /// Event name used for the SSE `event:` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StreamerEventKind::Live { .. } => "live",
+            StreamerEventKind::Offline => "offline",
+            StreamerEventKind::TitleChange { .. } => "title_change",
+            StreamerEventKind::CategoryChange { .. } => "category_change",
+        }
+    }
+}
+
+impl StreamerEvent {
+    fn new(streamer: &StreamerModel, occurred_at: NaiveDateTime, kind: StreamerEventKind) -> Self {
+        Self {
+            streamer_id: streamer.id,
+            streamer_name: streamer.name.clone(),
+            platform: streamer.platform.clone(),
+            occurred_at,
+            kind,
+        }
+    }
+
+    pub fn went_live(streamer: &StreamerModel, session: &LiveSessionModel) -> Self {
+        Self::new(
+            streamer,
+            session.started_at,
+            StreamerEventKind::Live {
+                title: streamer.title.clone(),
+                category: streamer.category.clone(),
+                started_at: session.started_at,
+            },
+        )
+    }
+
+    pub fn went_offline(streamer: &StreamerModel, occurred_at: NaiveDateTime) -> Self {
+        Self::new(streamer, occurred_at, StreamerEventKind::Offline)
+    }
+
+    /// Builds the event for a recorded change; only title and category changes are pushed.
+    pub fn changed(streamer: &StreamerModel, change: &StreamerChangeModel) -> Option<Self> {
+        let kind = match change.field.as_str() {
+            "title" => StreamerEventKind::TitleChange {
+                old_title: change.old_value.clone(),
+                new_title: change.new_value.clone(),
+            },
+            "category" => StreamerEventKind::CategoryChange {
+                old_category: change.old_value.clone(),
+                new_category: change.new_value.clone(),
+            },
+            _ => return None,
+        };
+        Some(Self::new(streamer, change.changed_at, kind))
+    }
+}
+
+/// An event together with the users allowed to receive it.
+#[derive(Debug)]
+pub struct EventEnvelope {
+    pub user_ids: Vec<i32>,
+    pub event: StreamerEvent,
+}
+
+impl EventEnvelope {
+    pub fn is_for(&self, user_id: i32) -> bool {
+        self.user_ids.contains(&user_id)
+    }
+}
+
+/// In-process fan-out of streamer events to the connected event streams.
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<Arc<EventEnvelope>>,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<EventEnvelope>> {
+        self.sender.subscribe()
+    }
+
+    /// Whether anyone is listening; lets publishers skip building events nobody reads.
+    pub fn has_listeners(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+
+    pub fn publish(&self, user_ids: Vec<i32>, event: StreamerEvent) {
+        if user_ids.is_empty() {
+            return;
+        }
+        // Sending only fails when there are no receivers, which is not an error here.
+        let _ = self
+            .sender
+            .send(Arc::new(EventEnvelope { user_ids, event }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(kind: StreamerEventKind) -> StreamerEvent {
+        StreamerEvent {
+            streamer_id: 7,
+            streamer_name: "alice".to_string(),
+            platform: "Bilibili".to_string(),
+            occurred_at: NaiveDateTime::default(),
+            kind,
+        }
+    }
+
+    #[test]
+    fn serializes_kind_as_type_tag() {
+        let value = serde_json::to_value(event(StreamerEventKind::TitleChange {
+            old_title: "a".to_string(),
+            new_title: "b".to_string(),
+        }))
+        .unwrap();
+
+        assert_eq!(value["type"], "title_change");
+        assert_eq!(value["streamer_id"], 7);
+        assert_eq!(value["new_title"], "b");
+    }
+
+    #[tokio::test]
+    async fn delivers_envelopes_with_recipients() {
+        let bus = EventBus::new(8);
+        let mut receiver = bus.subscribe();
+        assert!(bus.has_listeners());
+
+        bus.publish(vec![], event(StreamerEventKind::Offline));
+        bus.publish(vec![1, 2], event(StreamerEventKind::Offline));
+
+        let envelope = receiver.recv().await.unwrap();
+        assert!(envelope.is_for(2));
+        assert!(!envelope.is_for(3));
+        assert!(receiver.try_recv().is_err());
+    }
+}
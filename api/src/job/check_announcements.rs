@@ -0,0 +1,49 @@
+use crate::AppState;
+use crate::job::types::{AppJob, JobConfig, JobOverlapStrategy};
+use migration::async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Fetches room announcements, where streamers post their schedules. New ones
+/// are queued for subscribers in the notification outbox.
+pub struct CheckAnnouncementsJob {}
+
+impl CheckAnnouncementsJob {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl AppJob for CheckAnnouncementsJob {
+    fn name(&self) -> &'static str {
+        "check-announcements"
+    }
+
+    fn config(&self) -> JobConfig {
+        JobConfig {
+            overlap_strategy: JobOverlapStrategy::Skip,
+        }
+    }
+
+    async fn execute(&self, state: Arc<AppState>) -> anyhow::Result<()> {
+        let streamers = state.services.streamer.find_all().await?;
+        let total = streamers.len();
+        let mut changed = 0;
+
+        for streamer in streamers {
+            let streamer_id = streamer.id;
+            match state.services.streamer.refresh_announcement(streamer).await {
+                Ok(changes) if !changes.is_empty() => changed += 1,
+                Ok(_) => {}
+                Err(err) => warn!(streamer_id, ?err, "failed to refresh room announcement"),
+            }
+        }
+
+        info!(
+            job = self.name(),
+            total, changed, "checked room announcements"
+        );
+        Ok(())
+    }
+}
@@ -1,10 +1,13 @@
 use crate::AppState;
+use crate::event::StreamerEvent;
 use crate::job::types::{AppJob, JobConfig, JobOverlapStrategy};
+use crate::service::{DueStreamers, LiveCheck, LiveTransition};
+use chrono::Utc;
+use entity::streamer::Model as StreamerModel;
+use entity::streamer_change::Model as StreamerChangeModel;
 use migration::async_trait::async_trait;
 use std::sync::Arc;
-use std::time::Duration;
-use tokio::time;
-use tracing::info;
+use tracing::{info, warn};
 
 pub struct CheckLivedJob {}
 
@@ -12,6 +15,43 @@ impl CheckLivedJob {
     pub fn new() -> Self {
         Self {}
     }
+
+    /// Pushes the detected events to the event streams of the streamer's subscribers.
+    async fn publish_events(
+        state: &AppState,
+        streamer: &StreamerModel,
+        transition: &LiveTransition,
+        changes: &[StreamerChangeModel],
+    ) -> anyhow::Result<()> {
+        let mut events = Vec::new();
+        match transition {
+            LiveTransition::WentLive(session) => {
+                events.push(StreamerEvent::went_live(streamer, session))
+            }
+            LiveTransition::WentOffline(ended_at) => {
+                events.push(StreamerEvent::went_offline(streamer, *ended_at))
+            }
+            LiveTransition::Unchanged => {}
+        }
+        events.extend(
+            changes
+                .iter()
+                .filter_map(|change| StreamerEvent::changed(streamer, change)),
+        );
+        if events.is_empty() || !state.events.has_listeners() {
+            return Ok(());
+        }
+
+        let user_ids = state
+            .services
+            .subscription
+            .subscriber_ids(streamer.id)
+            .await?;
+        for event in events {
+            state.events.publish(user_ids.clone(), event);
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -26,9 +66,64 @@ impl AppJob for CheckLivedJob {
         }
     }
 
-    async fn execute(&self, _state: Arc<AppState>) -> anyhow::Result<()> {
-        time::sleep(Duration::from_secs(70)).await;
-        info!("check-lived {}", self.name());
+    async fn execute(&self, state: Arc<AppState>) -> anyhow::Result<()> {
+        let now = Utc::now().naive_utc();
+        let DueStreamers {
+            streamers,
+            deferred,
+        } = state.services.polling.due(now).await?;
+        let due = streamers.len();
+        let mut live = 0;
+        let mut polled = Vec::with_capacity(due);
+
+        for streamer in streamers {
+            let streamer_id = streamer.id;
+            let LiveCheck {
+                streamer,
+                transition,
+                changes,
+            } = match state
+                .services
+                .streamer
+                .refresh_live_status(streamer.clone())
+                .await
+            {
+                Ok(check) => check,
+                Err(err) => {
+                    warn!(streamer_id, ?err, "failed to refresh live status");
+                    // Retried at its usual interval instead of on every run.
+                    polled.push(streamer);
+                    continue;
+                }
+            };
+
+            if streamer.is_live {
+                live += 1;
+            }
+
+            if let Err(err) = state.services.viewer_stats.record_sample(&streamer).await {
+                warn!(streamer_id, ?err, "failed to record viewer sample");
+            }
+
+            if let Err(err) = Self::publish_events(&state, &streamer, &transition, &changes).await {
+                warn!(streamer_id, ?err, "failed to publish streamer events");
+            }
+
+            // Notifications were queued with the session and changes; the
+            // dispatch-notifications job sends them.
+            polled.push(streamer);
+        }
+
+        state
+            .services
+            .polling
+            .schedule(&polled, Utc::now().naive_utc())
+            .await?;
+
+        info!(
+            job = self.name(),
+            due, deferred, live, "checked live status"
+        );
         Ok(())
     }
 }
@@ -0,0 +1,44 @@
+use crate::AppState;
+use crate::job::types::{AppJob, JobConfig, JobOverlapStrategy};
+use crate::service::OutboxRun;
+use migration::async_trait::async_trait;
+use std::sync::Arc;
+use tracing::info;
+
+/// Sends the notifications queued in the outbox and retries failed ones.
+pub struct DispatchNotificationsJob {}
+
+impl DispatchNotificationsJob {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl AppJob for DispatchNotificationsJob {
+    fn name(&self) -> &'static str {
+        "dispatch-notifications"
+    }
+
+    fn config(&self) -> JobConfig {
+        JobConfig {
+            overlap_strategy: JobOverlapStrategy::Skip,
+        }
+    }
+
+    async fn execute(&self, state: Arc<AppState>) -> anyhow::Result<()> {
+        let OutboxRun {
+            claimed,
+            delivered,
+            retrying,
+            dead,
+        } = state.services.notification.dispatch_outbox().await?;
+        if claimed > 0 {
+            info!(
+                job = self.name(),
+                claimed, delivered, retrying, dead, "dispatched notifications"
+            );
+        }
+        Ok(())
+    }
+}
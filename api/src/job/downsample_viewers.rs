@@ -0,0 +1,29 @@
+use crate::AppState;
+use crate::job::types::AppJob;
+use chrono::Utc;
+use migration::async_trait::async_trait;
+use std::sync::Arc;
+
+pub struct DownsampleViewersJob {}
+
+impl DownsampleViewersJob {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl AppJob for DownsampleViewersJob {
+    fn name(&self) -> &'static str {
+        "downsample-viewers"
+    }
+
+    async fn execute(&self, state: Arc<AppState>) -> anyhow::Result<()> {
+        state
+            .services
+            .viewer_stats
+            .downsample(Utc::now().naive_utc())
+            .await?;
+        Ok(())
+    }
+}
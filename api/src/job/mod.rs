@@ -1,4 +1,8 @@
+pub mod check_announcements;
 pub mod check_lived;
+pub mod dispatch_notifications;
+pub mod downsample_viewers;
+pub mod refresh_profiles;
 mod registry;
 mod types;
 
@@ -13,7 +17,11 @@ use tokio_cron_scheduler::JobScheduler;
 use tracing::{error, info};
 
 use crate::AppState;
+use crate::job::check_announcements::CheckAnnouncementsJob;
 use crate::job::check_lived::CheckLivedJob;
+use crate::job::dispatch_notifications::DispatchNotificationsJob;
+use crate::job::downsample_viewers::DownsampleViewersJob;
+use crate::job::refresh_profiles::RefreshProfilesJob;
 use crate::job::types::JobOverlapStrategy;
 
 pub struct JobManager {
@@ -25,7 +33,11 @@ pub struct JobManager {
 impl JobManager {
     pub async fn new(state: Arc<AppState>) -> Result<Self> {
         let mut registry = JobRegistry::new();
+        registry.register(CheckAnnouncementsJob::new());
         registry.register(CheckLivedJob::new());
+        registry.register(DispatchNotificationsJob::new());
+        registry.register(DownsampleViewersJob::new());
+        registry.register(RefreshProfilesJob::new());
         Self::with_registry(state, registry).await
     }
 
@@ -0,0 +1,47 @@
+use crate::AppState;
+use crate::job::types::{AppJob, JobConfig, JobOverlapStrategy};
+use migration::async_trait::async_trait;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+/// Re-fetches streamer profiles so name and avatar changes end up in the change history.
+pub struct RefreshProfilesJob {}
+
+impl RefreshProfilesJob {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait]
+impl AppJob for RefreshProfilesJob {
+    fn name(&self) -> &'static str {
+        "refresh-profiles"
+    }
+
+    fn config(&self) -> JobConfig {
+        JobConfig {
+            overlap_strategy: JobOverlapStrategy::Skip,
+        }
+    }
+
+    async fn execute(&self, state: Arc<AppState>) -> anyhow::Result<()> {
+        let streamers = state.services.streamer.find_all().await?;
+        let total = streamers.len();
+        let mut changed = 0;
+
+        for streamer in streamers {
+            let streamer_id = streamer.id;
+            match state.services.streamer.refresh_profile(streamer).await {
+                Ok(changes) => changed += changes.len(),
+                Err(err) => warn!(streamer_id, ?err, "failed to refresh streamer profile"),
+            }
+        }
+
+        info!(
+            job = self.name(),
+            total, changed, "refreshed streamer profiles"
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,19 @@
 use crate::config::Config;
+use crate::event::EventBus;
 use crate::job::JobManager;
 use crate::notification::bark::BarkProvider;
+use crate::notification::dingtalk::DingTalkProvider;
+use crate::notification::discord::DiscordProvider;
+use crate::notification::email::EmailProvider;
+use crate::notification::feishu::FeishuProvider;
+use crate::notification::gotify::GotifyProvider;
+use crate::notification::ntfy::NtfyProvider;
+use crate::notification::pushover::PushoverProvider;
+use crate::notification::slack::SlackProvider;
+use crate::notification::telegram::TelegramProvider;
+use crate::notification::web_push::WebPushProvider;
+use crate::notification::webhook::WebhookProvider;
+use crate::notification::wecom::WeComProvider;
 use crate::notification::{NotificationCenter, NotificationProvider};
 use crate::utils::jwt::JwtUtil;
 use live_platform::LivePlatformProvider;
@@ -12,6 +25,7 @@ mod config;
 mod domain;
 mod dto;
 mod error;
+mod event;
 mod job;
 mod notification;
 mod repository;
@@ -25,6 +39,7 @@ pub struct AppState {
     pub jwt: Arc<JwtUtil>,
     pub live_platform_provider: Arc<LivePlatformProvider>,
     pub notification_center: Arc<NotificationCenter>,
+    pub events: EventBus,
 }
 
 pub struct Application {
@@ -34,34 +49,92 @@ pub struct Application {
     pub state: Arc<AppState>,
 }
 
-impl Application {
+impl AppState {
+    /// Connects to the database and wires up the services, without starting
+    /// the HTTP server or the jobs.
     pub async fn build(app_config: Config) -> anyhow::Result<Self> {
         let db = app_config.database.create_db().await?;
 
         let repos = Arc::new(repository::Repositories::new(db));
 
         let jwt = Arc::new(JwtUtil::new(app_config.jwt.clone()));
-        let services = Arc::new(service::Services::build(repos, jwt.clone()).await?);
-
         let live_platform_provider = Arc::new(LivePlatformProvider::new()?);
 
-        let bark_provider: Arc<dyn NotificationProvider> = Arc::new(BarkProvider::new()?);
-        let notification_center = Arc::new(NotificationCenter::with_providers(vec![bark_provider]));
+        let mut providers: Vec<Arc<dyn NotificationProvider>> = vec![
+            Arc::new(BarkProvider::new(app_config.notification.bark.clone())?),
+            Arc::new(WebhookProvider::new(
+                app_config.notification.webhook.clone(),
+            )?),
+            Arc::new(DiscordProvider::new(
+                app_config.notification.discord.clone(),
+            )?),
+            Arc::new(SlackProvider::new(app_config.notification.slack.clone())?),
+            Arc::new(DingTalkProvider::new(
+                app_config.notification.dingtalk.clone(),
+            )?),
+            Arc::new(FeishuProvider::feishu(&app_config.notification.feishu)?),
+            Arc::new(FeishuProvider::lark(&app_config.notification.feishu)?),
+            Arc::new(WeComProvider::new(app_config.notification.wecom.clone())?),
+            Arc::new(NtfyProvider::new(app_config.notification.ntfy.clone())?),
+            Arc::new(GotifyProvider::new(app_config.notification.gotify.clone())?),
+        ];
+        if app_config.notification.telegram.is_enabled() {
+            providers.push(Arc::new(TelegramProvider::new(
+                app_config.notification.telegram.clone(),
+            )?));
+        }
+        if app_config.notification.pushover.is_enabled() {
+            providers.push(Arc::new(PushoverProvider::new(
+                app_config.notification.pushover.clone(),
+            )?));
+        }
+        if app_config.notification.web_push.is_enabled() {
+            providers.push(Arc::new(WebPushProvider::new(
+                app_config.notification.web_push.clone(),
+            )?));
+        }
+        if app_config.notification.email.is_enabled() {
+            providers.push(Arc::new(EmailProvider::new(
+                &app_config.notification.email,
+            )?));
+        }
+        let notification_center = Arc::new(NotificationCenter::with_providers(
+            &app_config.notification.delivery,
+            providers,
+        ));
 
-        let state = Arc::new(AppState {
-            config: app_config.clone(),
+        let services = Arc::new(
+            service::Services::build(
+                &app_config,
+                repos,
+                jwt.clone(),
+                live_platform_provider.clone(),
+                notification_center.clone(),
+            )
+            .await?,
+        );
+
+        Ok(Self {
+            events: EventBus::new(app_config.events.channel_capacity),
+            config: app_config,
             services,
             jwt,
             live_platform_provider,
             notification_center,
-        });
+        })
+    }
+}
+
+impl Application {
+    pub async fn build(app_config: Config) -> anyhow::Result<Self> {
+        let state = Arc::new(AppState::build(app_config.clone()).await?);
 
         let router = api::routes::create_router(state.clone());
 
         let job_manager = JobManager::new(state.clone()).await?;
 
         Ok(Self {
-            config: app_config.clone(),
+            config: app_config,
             router,
             job_manager,
             state,
@@ -153,3 +226,36 @@ pub async fn main() {
     };
     app.run().await.expect("Failed to run application");
 }
+
+/// Exports the subscriptions and notification channels of a user as
+/// pretty-printed JSON.
+pub async fn export_subscriptions(username: &str) -> anyhow::Result<String> {
+    let state = AppState::build(config::load()?).await?;
+    let transfer = &state.services.transfer;
+    let document = transfer
+        .export(transfer.find_user_id(username).await?)
+        .await?;
+    Ok(serde_json::to_string_pretty(&document)?)
+}
+
+/// Imports an export document or a list of room URLs for a user and returns the
+/// import report as pretty-printed JSON.
+pub async fn import_subscriptions(
+    username: &str,
+    input: &str,
+    dry_run: bool,
+) -> anyhow::Result<String> {
+    let state = AppState::build(config::load()?).await?;
+    let transfer = &state.services.transfer;
+    let report = transfer
+        .import(transfer.find_user_id(username).await?, input, dry_run)
+        .await?;
+    Ok(serde_json::to_string_pretty(&report)?)
+}
+
+/// Generates a VAPID key pair for Web Push, returned as base64url
+/// `(private key, public key)`.
+pub fn generate_vapid_keys() -> (String, String) {
+    let key = notification::web_push::VapidKey::generate();
+    (key.private_key(), key.public_key().to_string())
+}
@@ -1,71 +1,88 @@
+use std::time::Duration;
+
 use anyhow::Context;
 use migration::async_trait::async_trait;
+use reqwest::Url;
 
-use crate::notification::bark::msg::{BarkResponse, Msg};
+use crate::config::BarkConfig;
+use crate::notification::bark::crypto::Cipher;
+use crate::notification::bark::msg::{self, BarkResponse};
+use crate::notification::delivery::StatusError;
 use crate::notification::provider::{
     NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
 };
 
-const BASE_URL: &str = "https://api.day.app";
+const USER_AGENT: &str = "Fusion-Bark";
+const PUBLIC_RELAY_HOST: &str = "api.day.app";
 
+/// Pushes notifications through a Bark server. The address destination is
+/// the device key or device url; `server` in the address metadata points a
+/// bare key at a self-hosted server, and `key`, `mode` and `iv` turn on
+/// encrypted pushes.
 pub struct BarkProvider {
     client: reqwest::Client,
+    config: BarkConfig,
 }
 
 impl BarkProvider {
-    pub fn new() -> anyhow::Result<Self> {
+    pub fn new(config: BarkConfig) -> anyhow::Result<Self> {
         let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
             .build()
             .context("failed to build reqwest client")?;
-        Ok(Self { client })
+        Ok(Self { client, config })
+    }
+
+    /// Whether the push would reach the public relay unencrypted.
+    pub fn is_plaintext_relay(server: &Url, cipher: Option<&Cipher>) -> bool {
+        cipher.is_none() && server.host_str() == Some(PUBLIC_RELAY_HOST)
     }
 
-    async fn push(&self, device_key: &str, msg: &Msg) -> anyhow::Result<()> {
+    async fn push(&self, url: Url, body: &impl serde::Serialize) -> anyhow::Result<()> {
         let response = self
             .client
-            .post(format!("{}/{}", BASE_URL, device_key))
-            .header("Content-Type", "application/json")
-            .json(msg)
+            .post(url)
+            .json(body)
             .send()
             .await
-            .context("bark: failed to send message to device")?
-            .json::<BarkResponse>()
-            .await?;
+            .map_err(reqwest::Error::without_url)
+            .context("bark: failed to send message to device")?;
+        let status = response.status();
+        if !status.is_success() {
+            // Bark explains rejections in a JSON `message`; proxies in front of
+            // it answer with HTML, which is kept as is.
+            let body = response.text().await.unwrap_or_default();
+            let reason = serde_json::from_str::<BarkResponse>(&body)
+                .map(|response| response.message)
+                .unwrap_or(body);
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("bark: server answered {status}: {}", reason.trim()),
+            )
+            .into());
+        }
 
+        let response = response
+            .json::<BarkResponse>()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("bark: invalid response")?;
         if response.code != 200 {
-            anyhow::bail!(
-                "failed to send message to device: {}, message: {}",
-                response.code,
-                response.message
+            let message = format!(
+                "bark: failed to send message to device: {}, message: {}",
+                response.code, response.message
             );
+            // The code mirrors an HTTP status, so outages and rate limits are
+            // retried like the ones reported in the status line.
+            return match u16::try_from(response.code) {
+                Ok(code @ 400..=599) => Err(StatusError::new(code, message).into()),
+                _ => Err(anyhow::anyhow!(message)),
+            };
         }
 
         Ok(())
     }
-
-    fn build_message(notification: &NotificationMessage, address: &NotificationAddress) -> Msg {
-        let mut msg = Msg::new(notification.title(), notification.body());
-
-        if let Some(url) = notification.url() {
-            msg.set_url(url);
-        }
-
-        if let Some(sound) = notification
-            .metadata_value("sound")
-            .or_else(|| address.metadata_value("sound"))
-        {
-            msg.set_sound(sound);
-        }
-
-        if let Some(group) = address
-            .metadata_value("group")
-            .or_else(|| notification.metadata_value("group"))
-        {
-            msg.set_group(group);
-        }
-
-        msg
-    }
 }
 
 #[async_trait]
@@ -79,7 +96,55 @@ impl NotificationProvider for BarkProvider {
         address: &NotificationAddress,
         message: &NotificationMessage,
     ) -> anyhow::Result<()> {
-        let msg = Self::build_message(message, address);
-        self.push(address.destination(), &msg).await
+        let server = address
+            .metadata_value("server")
+            .unwrap_or(&self.config.base_url);
+        let (server, device_key) = msg::split_device(address.destination(), server)
+            .context("bark: destination is not a device key or device url")?;
+        let cipher = Cipher::new(
+            address.metadata_value("key"),
+            address.metadata_value("mode"),
+            address.metadata_value("iv"),
+        )
+        .context("bark: invalid encryption settings")?;
+        if !self.config.allow_plaintext_relay && Self::is_plaintext_relay(&server, cipher.as_ref())
+        {
+            anyhow::bail!("bark: refusing to push unencrypted through the public relay");
+        }
+
+        let url = server
+            .join(&device_key)
+            .context("bark: invalid device url")?;
+        let msg = msg::build(message, address);
+        match cipher {
+            Some(cipher) => {
+                let plaintext =
+                    serde_json::to_vec(&msg).context("bark: failed to encode message")?;
+                self.push(url, &cipher.encrypt(&plaintext)?).await
+            }
+            None => self.push(url, &msg).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::delivery::ErrorClass;
+    use crate::notification::test_server::answer_once;
+
+    #[tokio::test]
+    async fn retries_error_pages_of_proxies() {
+        let base_url = answer_once("502 Bad Gateway", "<html>Bad Gateway</html>").await;
+        let provider = BarkProvider::new(BarkConfig::default()).unwrap();
+        let address = NotificationAddress::new(NotificationChannel::Bark, "device")
+            .insert_metadata("server", base_url);
+        let err = provider
+            .send(&address, &NotificationMessage::new("title", "body"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(ErrorClass::of(&err), ErrorClass::ServerError);
+        assert!(format!("{err:#}").contains("<html>Bad Gateway</html>"));
     }
 }
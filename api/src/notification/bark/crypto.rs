@@ -0,0 +1,216 @@
+//! Bark's encrypted push mode: the message JSON is encrypted with AES and
+//! sent as `ciphertext`, so the server only relays opaque bytes. The key and
+//! iv are the strings entered in the Bark app.
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::{BlockCipher, BlockEncryptMut, KeyInit, KeyIvInit};
+use aes::{Aes128, Aes192, Aes256};
+use aes_gcm::aead::Aead;
+use aes_gcm::aead::consts::U12;
+use aes_gcm::{AesGcm, Nonce};
+use anyhow::Context;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use rand_core::{OsRng, RngCore};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Cbc,
+    Ecb,
+    Gcm,
+}
+
+impl Mode {
+    /// Length of the iv the Bark app expects, if any.
+    fn iv_len(self) -> Option<usize> {
+        match self {
+            Mode::Cbc => Some(16),
+            Mode::Ecb => None,
+            Mode::Gcm => Some(12),
+        }
+    }
+}
+
+/// Encryption settings of a Bark device. The key length picks AES-128, 192
+/// or 256.
+#[derive(Debug, Clone)]
+pub struct Cipher {
+    key: Vec<u8>,
+    mode: Mode,
+    iv: Option<String>,
+}
+
+/// Body of an encrypted push.
+#[derive(Debug, serde::Serialize)]
+pub struct EncryptedPush {
+    pub ciphertext: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub iv: Option<String>,
+}
+
+impl Cipher {
+    /// Reads the channel's `key`, `mode` (`cbc` when left out, `ecb` or
+    /// `gcm`) and `iv`. Returns `None` for channels without a key. Pushes get
+    /// a fresh random iv when none is set.
+    pub fn new(
+        key: Option<&str>,
+        mode: Option<&str>,
+        iv: Option<&str>,
+    ) -> anyhow::Result<Option<Self>> {
+        let Some(key) = key.filter(|key| !key.is_empty()) else {
+            return Ok(None);
+        };
+        if ![16, 24, 32].contains(&key.len()) {
+            anyhow::bail!("Bark encryption key must be 16, 24 or 32 characters long");
+        }
+        let mode = match mode.map(|mode| mode.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("cbc") => Mode::Cbc,
+            Some("ecb") => Mode::Ecb,
+            Some("gcm") => Mode::Gcm,
+            Some(_) => anyhow::bail!("Bark encryption mode must be cbc, ecb or gcm"),
+        };
+        let iv = iv.filter(|iv| !iv.is_empty());
+        match (mode.iv_len(), iv) {
+            (Some(len), Some(iv)) if iv.len() != len => {
+                anyhow::bail!("Bark {mode:?} iv must be {len} characters long")
+            }
+            (None, Some(_)) => anyhow::bail!("Bark ECB mode takes no iv"),
+            _ => {}
+        }
+        Ok(Some(Self {
+            key: key.as_bytes().to_vec(),
+            mode,
+            iv: iv.map(str::to_string),
+        }))
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> anyhow::Result<EncryptedPush> {
+        let iv = match (self.mode.iv_len(), &self.iv) {
+            (Some(_), Some(iv)) => Some(iv.clone()),
+            (Some(len), None) => Some(random_iv(len)),
+            (None, _) => None,
+        };
+        let ciphertext =
+            self.encrypt_with(iv.as_deref().unwrap_or_default().as_bytes(), plaintext)?;
+        Ok(EncryptedPush {
+            ciphertext: STANDARD.encode(ciphertext),
+            iv,
+        })
+    }
+
+    fn encrypt_with(&self, iv: &[u8], plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+        let key = self.key.as_slice();
+        match (self.mode, key.len()) {
+            (Mode::Cbc, 16) => cbc::<Aes128>(key, iv, plaintext),
+            (Mode::Cbc, 24) => cbc::<Aes192>(key, iv, plaintext),
+            (Mode::Cbc, _) => cbc::<Aes256>(key, iv, plaintext),
+            (Mode::Ecb, 16) => ecb::<Aes128>(key, plaintext),
+            (Mode::Ecb, 24) => ecb::<Aes192>(key, plaintext),
+            (Mode::Ecb, _) => ecb::<Aes256>(key, plaintext),
+            (Mode::Gcm, 16) => gcm::<Aes128>(key, iv, plaintext),
+            (Mode::Gcm, 24) => gcm::<Aes192>(key, iv, plaintext),
+            (Mode::Gcm, _) => gcm::<Aes256>(key, iv, plaintext),
+        }
+    }
+}
+
+fn cbc<C>(key: &[u8], iv: &[u8], plaintext: &[u8]) -> anyhow::Result<Vec<u8>>
+where
+    C: BlockEncryptMut + BlockCipher + KeyInit,
+    cbc::Encryptor<C>: KeyIvInit,
+{
+    Ok(cbc::Encryptor::<C>::new_from_slices(key, iv)
+        .context("invalid Bark key or iv")?
+        .encrypt_padded_vec_mut::<Pkcs7>(plaintext))
+}
+
+fn ecb<C>(key: &[u8], plaintext: &[u8]) -> anyhow::Result<Vec<u8>>
+where
+    C: BlockEncryptMut + BlockCipher + KeyInit,
+{
+    Ok(ecb::Encryptor::<C>::new_from_slice(key)
+        .context("invalid Bark key")?
+        .encrypt_padded_vec_mut::<Pkcs7>(plaintext))
+}
+
+/// GCM output is the ciphertext followed by the 16-byte tag.
+fn gcm<C>(key: &[u8], iv: &[u8], plaintext: &[u8]) -> anyhow::Result<Vec<u8>>
+where
+    AesGcm<C, U12>: KeyInit + Aead,
+{
+    AesGcm::<C, U12>::new_from_slice(key)
+        .context("invalid Bark key")?
+        .encrypt(Nonce::from_slice(iv), plaintext)
+        .map_err(|_| anyhow::anyhow!("Bark payload encryption failed"))
+}
+
+/// Alphanumeric, since the Bark app reads the iv as text.
+fn random_iv(len: usize) -> String {
+    const CHARSET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+    let mut bytes = vec![0; len];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+        .into_iter()
+        .map(|byte| CHARSET[byte as usize % CHARSET.len()] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAINTEXT: &[u8] = br#"{"body":"test"}"#;
+
+    fn encrypt(key: &str, mode: &str, iv: Option<&str>) -> EncryptedPush {
+        Cipher::new(Some(key), Some(mode), iv)
+            .unwrap()
+            .unwrap()
+            .encrypt(PLAINTEXT)
+            .unwrap()
+    }
+
+    #[test]
+    fn matches_openssl_for_each_mode() {
+        let push = encrypt("1234567890123456", "cbc", Some("1111111111111111"));
+        assert_eq!(push.ciphertext, "6lmdqCS5QtuKfVLAzfC7Xg==");
+        assert_eq!(push.iv.as_deref(), Some("1111111111111111"));
+
+        let push = encrypt("123456789012345678901234", "ECB", None);
+        assert_eq!(push.ciphertext, "v7T4HHnOEKSz6ORvURPtPQ==");
+        assert_eq!(push.iv, None);
+
+        let push = encrypt(
+            "12345678901234567890123456789012",
+            "gcm",
+            Some("111111111111"),
+        );
+        assert_eq!(
+            push.ciphertext,
+            "7h58z2hMtfpAZnNetzTtHvBe9QjaVXI8cj9WdXufxA=="
+        );
+    }
+
+    #[test]
+    fn generates_an_iv_when_none_is_set() {
+        let push = encrypt("1234567890123456", "gcm", None);
+        let iv = push.iv.unwrap();
+        assert_eq!(iv.len(), 12);
+        assert!(iv.chars().all(|c| c.is_ascii_alphanumeric()));
+    }
+
+    #[test]
+    fn rejects_bad_settings() {
+        assert!(Cipher::new(None, Some("gcm"), None).unwrap().is_none());
+        assert!(Cipher::new(Some("short"), None, None).is_err());
+        assert!(Cipher::new(Some("1234567890123456"), Some("ctr"), None).is_err());
+        assert!(Cipher::new(Some("1234567890123456"), None, Some("123")).is_err());
+        assert!(
+            Cipher::new(
+                Some("1234567890123456"),
+                Some("ecb"),
+                Some("1111111111111111")
+            )
+            .is_err()
+        );
+    }
+}
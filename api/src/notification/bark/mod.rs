@@ -1,4 +1,5 @@
 mod client;
+pub mod crypto;
 pub mod msg;
 
 pub use client::BarkProvider;
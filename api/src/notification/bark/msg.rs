@@ -1,6 +1,10 @@
+use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
+use crate::notification::priority::Priority;
+use crate::notification::provider::{NotificationAddress, NotificationMessage};
+
 /// Push Notification Message structure.
 ///
 /// This struct represents a push notification message that can be sent to devices.
@@ -22,6 +26,7 @@ use std::fmt::Display;
 /// // and so on
 /// ```
 #[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct Msg {
     /// Push Title
     title: String,
@@ -36,6 +41,8 @@ pub struct Msg {
     /// timeSensitive: Time-sensitive notification, can be displayed while in focus mode.
     ///
     /// passive: Only adds the notification to the notification list, will not display on the screen.
+    ///
+    /// critical: Plays the sound even when the device is muted or in focus mode.
     #[serde(skip_serializing_if = "Option::is_none")]
     level: Option<Level>,
 
@@ -55,6 +62,10 @@ pub struct Msg {
     #[serde(skip_serializing_if = "Option::is_none")]
     sound: Option<String>,
 
+    /// Pass 1 to repeat the ringtone for 30 seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    call: Option<u8>,
+
     /// Set a custom icon for the push; the set icon will replace the default Bark icon
     #[serde(skip_serializing_if = "Option::is_none")]
     icon: Option<String>,
@@ -87,20 +98,25 @@ pub struct Msg {
 /// timeSensitive: Time-sensitive notification, can be displayed while in focus mode.
 ///
 /// passive: Only adds the notification to the notification list, will not display on the screen.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy)]
+///
+/// critical: Plays the sound even when the device is muted or in focus mode.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
 pub enum Level {
     Active,
     TimeSensitive,
     Passive,
+    Critical,
 }
 
 #[allow(dead_code)] // Public builder helpers retained for potential external consumers
 impl Level {
     pub fn from_str(str: &str) -> Option<Self> {
-        match str.to_lowercase().as_str() {
+        match str.trim().to_lowercase().replace(['-', '_'], "").as_str() {
             "timesensitive" => Some(Self::TimeSensitive),
             "passive" => Some(Self::Passive),
             "active" => Some(Self::Active),
+            "critical" => Some(Self::Critical),
             _ => None,
         }
     }
@@ -112,6 +128,7 @@ impl Display for Level {
             Level::Active => "active",
             Level::TimeSensitive => "timeSensitive",
             Level::Passive => "passive",
+            Level::Critical => "critical",
         };
         write!(f, "{}", str)
     }
@@ -163,6 +180,7 @@ impl Msg {
             auto_copy: None,
             copy: None,
             sound: Some("chime.caf".to_string()),
+            call: None,
             icon: Some("https://github.com/66f94eae/bark-dev/raw/main/bot.jpg".to_string()),
             group: None,
             is_archive: None,
@@ -251,6 +269,21 @@ impl Msg {
         self
     }
 
+    /// Sets whether the ringtone repeats for 30 seconds, like a call.
+    ///
+    /// # Arguments
+    /// - `call`: true to repeat the ringtone.
+    ///
+    /// # Returns
+    /// A mutable reference to `self` for method chaining.
+    pub fn set_call(&mut self, call: bool) -> &mut Self {
+        match call {
+            true => self.call = Some(1),
+            false => self.call = None,
+        }
+        self
+    }
+
     /// Sets a custom icon URL for the notification.
     ///
     /// # Arguments
@@ -324,8 +357,150 @@ impl Msg {
     }
 }
 
+/// Builds the push for a notification. `sound`, `level`, `badge`, `icon`,
+/// `call`, `is_archive` and `copy` come from the message metadata, else from
+/// the channel metadata; `group` prefers the channel. Without a `level`, the
+/// message priority picks one.
+pub fn build(notification: &NotificationMessage, address: &NotificationAddress) -> Msg {
+    let value = |key: &str| {
+        notification
+            .metadata_value(key)
+            .or_else(|| address.metadata_value(key))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let flag = |key: &str| value(key).map(|value| matches!(value, "1" | "true"));
+    let mut msg = Msg::new(notification.title(), notification.body());
+
+    if let Some(url) = notification.url() {
+        msg.set_url(url);
+    }
+    if let Some(sound) = value("sound") {
+        msg.set_sound(sound);
+    }
+    if let Some(group) = address
+        .metadata_value("group")
+        .or_else(|| notification.metadata_value("group"))
+    {
+        msg.set_group(group);
+    }
+
+    let level = value("level").and_then(Level::from_str).or_else(|| {
+        Priority::of(notification, address).map(|priority| match priority {
+            Priority::Min | Priority::Low => Level::Passive,
+            Priority::Default => Level::Active,
+            Priority::High => Level::TimeSensitive,
+            Priority::Urgent => Level::Critical,
+        })
+    });
+    if let Some(level) = level {
+        msg.set_level(level);
+    }
+    if let Some(badge) = value("badge").and_then(|badge| badge.parse().ok()) {
+        msg.set_badge(badge);
+    }
+    if let Some(icon) = value("icon").filter(|icon| icon.starts_with("https://")) {
+        msg.set_icon(icon);
+    }
+    if let Some(call) = flag("call") {
+        msg.set_call(call);
+    }
+    if let Some(is_archive) = flag("is_archive") {
+        msg.set_is_archive(is_archive);
+    }
+    if let Some(copy) = value("copy") {
+        msg.set_copy(copy);
+    }
+    msg
+}
+
+/// Splits a destination into the server to push through and the device key.
+/// A device url such as `https://bark.example.com/<key>` names both; a bare
+/// key goes to `server`.
+pub fn split_device(destination: &str, server: &str) -> Option<(Url, String)> {
+    let destination = destination.trim();
+    let (mut url, key) =
+        if destination.starts_with("https://") || destination.starts_with("http://") {
+            let url = Url::parse(destination).ok()?;
+            let key = url.path_segments()?.rfind(|segment| !segment.is_empty())?;
+            let key = key.to_string();
+            (url, key)
+        } else {
+            let server = server.trim();
+            if !server.starts_with("https://") && !server.starts_with("http://") {
+                return None;
+            }
+            let base = Url::parse(&format!("{}/", server.trim_end_matches('/'))).ok()?;
+            (base.join(destination).ok()?, destination.to_string())
+        };
+
+    if key.is_empty() || key.len() > 64 || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    url.path_segments_mut().ok()?.pop_if_empty().pop().push("");
+    url.set_query(None);
+    url.set_fragment(None);
+    Some((url, key))
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BarkResponse {
     pub code: i64,
     pub message: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationChannel;
+
+    #[test]
+    fn maps_message_metadata_before_channel_metadata() {
+        let address = NotificationAddress::new(NotificationChannel::Bark, "key")
+            .insert_metadata("group", "live")
+            .insert_metadata("badge", "3")
+            .insert_metadata("call", "true")
+            .insert_metadata("priority", "urgent");
+        let mut message = NotificationMessage::new("alice is live", "Ranked");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("badge", "1")
+            .insert_metadata("icon", "https://i0.hdslb.com/face.jpg")
+            .insert_metadata("is_archive", "1")
+            .insert_metadata("copy", "7734200");
+        let body = serde_json::to_value(build(&message, &address)).unwrap();
+
+        assert_eq!(body["title"], "alice is live");
+        assert_eq!(body["url"], "https://live.bilibili.com/7734200");
+        assert_eq!(body["group"], "live");
+        assert_eq!(body["badge"], 1);
+        assert_eq!(body["icon"], "https://i0.hdslb.com/face.jpg");
+        assert_eq!(body["call"], 1);
+        assert_eq!(body["isArchive"], 1);
+        assert_eq!(body["copy"], "7734200");
+        assert_eq!(body["level"], "critical");
+
+        message.insert_metadata("level", "time-sensitive");
+        let body = serde_json::to_value(build(&message, &address)).unwrap();
+        assert_eq!(body["level"], "timeSensitive");
+    }
+
+    #[test]
+    fn splits_device_urls_and_bare_keys() {
+        let (server, key) = split_device(
+            "https://bark.example.com/push/AbC123/",
+            "https://api.day.app",
+        )
+        .unwrap();
+        assert_eq!(server.as_str(), "https://bark.example.com/push/");
+        assert_eq!(key, "AbC123");
+
+        let (server, key) = split_device(" AbC123 ", "https://api.day.app").unwrap();
+        assert_eq!(server.as_str(), "https://api.day.app/");
+        assert_eq!(key, "AbC123");
+
+        assert!(split_device("AbC123", "bark.example.com").is_none());
+        assert!(split_device("not a key", "https://api.day.app").is_none());
+    }
+}
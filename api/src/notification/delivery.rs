@@ -0,0 +1,232 @@
+use std::fmt::{Display, Formatter};
+
+use crate::notification::provider::NotificationChannel;
+use crate::notification::web_push::SubscriptionGone;
+
+/// A remote service answered with an error status. Providers return it so
+/// failed deliveries can be classified; it displays as its message.
+#[derive(Debug, thiserror::Error)]
+#[error("{message}")]
+pub struct StatusError {
+    pub status: u16,
+    message: String,
+}
+
+impl StatusError {
+    pub fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// Why a delivery failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// No provider is registered for the channel.
+    Unconfigured,
+    /// The provider did not finish within its timeout.
+    Timeout,
+    /// The service could not be reached.
+    Network,
+    /// The service asked to slow down.
+    RateLimited,
+    /// The service failed on its side (5xx).
+    ServerError,
+    /// The destination no longer exists, such as an expired push subscription.
+    Gone,
+    /// The service refused the message or the channel is misconfigured.
+    Rejected,
+}
+
+impl ErrorClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorClass::Unconfigured => "unconfigured",
+            ErrorClass::Timeout => "timeout",
+            ErrorClass::Network => "network",
+            ErrorClass::RateLimited => "rate_limited",
+            ErrorClass::ServerError => "server_error",
+            ErrorClass::Gone => "gone",
+            ErrorClass::Rejected => "rejected",
+        }
+    }
+
+    /// Whether sending the same message again later may succeed.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorClass::Timeout
+                | ErrorClass::Network
+                | ErrorClass::RateLimited
+                | ErrorClass::ServerError
+        )
+    }
+
+    /// Classifies a provider error by the first cause that tells.
+    pub fn of(err: &anyhow::Error) -> Self {
+        for cause in err.chain() {
+            if cause.is::<SubscriptionGone>() {
+                return ErrorClass::Gone;
+            }
+            if let Some(err) = cause.downcast_ref::<StatusError>() {
+                return Self::of_status(err.status);
+            }
+            if let Some(err) = cause.downcast_ref::<reqwest::Error>() {
+                if err.is_timeout() {
+                    return ErrorClass::Timeout;
+                }
+                if let Some(status) = err.status() {
+                    return Self::of_status(status.as_u16());
+                }
+                if err.is_connect() || err.is_request() {
+                    return ErrorClass::Network;
+                }
+            }
+            if let Some(err) = cause.downcast_ref::<lettre::transport::smtp::Error>() {
+                if err.is_timeout() {
+                    return ErrorClass::Timeout;
+                }
+                if err.is_transient() {
+                    return ErrorClass::ServerError;
+                }
+                if err.is_permanent() {
+                    return ErrorClass::Rejected;
+                }
+                return ErrorClass::Network;
+            }
+        }
+        ErrorClass::Rejected
+    }
+
+    fn of_status(status: u16) -> Self {
+        match status {
+            408 => ErrorClass::Timeout,
+            429 => ErrorClass::RateLimited,
+            500..=599 => ErrorClass::ServerError,
+            _ => ErrorClass::Rejected,
+        }
+    }
+}
+
+impl Display for ErrorClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Outcome of sending a message to one address.
+#[derive(Debug)]
+pub struct Delivery {
+    /// Id of the stored channel the address came from, if any.
+    pub address_id: Option<i32>,
+    pub channel: NotificationChannel,
+    pub result: Result<(), DeliveryFailure>,
+}
+
+#[derive(Debug)]
+pub struct DeliveryFailure {
+    pub class: ErrorClass,
+    pub error: anyhow::Error,
+}
+
+impl DeliveryFailure {
+    pub fn new(error: anyhow::Error) -> Self {
+        Self {
+            class: ErrorClass::of(&error),
+            error,
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.class.is_retryable()
+    }
+}
+
+/// Per-address outcomes of a fan-out, in the order of the target addresses.
+#[derive(Debug, Default)]
+pub struct DeliveryReport {
+    user_id: i32,
+    deliveries: Vec<Delivery>,
+}
+
+impl DeliveryReport {
+    pub fn new(user_id: i32, deliveries: Vec<Delivery>) -> Self {
+        Self {
+            user_id,
+            deliveries,
+        }
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn deliveries(&self) -> &[Delivery] {
+        &self.deliveries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.deliveries.is_empty()
+    }
+
+    pub fn delivered(&self) -> usize {
+        self.deliveries
+            .iter()
+            .filter(|delivery| delivery.result.is_ok())
+            .count()
+    }
+
+    pub fn failures(&self) -> impl Iterator<Item = (&Delivery, &DeliveryFailure)> {
+        self.deliveries
+            .iter()
+            .filter_map(|delivery| delivery.result.as_ref().err().map(|err| (delivery, err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_through_context() {
+        let err = anyhow::Error::new(StatusError::new(503, "ntfy: server answered 503"))
+            .context("channel Ntfy");
+        assert_eq!(ErrorClass::of(&err), ErrorClass::ServerError);
+        assert!(ErrorClass::of(&err).is_retryable());
+
+        let err = anyhow::Error::new(StatusError::new(429, "slack: webhook answered 429"));
+        assert_eq!(ErrorClass::of(&err), ErrorClass::RateLimited);
+
+        let err = anyhow::Error::new(StatusError::new(400, "gotify: server answered 400"));
+        assert_eq!(ErrorClass::of(&err), ErrorClass::Rejected);
+        assert!(!ErrorClass::of(&err).is_retryable());
+
+        let err = anyhow::anyhow!("bark: destination is not a device key or device url");
+        assert_eq!(ErrorClass::of(&err), ErrorClass::Rejected);
+    }
+
+    #[test]
+    fn counts_partial_success() {
+        let report = DeliveryReport::new(
+            1,
+            vec![
+                Delivery {
+                    address_id: Some(1),
+                    channel: NotificationChannel::Bark,
+                    result: Err(DeliveryFailure::new(anyhow::anyhow!("bark: bad key"))),
+                },
+                Delivery {
+                    address_id: Some(2),
+                    channel: NotificationChannel::Ntfy,
+                    result: Ok(()),
+                },
+            ],
+        );
+        assert_eq!(report.delivered(), 1);
+        let failures: Vec<_> = report.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0.address_id, Some(1));
+    }
+}
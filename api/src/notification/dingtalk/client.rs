@@ -0,0 +1,86 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use migration::async_trait::async_trait;
+
+use crate::config::DingTalkConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::dingtalk::msg::{self, RobotMessage, RobotResponse};
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+
+const USER_AGENT: &str = "Fusion-DingTalk";
+
+/// Posts notifications to a DingTalk group robot. The address destination is
+/// the robot `access_token`; robots with signing enabled need the `secret`
+/// (`SEC…`) in the address metadata.
+pub struct DingTalkProvider {
+    client: reqwest::Client,
+    config: DingTalkConfig,
+}
+
+impl DingTalkProvider {
+    pub fn new(config: DingTalkConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self { client, config })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for DingTalkProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::DingTalk
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let mut query = vec![("access_token", address.destination().trim().to_string())];
+        if let Some(secret) = address
+            .metadata_value("secret")
+            .map(str::trim)
+            .filter(|secret| !secret.is_empty())
+        {
+            let timestamp = Utc::now().timestamp_millis();
+            query.push(("timestamp", timestamp.to_string()));
+            query.push(("sign", msg::sign(secret, timestamp)));
+        }
+
+        let url = format!("{}/robot/send", self.config.base_url.trim_end_matches('/'));
+        // The query carries the access token; keep it out of the logs.
+        let response: RobotResponse = self
+            .client
+            .post(url)
+            .query(&query)
+            .json(&RobotMessage::new(message))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(reqwest::Error::without_url)
+            .context("dingtalk: request failed")?
+            .json()
+            .await
+            .context("dingtalk: invalid response")?;
+
+        if response.errcode != 0 {
+            let message = format!(
+                "dingtalk: robot rejected the message ({}): {}",
+                response.errcode, response.errmsg
+            );
+            // Reported as a 429 so the delivery is retried later.
+            if response.is_rate_limited() {
+                return Err(StatusError::new(429, message).into());
+            }
+            anyhow::bail!(message);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::notification::provider::NotificationMessage;
+
+/// Body of a robot `send` request: an action card with a button to the room,
+/// or plain markdown for messages without a link.
+#[derive(Debug, Serialize)]
+#[serde(tag = "msgtype", rename_all = "camelCase")]
+pub enum RobotMessage {
+    Markdown {
+        markdown: Markdown,
+    },
+    ActionCard {
+        #[serde(rename = "actionCard")]
+        action_card: ActionCard,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct Markdown {
+    /// Shown in the conversation list only.
+    pub title: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionCard {
+    pub title: String,
+    pub text: String,
+    pub single_title: &'static str,
+    #[serde(rename = "singleURL")]
+    pub single_url: String,
+}
+
+/// DingTalk answers every request with 200 and reports failures here.
+#[derive(Debug, Deserialize)]
+pub struct RobotResponse {
+    pub errcode: i64,
+    #[serde(default)]
+    pub errmsg: String,
+}
+
+impl RobotResponse {
+    /// Whether the robot refused the message for sending too fast (more than
+    /// 20 messages a minute).
+    pub fn is_rate_limited(&self) -> bool {
+        self.errcode == 130101
+    }
+}
+
+impl RobotMessage {
+    pub fn new(message: &NotificationMessage) -> Self {
+        let title = message.title().to_string();
+        let text = markdown(message);
+        match link(message.url()) {
+            Some(url) => RobotMessage::ActionCard {
+                action_card: ActionCard {
+                    title,
+                    text,
+                    single_title: "Open the room",
+                    single_url: url.to_string(),
+                },
+            },
+            None => RobotMessage::Markdown {
+                markdown: Markdown { title, text },
+            },
+        }
+    }
+}
+
+/// Markdown text with the cover, the body and the category and viewer lines.
+pub fn markdown(message: &NotificationMessage) -> String {
+    let text = |key: &str| {
+        message
+            .metadata_value(key)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut paragraphs = vec![format!("### {}", message.title().trim())];
+    if let Some(cover) = link(text("cover")) {
+        paragraphs.push(format!("![cover]({cover})"));
+    }
+    if !message.body().trim().is_empty() {
+        // Single newlines do not break lines in DingTalk markdown.
+        paragraphs.push(message.body().trim().replace('\n', "  \n"));
+    }
+    let facts: Vec<String> = [("Category", text("category")), ("Viewers", text("viewers"))]
+        .into_iter()
+        .filter_map(|(name, value)| Some(format!("**{name}**: {}", value?)))
+        .collect();
+    if !facts.is_empty() {
+        paragraphs.push(facts.join("  \n"));
+    }
+    paragraphs.join("\n\n")
+}
+
+/// The `sign` query parameter of robots with signing enabled: the base64
+/// HMAC-SHA256 of `{timestamp}\n{secret}`, keyed with the secret.
+pub fn sign(secret: &str, timestamp_millis: i64) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{timestamp_millis}\n{secret}").as_bytes());
+    STANDARD.encode(mac.finalize().into_bytes())
+}
+
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_timestamp_and_secret() {
+        assert_eq!(
+            sign("SEC0123456789abcdef", 1700000000000),
+            "TSZbRFUuvaSQaRKUpF970OPCb2/LcQAP3wOvwZIzBZk="
+        );
+    }
+
+    #[test]
+    fn tells_rate_limits_from_other_errors() {
+        let response = |body| serde_json::from_str::<RobotResponse>(body).unwrap();
+
+        assert!(response(r#"{"errcode":130101,"errmsg":"send too fast"}"#).is_rate_limited());
+        assert!(!response(r#"{"errcode":300001,"errmsg":"token is not exist"}"#).is_rate_limited());
+    }
+
+    #[test]
+    fn renders_an_action_card_for_rooms() {
+        let mut message = NotificationMessage::new("alice is live", "Ranked\nDay 3");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg")
+            .insert_metadata("category", "League of Legends")
+            .insert_metadata("viewers", "1200");
+        let body = serde_json::to_value(RobotMessage::new(&message)).unwrap();
+
+        assert_eq!(body["msgtype"], "actionCard");
+        assert_eq!(body["actionCard"]["title"], "alice is live");
+        assert_eq!(
+            body["actionCard"]["text"],
+            "### alice is live\n\n![cover](https://i0.hdslb.com/cover.jpg)\n\nRanked  \nDay 3\n\n\
+             **Category**: League of Legends  \n**Viewers**: 1200"
+        );
+        assert_eq!(body["actionCard"]["singleTitle"], "Open the room");
+        assert_eq!(
+            body["actionCard"]["singleURL"],
+            "https://live.bilibili.com/7734200"
+        );
+    }
+
+    #[test]
+    fn renders_markdown_without_a_link() {
+        let body = serde_json::to_value(RobotMessage::new(&NotificationMessage::new("hello", "")))
+            .unwrap();
+
+        assert_eq!(body["msgtype"], "markdown");
+        assert_eq!(body["markdown"]["title"], "hello");
+        assert_eq!(body["markdown"]["text"], "### hello");
+    }
+}
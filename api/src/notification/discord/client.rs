@@ -0,0 +1,114 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+use reqwest::StatusCode;
+use tracing::warn;
+
+use crate::config::ChatWebhookConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::discord::msg::{ApiError, WebhookMessage};
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+use crate::notification::rate_limit::{self, RateLimits};
+
+const USER_AGENT: &str = "Fusion-Discord";
+
+const REMAINING_HEADER: &str = "x-ratelimit-remaining";
+const RESET_AFTER_HEADER: &str = "x-ratelimit-reset-after";
+
+/// Posts notifications as embeds to the Discord webhook url in the address
+/// destination. A `thread_id` query on that url targets a thread.
+pub struct DiscordProvider {
+    client: reqwest::Client,
+    config: ChatWebhookConfig,
+    limits: RateLimits,
+}
+
+impl DiscordProvider {
+    pub fn new(config: ChatWebhookConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self {
+            client,
+            config,
+            limits: RateLimits::default(),
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for DiscordProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Discord
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let url = address.destination().trim();
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            anyhow::bail!("discord: destination is not an http(s) url");
+        }
+        let body = WebhookMessage::new(message);
+
+        let mut retries = 0;
+        loop {
+            self.limits.wait(url).await;
+            // The webhook url is its token; keep it out of the logs.
+            let response = self
+                .client
+                .post(url)
+                .query(&[("wait", "true"), ("with_components", "true")])
+                .json(&body)
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url)
+                .context("discord: request failed")?;
+
+            let headers = response.headers();
+            let reset_after = rate_limit::seconds_header(headers, RESET_AFTER_HEADER);
+            let exhausted = headers
+                .get(REMAINING_HEADER)
+                .is_some_and(|remaining| remaining == "0");
+            if exhausted && let Some(reset_after) = reset_after {
+                self.limits.block(url, reset_after);
+            }
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(());
+            }
+            if status == StatusCode::TOO_MANY_REQUESTS
+                && let Some(wait) = reset_after.or_else(|| rate_limit::retry_after(headers))
+            {
+                // Later messages to this webhook wait as well, retried or not.
+                self.limits.block(url, wait);
+                if retries < self.config.max_retries
+                    && wait <= Duration::from_secs(self.config.max_retry_after_secs)
+                {
+                    retries += 1;
+                    warn!(?wait, retries, "discord: rate limited, retrying");
+                    continue;
+                }
+            }
+
+            let error = response
+                .json::<ApiError>()
+                .await
+                .map(|error| error.message)
+                .unwrap_or_default();
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("discord: webhook answered {status}: {error}"),
+            )
+            .into());
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use serde::{Deserialize, Serialize};
+
+use crate::notification::provider::NotificationMessage;
+
+const TITLE_LIMIT: usize = 256;
+const DESCRIPTION_LIMIT: usize = 4096;
+const FIELD_LIMIT: usize = 1024;
+
+/// Embed accents: red for streamers going live, blurple for everything else.
+const LIVE_COLOR: u32 = 0xE74C3C;
+const COLOR: u32 = 0x5865F2;
+
+const ACTION_ROW: u8 = 1;
+const BUTTON: u8 = 2;
+const LINK_STYLE: u8 = 5;
+
+/// Body of an execute-webhook request.
+#[derive(Debug, Serialize)]
+pub struct WebhookMessage {
+    pub embeds: Vec<Embed>,
+    /// Needs `with_components=true`, which lets any webhook send link buttons.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<ActionRow>,
+    /// Titles and announcements are user content; never let them ping anyone.
+    pub allowed_mentions: AllowedMentions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Embed {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    pub color: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<Image>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Image {
+    pub url: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Field {
+    pub name: &'static str,
+    pub value: String,
+    pub inline: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ActionRow {
+    #[serde(rename = "type")]
+    pub kind: u8,
+    pub components: Vec<Button>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Button {
+    #[serde(rename = "type")]
+    pub kind: u8,
+    pub style: u8,
+    pub label: &'static str,
+    pub url: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct AllowedMentions {
+    pub parse: Vec<String>,
+}
+
+/// Error body of a rejected request.
+#[derive(Debug, Deserialize)]
+pub struct ApiError {
+    pub message: String,
+}
+
+impl WebhookMessage {
+    pub fn new(message: &NotificationMessage) -> Self {
+        let text = |key: &str| {
+            message
+                .metadata_value(key)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let url = link(message.url()).map(str::to_string);
+
+        let mut fields = Vec::new();
+        if let Some(category) = text("category") {
+            fields.push(Field {
+                name: "Category",
+                value: truncate(category, FIELD_LIMIT),
+                inline: true,
+            });
+        }
+        if let Some(viewers) = text("viewers") {
+            fields.push(Field {
+                name: "Viewers",
+                value: truncate(viewers, FIELD_LIMIT),
+                inline: true,
+            });
+        }
+
+        let body = message.body().trim();
+        let embed = Embed {
+            title: truncate(message.title(), TITLE_LIMIT),
+            description: (!body.is_empty()).then(|| truncate(body, DESCRIPTION_LIMIT)),
+            url: url.clone(),
+            color: match text("event") {
+                Some("live") => LIVE_COLOR,
+                _ => COLOR,
+            },
+            thumbnail: link(text("cover")).map(|cover| Image {
+                url: cover.to_string(),
+            }),
+            fields,
+        };
+
+        Self {
+            embeds: vec![embed],
+            components: url
+                .map(|url| ActionRow {
+                    kind: ACTION_ROW,
+                    components: vec![Button {
+                        kind: BUTTON,
+                        style: LINK_STYLE,
+                        label: "Open the room",
+                        url,
+                    }],
+                })
+                .into_iter()
+                .collect(),
+            allowed_mentions: AllowedMentions::default(),
+        }
+    }
+}
+
+/// Discord rejects the whole message over a malformed url, so only http(s)
+/// ones are sent.
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+/// Cuts `text` to `limit` characters, ending with an ellipsis when shortened.
+fn truncate(text: &str, limit: usize) -> String {
+    match text.char_indices().nth(limit.saturating_sub(1)) {
+        Some((end, _)) if text[end..].chars().count() > 1 => format!("{}…", &text[..end]),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_an_embed_with_fields_and_a_link_button() {
+        let mut message = NotificationMessage::new("alice is live", "Ranked @everyone");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("event", "live")
+            .insert_metadata("category", "League of Legends")
+            .insert_metadata("viewers", "1200")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg");
+        let body = serde_json::to_value(WebhookMessage::new(&message)).unwrap();
+
+        let embed = &body["embeds"][0];
+        assert_eq!(embed["title"], "alice is live");
+        assert_eq!(embed["description"], "Ranked @everyone");
+        assert_eq!(embed["url"], "https://live.bilibili.com/7734200");
+        assert_eq!(embed["color"], LIVE_COLOR);
+        assert_eq!(embed["thumbnail"]["url"], "https://i0.hdslb.com/cover.jpg");
+        assert_eq!(embed["fields"][0]["name"], "Category");
+        assert_eq!(embed["fields"][0]["value"], "League of Legends");
+        assert_eq!(embed["fields"][1]["name"], "Viewers");
+        assert_eq!(embed["fields"][1]["value"], "1200");
+
+        let button = &body["components"][0]["components"][0];
+        assert_eq!(button["type"], 2);
+        assert_eq!(button["style"], 5);
+        assert_eq!(button["url"], "https://live.bilibili.com/7734200");
+        assert_eq!(body["allowed_mentions"]["parse"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn leaves_out_what_the_message_lacks() {
+        let mut message = NotificationMessage::new("hello", "");
+        message
+            .set_url("javascript:alert(1)")
+            .insert_metadata("cover", "");
+        let body = serde_json::to_value(WebhookMessage::new(&message)).unwrap();
+
+        let embed = &body["embeds"][0];
+        assert_eq!(embed["color"], COLOR);
+        assert!(embed.get("description").is_none());
+        assert!(embed.get("url").is_none());
+        assert!(embed.get("thumbnail").is_none());
+        assert!(embed.get("fields").is_none());
+        assert!(body.get("components").is_none());
+    }
+
+    #[test]
+    fn truncates_by_characters() {
+        assert_eq!(truncate("abc", 3), "abc");
+        assert_eq!(truncate("abcd", 3), "ab…");
+        assert_eq!(truncate("直播中啦", 3), "直播…");
+    }
+}
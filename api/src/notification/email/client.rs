@@ -0,0 +1,204 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::transport::smtp::client::{Tls, TlsParameters};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use migration::async_trait::async_trait;
+
+use crate::config::{EmailConfig, SmtpSecurity};
+use crate::notification::email::msg;
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+
+/// Sends notifications as email through the configured SMTP server. The
+/// address destination is the recipient.
+pub struct EmailProvider {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl EmailProvider {
+    pub fn new(config: &EmailConfig) -> anyhow::Result<Self> {
+        let host = config.host.trim();
+        let tls_parameters =
+            || TlsParameters::new(host.to_string()).context("email: invalid TLS parameters");
+        let tls = match config.security {
+            SmtpSecurity::Starttls => Tls::Required(tls_parameters()?),
+            SmtpSecurity::Tls => Tls::Wrapper(tls_parameters()?),
+            SmtpSecurity::None => Tls::None,
+        };
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(host)
+            .port(config.port())
+            .tls(tls)
+            .timeout(Some(Duration::from_secs(config.timeout_secs)));
+        if !config.username.is_empty() {
+            builder = builder.credentials(Credentials::new(
+                config.username.clone(),
+                config.password.clone(),
+            ));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: config.from.parse().context("email: invalid sender")?,
+        })
+    }
+
+    /// Sends a mail with plain-text and HTML alternatives.
+    pub async fn send_mail(
+        &self,
+        to: &str,
+        subject: &str,
+        plain: String,
+        html: String,
+    ) -> anyhow::Result<()> {
+        let to: Mailbox = to
+            .trim()
+            .parse()
+            .with_context(|| format!("email: invalid recipient {to:?}"))?;
+        let mail = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .multipart(MultiPart::alternative_plain_html(plain, html))
+            .context("email: failed to build message")?;
+
+        self.transport
+            .send(mail)
+            .await
+            .context("email: failed to send message")?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for EmailProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Email
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        self.send_mail(
+            address.destination(),
+            message.title(),
+            msg::plain(message),
+            msg::html(message),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+    use tokio::task::JoinHandle;
+
+    /// Accepts one SMTP session on a local port and returns what the client sent.
+    async fn smtp_sink() -> (u16, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let session = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (read, mut write) = stream.into_split();
+            let mut lines = BufReader::new(read).lines();
+            write.write_all(b"220 sink ESMTP\r\n").await.unwrap();
+
+            let mut transcript = String::new();
+            let mut in_data = false;
+            while let Some(line) = lines.next_line().await.unwrap() {
+                transcript.push_str(&line);
+                transcript.push('\n');
+                let reply: &[u8] = if in_data {
+                    if line != "." {
+                        continue;
+                    }
+                    in_data = false;
+                    b"250 queued\r\n"
+                } else {
+                    let command = line.split(' ').next().unwrap_or_default();
+                    match command.to_ascii_uppercase().as_str() {
+                        "EHLO" => b"250-sink\r\n250 AUTH PLAIN LOGIN\r\n",
+                        "AUTH" => b"235 authenticated\r\n",
+                        "DATA" => {
+                            in_data = true;
+                            b"354 end with .\r\n"
+                        }
+                        "QUIT" => {
+                            write.write_all(b"221 bye\r\n").await.unwrap();
+                            break;
+                        }
+                        _ => b"250 ok\r\n",
+                    }
+                };
+                write.write_all(reply).await.unwrap();
+            }
+            transcript
+        });
+        (port, session)
+    }
+
+    #[tokio::test]
+    async fn sends_plain_and_html_alternatives() {
+        let (port, session) = smtp_sink().await;
+        let provider = EmailProvider::new(&EmailConfig {
+            host: "127.0.0.1".to_string(),
+            port: Some(port),
+            security: SmtpSecurity::None,
+            username: "fusion".to_string(),
+            password: "secret".to_string(),
+            from: "Fusion <noreply@example.com>".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let mut message = NotificationMessage::new("alice is live", "Ranked & chill");
+        message.set_url("https://live.bilibili.com/1");
+        provider
+            .send(
+                &NotificationAddress::new(NotificationChannel::Email, "bob@example.com"),
+                &message,
+            )
+            .await
+            .unwrap();
+
+        let transcript = session.await.unwrap();
+        assert!(transcript.contains("AUTH PLAIN"));
+        assert!(transcript.contains("MAIL FROM:<noreply@example.com>"));
+        assert!(transcript.contains("RCPT TO:<bob@example.com>"));
+        assert!(transcript.contains("Subject: alice is live"));
+        assert!(transcript.contains("multipart/alternative"));
+        assert!(transcript.contains("Content-Type: text/plain"));
+        assert!(transcript.contains("Content-Type: text/html"));
+        assert!(transcript.contains("Ranked &amp; chill"));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_recipients() {
+        let provider = EmailProvider::new(&EmailConfig {
+            host: "127.0.0.1".to_string(),
+            security: SmtpSecurity::None,
+            ..Default::default()
+        })
+        .unwrap();
+        let message = NotificationMessage::new("title", "body");
+
+        let err = provider
+            .send(
+                &NotificationAddress::new(NotificationChannel::Email, "not an address"),
+                &message,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid recipient"));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::notification::provider::NotificationMessage;
+
+/// Plain-text body: the title, the body and the room link.
+pub fn plain(message: &NotificationMessage) -> String {
+    let mut text = message.title().to_string();
+    if !message.body().trim().is_empty() {
+        text.push_str("\n\n");
+        text.push_str(message.body().trim());
+    }
+    if let Some(url) = link(message.url()) {
+        text.push_str("\n\n");
+        text.push_str(url);
+    }
+    text.push('\n');
+    text
+}
+
+/// HTML body with the cover image, when the message has one.
+pub fn html(message: &NotificationMessage) -> String {
+    let mut html = String::from(
+        "<!DOCTYPE html>\n<html>\n<body style=\"font-family: sans-serif; line-height: 1.5\">\n",
+    );
+    html.push_str(&format!("<h2>{}</h2>\n", escape(message.title())));
+    if !message.body().trim().is_empty() {
+        let lines: Vec<String> = message.body().trim().lines().map(escape).collect();
+        html.push_str(&format!("<p>{}</p>\n", lines.join("<br>\n")));
+    }
+    if let Some(cover) = link(message.metadata_value("cover")) {
+        html.push_str(&format!(
+            "<p><img src=\"{}\" alt=\"\" style=\"max-width: 100%\"></p>\n",
+            escape(cover)
+        ));
+    }
+    if let Some(url) = link(message.url()) {
+        html.push_str(&format!(
+            "<p><a href=\"{}\">Open the room</a></p>\n",
+            escape(url)
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+/// Only http(s) URLs end up in the mail.
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> NotificationMessage {
+        let mut message = NotificationMessage::new("alice is live", "<b>Ranked</b> & chill\nDay 3");
+        message
+            .set_url("https://live.bilibili.com/1?a=1&b=2")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg");
+        message
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        assert_eq!(
+            plain(&message()),
+            "alice is live\n\n<b>Ranked</b> & chill\nDay 3\n\nhttps://live.bilibili.com/1?a=1&b=2\n"
+        );
+        assert_eq!(plain(&NotificationMessage::new("title", "")), "title\n");
+    }
+
+    #[test]
+    fn renders_escaped_html() {
+        let html = html(&message());
+
+        assert!(html.contains("<h2>alice is live</h2>"));
+        assert!(html.contains("<p>&lt;b&gt;Ranked&lt;/b&gt; &amp; chill<br>\nDay 3</p>"));
+        assert!(html.contains("<img src=\"https://i0.hdslb.com/cover.jpg\""));
+        assert!(html.contains("<a href=\"https://live.bilibili.com/1?a=1&amp;b=2\">"));
+    }
+
+    #[test]
+    fn skips_non_http_links() {
+        let mut message = NotificationMessage::new("title", "body");
+        message
+            .set_url("javascript:alert(1)")
+            .insert_metadata("cover", "");
+        let html = html(&message);
+
+        assert!(!html.contains("<a "));
+        assert!(!html.contains("<img"));
+    }
+}
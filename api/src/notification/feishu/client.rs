@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use chrono::Utc;
+use migration::async_trait::async_trait;
+
+use crate::config::FeishuConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::feishu::msg::{RobotMessage, RobotResponse};
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+
+const USER_AGENT: &str = "Fusion-Feishu";
+
+/// Posts notifications as message cards to a Feishu or Lark custom bot. The
+/// address destination is the hook id at the end of the bot webhook url; bots
+/// with signing enabled need the `secret` in the address metadata.
+pub struct FeishuProvider {
+    client: reqwest::Client,
+    channel: NotificationChannel,
+    base_url: String,
+}
+
+impl FeishuProvider {
+    pub fn feishu(config: &FeishuConfig) -> anyhow::Result<Self> {
+        Self::new(
+            NotificationChannel::Feishu,
+            &config.base_url,
+            config.timeout_secs,
+        )
+    }
+
+    pub fn lark(config: &FeishuConfig) -> anyhow::Result<Self> {
+        Self::new(
+            NotificationChannel::Lark,
+            &config.lark_base_url,
+            config.timeout_secs,
+        )
+    }
+
+    fn new(
+        channel: NotificationChannel,
+        base_url: &str,
+        timeout_secs: u64,
+    ) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self {
+            client,
+            channel,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for FeishuProvider {
+    fn channel(&self) -> NotificationChannel {
+        self.channel
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let channel = self.channel;
+        let hook = address.destination().trim();
+        if hook.is_empty() || !hook.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            anyhow::bail!("{channel}: destination is not a bot hook id");
+        }
+
+        let mut body = RobotMessage::new(message);
+        if let Some(secret) = address
+            .metadata_value("secret")
+            .map(str::trim)
+            .filter(|secret| !secret.is_empty())
+        {
+            body = body.signed(secret, Utc::now().timestamp());
+        }
+
+        let url = format!("{}/open-apis/bot/v2/hook/{hook}", self.base_url);
+        // The hook id is the bot credential; keep it out of the logs.
+        let response = self
+            .client
+            .post(url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("{channel}: request failed"))?;
+        let status = response.status();
+        if !status.is_success() {
+            // The bot explains rejections in JSON; proxies in front of it
+            // answer with HTML, which is kept as is.
+            let body = response.text().await.unwrap_or_default();
+            let reason = match serde_json::from_str::<RobotResponse>(&body) {
+                Ok(response) => format!("({}): {}", response.code, response.msg),
+                Err(_) => body.trim().to_string(),
+            };
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("{channel}: bot answered {status}: {reason}"),
+            )
+            .into());
+        }
+
+        let response: RobotResponse = response
+            .json()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .with_context(|| format!("{channel}: invalid response"))?;
+
+        if response.code != 0 {
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!(
+                    "{channel}: bot rejected the message ({}): {}",
+                    response.code, response.msg
+                ),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::delivery::ErrorClass;
+    use crate::notification::test_server::answer_once;
+
+    #[tokio::test]
+    async fn retries_error_pages_of_proxies() {
+        let base_url = answer_once("502 Bad Gateway", "<html>Bad Gateway</html>").await;
+        let provider = FeishuProvider::new(NotificationChannel::Feishu, &base_url, 5).unwrap();
+        let address = NotificationAddress::new(NotificationChannel::Feishu, "hook-id");
+        let err = provider
+            .send(&address, &NotificationMessage::new("title", "body"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(ErrorClass::of(&err), ErrorClass::ServerError);
+        assert!(format!("{err:#}").contains("<html>Bad Gateway</html>"));
+    }
+}
@@ -0,0 +1,223 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use crate::notification::provider::NotificationMessage;
+
+/// Body of a custom bot hook request: an interactive message card.
+#[derive(Debug, Serialize)]
+pub struct RobotMessage {
+    /// Unix seconds, set together with `sign` on bots with signing enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign: Option<String>,
+    pub msg_type: &'static str,
+    pub card: Card,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Card {
+    pub header: Header,
+    pub elements: Vec<Element>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Header {
+    pub title: Text,
+    /// Header colour: red for streamers going live, blue for everything else.
+    pub template: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "tag", rename_all = "snake_case")]
+pub enum Text {
+    PlainText { content: String },
+    LarkMd { content: String },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "tag", rename_all = "snake_case")]
+pub enum Element {
+    Markdown { content: String },
+    Div { fields: Vec<Field> },
+    Action { actions: Vec<Button> },
+}
+
+#[derive(Debug, Serialize)]
+pub struct Field {
+    pub is_short: bool,
+    pub text: Text,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Button {
+    pub tag: &'static str,
+    pub text: Text,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub url: String,
+}
+
+/// Failures come back as a non-zero `code`; older deployments name the
+/// fields `StatusCode` and `StatusMessage`.
+#[derive(Debug, Deserialize)]
+pub struct RobotResponse {
+    #[serde(alias = "StatusCode")]
+    pub code: i64,
+    #[serde(default, alias = "StatusMessage")]
+    pub msg: String,
+}
+
+impl RobotMessage {
+    /// Card images need an `img_key` uploaded through the open API, which a
+    /// custom bot cannot do, so the cover is linked from the text instead.
+    pub fn new(message: &NotificationMessage) -> Self {
+        let text = |key: &str| {
+            message
+                .metadata_value(key)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+
+        let mut elements = Vec::new();
+        let mut content = message.body().trim().to_string();
+        if let Some(cover) = link(text("cover")) {
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(&format!("[Cover]({cover})"));
+        }
+        if !content.is_empty() {
+            elements.push(Element::Markdown { content });
+        }
+
+        let fields: Vec<Field> = [("Category", text("category")), ("Viewers", text("viewers"))]
+            .into_iter()
+            .filter_map(|(name, value)| {
+                Some(Field {
+                    is_short: true,
+                    text: Text::LarkMd {
+                        content: format!("**{name}**\n{}", value?),
+                    },
+                })
+            })
+            .collect();
+        if !fields.is_empty() {
+            elements.push(Element::Div { fields });
+        }
+
+        if let Some(url) = link(message.url()) {
+            elements.push(Element::Action {
+                actions: vec![Button {
+                    tag: "button",
+                    text: Text::PlainText {
+                        content: "Open the room".to_string(),
+                    },
+                    kind: "primary",
+                    url: url.to_string(),
+                }],
+            });
+        }
+
+        Self {
+            timestamp: None,
+            sign: None,
+            msg_type: "interactive",
+            card: Card {
+                header: Header {
+                    title: Text::PlainText {
+                        content: message.title().to_string(),
+                    },
+                    template: match text("event") {
+                        Some("live") => "red",
+                        _ => "blue",
+                    },
+                },
+                elements,
+            },
+        }
+    }
+
+    pub fn signed(mut self, secret: &str, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp.to_string());
+        self.sign = Some(sign(secret, timestamp));
+        self
+    }
+}
+
+/// The `sign` of bots with signing enabled: the base64 HMAC-SHA256 of an
+/// empty message, keyed with `{timestamp}\n{secret}`.
+pub fn sign(secret: &str, timestamp: i64) -> String {
+    let key = format!("{timestamp}\n{secret}");
+    let mac =
+        Hmac::<Sha256>::new_from_slice(key.as_bytes()).expect("HMAC accepts keys of any size");
+    STANDARD.encode(mac.finalize().into_bytes())
+}
+
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_with_the_timestamped_secret_as_key() {
+        assert_eq!(
+            sign("SEC0123456789abcdef", 1700000000),
+            "PiO7POLlSx/DM2qf8Dy/XCWeJK3exVnevp5G99LEN2M="
+        );
+    }
+
+    #[test]
+    fn renders_an_interactive_card() {
+        let mut message = NotificationMessage::new("alice is live", "Ranked");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("event", "live")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg")
+            .insert_metadata("category", "League of Legends")
+            .insert_metadata("viewers", "1200");
+        let body =
+            serde_json::to_value(RobotMessage::new(&message).signed("s", 1700000000)).unwrap();
+
+        assert_eq!(body["msg_type"], "interactive");
+        assert_eq!(body["timestamp"], "1700000000");
+        assert!(body["sign"].is_string());
+        let card = &body["card"];
+        assert_eq!(card["header"]["title"]["tag"], "plain_text");
+        assert_eq!(card["header"]["title"]["content"], "alice is live");
+        assert_eq!(card["header"]["template"], "red");
+        assert_eq!(card["elements"][0]["tag"], "markdown");
+        assert_eq!(
+            card["elements"][0]["content"],
+            "Ranked\n[Cover](https://i0.hdslb.com/cover.jpg)"
+        );
+        assert_eq!(card["elements"][1]["tag"], "div");
+        assert_eq!(card["elements"][1]["fields"][0]["text"]["tag"], "lark_md");
+        assert_eq!(
+            card["elements"][1]["fields"][1]["text"]["content"],
+            "**Viewers**\n1200"
+        );
+        let button = &card["elements"][2]["actions"][0];
+        assert_eq!(card["elements"][2]["tag"], "action");
+        assert_eq!(button["tag"], "button");
+        assert_eq!(button["url"], "https://live.bilibili.com/7734200");
+    }
+
+    #[test]
+    fn leaves_out_unsigned_and_empty_parts() {
+        let body = serde_json::to_value(RobotMessage::new(&NotificationMessage::new("hello", "")))
+            .unwrap();
+
+        assert!(body.get("timestamp").is_none());
+        assert!(body.get("sign").is_none());
+        assert_eq!(body["card"]["header"]["template"], "blue");
+        assert_eq!(body["card"]["elements"], serde_json::json!([]));
+    }
+}
@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+
+use crate::config::GotifyConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::gotify::msg::GotifyMessage;
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+
+const USER_AGENT: &str = "Fusion-Gotify";
+const TOKEN_HEADER: &str = "X-Gotify-Key";
+
+/// Pushes notifications through a Gotify server. The address destination is
+/// the server url and the application `token` goes in the address metadata.
+pub struct GotifyProvider {
+    client: reqwest::Client,
+}
+
+impl GotifyProvider {
+    pub fn new(config: GotifyConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for GotifyProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Gotify
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let server = address.destination().trim().trim_end_matches('/');
+        if !server.starts_with("https://") && !server.starts_with("http://") {
+            anyhow::bail!("gotify: destination is not an http(s) url");
+        }
+        let token = address
+            .metadata_value("token")
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .context("gotify: missing application token")?;
+
+        let response = self
+            .client
+            .post(format!("{server}/message"))
+            .header(TOKEN_HEADER, token)
+            .json(&GotifyMessage::new(message, address))
+            .send()
+            .await
+            .context("gotify: request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            // Gotify explains rejections in `errorDescription`.
+            let error = response.text().await.unwrap_or_default();
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("gotify: server answered {status}: {}", error.trim()),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
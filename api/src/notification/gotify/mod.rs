@@ -0,0 +1,4 @@
+mod client;
+pub mod msg;
+
+pub use client::GotifyProvider;
@@ -0,0 +1,170 @@
+use serde::Serialize;
+
+use crate::notification::priority::Priority;
+use crate::notification::provider::{NotificationAddress, NotificationMessage};
+
+/// Body of a `POST /message` request.
+#[derive(Debug, Serialize)]
+pub struct GotifyMessage {
+    pub title: String,
+    pub message: String,
+    /// Left out to use the application's default priority.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    pub extras: Extras,
+}
+
+/// Client extras understood by the Gotify apps.
+#[derive(Debug, Serialize)]
+pub struct Extras {
+    #[serde(rename = "client::display")]
+    pub display: Display,
+    #[serde(rename = "client::notification")]
+    pub notification: ClientNotification,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Display {
+    pub content_type: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientNotification {
+    /// Opened when the Android notification is tapped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub click: Option<Click>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub big_image_url: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Click {
+    pub url: String,
+}
+
+impl GotifyMessage {
+    pub fn new(message: &NotificationMessage, address: &NotificationAddress) -> Self {
+        let url = link(message.url());
+        let cover = link(message.metadata_value("cover"));
+        Self {
+            title: message.title().to_string(),
+            message: markdown(message),
+            priority: Priority::of(message, address).map(|priority| match priority {
+                Priority::Min => 0,
+                Priority::Low => 2,
+                Priority::Default => 5,
+                Priority::High => 8,
+                Priority::Urgent => 10,
+            }),
+            extras: Extras {
+                display: Display {
+                    content_type: "text/markdown",
+                },
+                notification: ClientNotification {
+                    click: url.map(|url| Click {
+                        url: url.to_string(),
+                    }),
+                    big_image_url: cover.map(str::to_string),
+                },
+            },
+        }
+    }
+}
+
+/// Markdown shown in the app: the body, the category and viewer lines, the
+/// room link and the cover.
+fn markdown(message: &NotificationMessage) -> String {
+    let text = |key: &str| {
+        message
+            .metadata_value(key)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut paragraphs = Vec::new();
+    if !message.body().trim().is_empty() {
+        paragraphs.push(message.body().trim().replace('\n', "  \n"));
+    }
+    let facts: Vec<String> = [("Category", text("category")), ("Viewers", text("viewers"))]
+        .into_iter()
+        .filter_map(|(name, value)| Some(format!("**{name}**: {}", value?)))
+        .collect();
+    if !facts.is_empty() {
+        paragraphs.push(facts.join("  \n"));
+    }
+    if let Some(url) = link(message.url()) {
+        paragraphs.push(format!("[Open the room]({url})"));
+    }
+    if let Some(cover) = link(text("cover")) {
+        paragraphs.push(format!("![cover]({cover})"));
+    }
+    if paragraphs.is_empty() {
+        // Gotify rejects messages without text.
+        return message.title().to_string();
+    }
+    paragraphs.join("\n\n")
+}
+
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationChannel;
+
+    #[test]
+    fn renders_markdown_with_client_extras() {
+        let address = NotificationAddress::new(NotificationChannel::Gotify, "https://gotify.lan");
+        let mut message = NotificationMessage::new("alice is live", "Ranked");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("priority", "urgent")
+            .insert_metadata("category", "League of Legends")
+            .insert_metadata("viewers", "1200")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg");
+        let body = serde_json::to_value(GotifyMessage::new(&message, &address)).unwrap();
+
+        assert_eq!(body["title"], "alice is live");
+        assert_eq!(
+            body["message"],
+            "Ranked\n\n**Category**: League of Legends  \n**Viewers**: 1200\n\n\
+             [Open the room](https://live.bilibili.com/7734200)\n\n\
+             ![cover](https://i0.hdslb.com/cover.jpg)"
+        );
+        assert_eq!(body["priority"], 10);
+        assert_eq!(
+            body["extras"]["client::display"]["contentType"],
+            "text/markdown"
+        );
+        assert_eq!(
+            body["extras"]["client::notification"]["click"]["url"],
+            "https://live.bilibili.com/7734200"
+        );
+        assert_eq!(
+            body["extras"]["client::notification"]["bigImageUrl"],
+            "https://i0.hdslb.com/cover.jpg"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_title_and_default_priority() {
+        let address = NotificationAddress::new(NotificationChannel::Gotify, "https://gotify.lan");
+        let body = serde_json::to_value(GotifyMessage::new(
+            &NotificationMessage::new("hello", ""),
+            &address,
+        ))
+        .unwrap();
+
+        assert_eq!(body["message"], "hello");
+        assert!(body.get("priority").is_none());
+        assert_eq!(
+            body["extras"]["client::notification"],
+            serde_json::json!({})
+        );
+    }
+}
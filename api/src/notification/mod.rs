@@ -1,6 +1,24 @@
 pub mod bark;
+mod delivery;
+pub mod dingtalk;
+pub mod discord;
+pub mod email;
+pub mod feishu;
+pub mod gotify;
+pub mod ntfy;
+mod priority;
 mod provider;
+pub mod pushover;
+mod rate_limit;
+pub mod slack;
+pub mod telegram;
+#[cfg(test)]
+mod test_server;
+pub mod web_push;
+pub mod webhook;
+pub mod wecom;
 
+pub use self::delivery::DeliveryReport;
 #[allow(unused_imports)]
 pub use self::provider::{
     NotificationAddress, NotificationCenter, NotificationChannel, NotificationMessage,
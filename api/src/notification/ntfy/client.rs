@@ -0,0 +1,78 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+
+use crate::config::NtfyConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::ntfy::msg::{self, Publish};
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+
+const USER_AGENT: &str = "Fusion-ntfy";
+
+/// Publishes notifications to an ntfy topic. The address destination is a
+/// topic url on any server, or a bare topic on the configured one; protected
+/// topics need an access `token` in the address metadata.
+pub struct NtfyProvider {
+    client: reqwest::Client,
+    config: NtfyConfig,
+}
+
+impl NtfyProvider {
+    pub fn new(config: NtfyConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self { client, config })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for NtfyProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Ntfy
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let (url, topic) = msg::split_topic(address.destination(), &self.config.base_url)
+            .context("ntfy: destination is not a topic or topic url")?;
+
+        let mut request = self
+            .client
+            .post(url)
+            .json(&Publish::new(&topic, message, address));
+        if let Some(token) = address
+            .metadata_value("token")
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+        {
+            request = request.bearer_auth(token);
+        }
+
+        // Topic urls may carry basic auth credentials; keep them out of the logs.
+        let response = request
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("ntfy: request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            // ntfy explains rejections in a JSON `error` field.
+            let error = response.text().await.unwrap_or_default();
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("ntfy: server answered {status}: {}", error.trim()),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,198 @@
+use reqwest::Url;
+use serde::Serialize;
+
+use crate::notification::priority::Priority;
+use crate::notification::provider::{NotificationAddress, NotificationMessage};
+
+/// Body of a JSON publish request, POSTed to the server root.
+#[derive(Debug, Serialize)]
+pub struct Publish<'a> {
+    pub topic: &'a str,
+    pub title: &'a str,
+    pub message: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    /// Tags naming an emoji short code are shown as that emoji.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Opened when the notification is tapped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub click: Option<&'a str>,
+    /// Attachment fetched by the app from its url.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attach: Option<&'a str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<Action<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Action<'a> {
+    pub action: &'static str,
+    pub label: &'static str,
+    pub url: &'a str,
+}
+
+impl<'a> Publish<'a> {
+    pub fn new(
+        topic: &'a str,
+        message: &'a NotificationMessage,
+        address: &'a NotificationAddress,
+    ) -> Self {
+        let body = message.body().trim();
+        let url = link(message.url());
+
+        // The user's own tags first, then one for the event.
+        let mut tags: Vec<String> = address
+            .metadata_value("tags")
+            .into_iter()
+            .flat_map(|tags| tags.split(','))
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect();
+        match message.metadata_value("event") {
+            Some("live") => tags.push("red_circle".to_string()),
+            Some("change") => tags.push("memo".to_string()),
+            _ => {}
+        }
+
+        Self {
+            topic,
+            title: message.title(),
+            // ntfy replaces an empty message with "triggered".
+            message: if body.is_empty() {
+                message.title()
+            } else {
+                body
+            },
+            priority: Priority::of(message, address).map(|priority| match priority {
+                Priority::Min => 1,
+                Priority::Low => 2,
+                Priority::Default => 3,
+                Priority::High => 4,
+                Priority::Urgent => 5,
+            }),
+            tags,
+            click: url,
+            attach: link(message.metadata_value("cover")),
+            actions: url
+                .map(|url| Action {
+                    action: "view",
+                    label: "Open the room",
+                    url,
+                })
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
+/// Splits a destination into the server url to publish to and the topic. A
+/// topic url such as `https://ntfy.example.com/alerts` names both; a bare
+/// topic is published to `base_url`.
+pub fn split_topic(destination: &str, base_url: &str) -> Option<(Url, String)> {
+    let destination = destination.trim();
+    let (mut url, topic) =
+        if destination.starts_with("https://") || destination.starts_with("http://") {
+            let url = Url::parse(destination).ok()?;
+            let topic = url.path_segments()?.rfind(|segment| !segment.is_empty())?;
+            let topic = topic.to_string();
+            (url, topic)
+        } else {
+            let base = Url::parse(&format!("{}/", base_url.trim_end_matches('/'))).ok()?;
+            (base.join(destination).ok()?, destination.to_string())
+        };
+
+    let valid = topic.len() <= 64
+        && topic
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if topic.is_empty() || !valid {
+        return None;
+    }
+
+    url.path_segments_mut().ok()?.pop_if_empty().pop().push("");
+    url.set_query(None);
+    url.set_fragment(None);
+    Some((url, topic))
+}
+
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationChannel;
+
+    #[test]
+    fn splits_topic_urls_and_bare_topics() {
+        let split = |destination| {
+            split_topic(destination, "https://ntfy.sh").map(|(url, topic)| (url.to_string(), topic))
+        };
+
+        assert_eq!(
+            split("https://push.example.com/ntfy/alerts?x=1"),
+            Some((
+                "https://push.example.com/ntfy/".to_string(),
+                "alerts".to_string()
+            ))
+        );
+        assert_eq!(
+            split("https://push.example.com/alerts/"),
+            Some((
+                "https://push.example.com/".to_string(),
+                "alerts".to_string()
+            ))
+        );
+        assert_eq!(
+            split(" alerts "),
+            Some(("https://ntfy.sh/".to_string(), "alerts".to_string()))
+        );
+        assert_eq!(
+            split_topic("alerts", "https://push.example.com/ntfy").map(|(url, _)| url.to_string()),
+            Some("https://push.example.com/ntfy/".to_string())
+        );
+        assert_eq!(split("https://push.example.com/"), None);
+        assert_eq!(split("a/b"), None);
+        assert_eq!(split("alerts!"), None);
+    }
+
+    #[test]
+    fn maps_priority_tags_and_links() {
+        let address = NotificationAddress::new(NotificationChannel::Ntfy, "alerts")
+            .insert_metadata("priority", "high")
+            .insert_metadata("tags", "tv, ,fusion");
+        let mut message = NotificationMessage::new("alice is live", "");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("event", "live")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg");
+        let body = serde_json::to_value(Publish::new("alerts", &message, &address)).unwrap();
+
+        assert_eq!(body["topic"], "alerts");
+        assert_eq!(body["message"], "alice is live");
+        assert_eq!(body["priority"], 4);
+        assert_eq!(
+            body["tags"],
+            serde_json::json!(["tv", "fusion", "red_circle"])
+        );
+        assert_eq!(body["click"], "https://live.bilibili.com/7734200");
+        assert_eq!(body["attach"], "https://i0.hdslb.com/cover.jpg");
+        assert_eq!(body["actions"][0]["action"], "view");
+        assert_eq!(
+            body["actions"][0]["url"],
+            "https://live.bilibili.com/7734200"
+        );
+
+        let plain = NotificationMessage::new("hello", "world");
+        let address = NotificationAddress::new(NotificationChannel::Ntfy, "alerts");
+        let body = serde_json::to_value(Publish::new("alerts", &plain, &address)).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"topic": "alerts", "title": "hello", "message": "world"})
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+use crate::notification::provider::{NotificationAddress, NotificationMessage};
+
+/// Urgency of a push, on ntfy's five-step scale. Providers map it onto their
+/// own range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Min,
+    Low,
+    Default,
+    High,
+    Urgent,
+}
+
+impl Priority {
+    /// The `priority` of the message metadata, else of the address metadata.
+    /// Unknown values are ignored.
+    pub fn of(message: &NotificationMessage, address: &NotificationAddress) -> Option<Self> {
+        message
+            .metadata_value("priority")
+            .and_then(|priority| priority.parse().ok())
+            .or_else(|| {
+                address
+                    .metadata_value("priority")
+                    .and_then(|priority| priority.parse().ok())
+            })
+    }
+}
+
+impl FromStr for Priority {
+    type Err = ();
+
+    /// Accepts ntfy's names and numbers (`min`/`1` to `urgent`/`max`/`5`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "min" | "1" => Ok(Priority::Min),
+            "low" | "2" => Ok(Priority::Low),
+            "default" | "normal" | "3" => Ok(Priority::Default),
+            "high" | "4" => Ok(Priority::High),
+            "urgent" | "max" | "5" => Ok(Priority::Urgent),
+            _ => Err(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationChannel;
+
+    #[test]
+    fn prefers_the_message_priority() {
+        let address = NotificationAddress::new(NotificationChannel::Ntfy, "alerts")
+            .insert_metadata("priority", "low");
+        let mut message = NotificationMessage::new("title", "body");
+        assert_eq!(Priority::of(&message, &address), Some(Priority::Low));
+
+        message.insert_metadata("priority", "MAX");
+        assert_eq!(Priority::of(&message, &address), Some(Priority::Urgent));
+
+        message.insert_metadata("priority", "loud");
+        assert_eq!(Priority::of(&message, &address), Some(Priority::Low));
+        assert_eq!(
+            Priority::of(
+                &message,
+                &NotificationAddress::new(NotificationChannel::Ntfy, "alerts")
+            ),
+            None
+        );
+    }
+}
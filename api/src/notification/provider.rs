@@ -1,13 +1,84 @@
 use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Result, anyhow};
 use migration::async_trait::async_trait;
-use uuid::Uuid;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+use crate::config::DeliveryConfig;
+use crate::notification::delivery::{Delivery, DeliveryFailure, DeliveryReport, ErrorClass};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum NotificationChannel {
     Bark,
+    Telegram,
+    Email,
+    Webhook,
+    Discord,
+    Slack,
+    DingTalk,
+    Feishu,
+    Lark,
+    WeCom,
+    Ntfy,
+    Gotify,
+    Pushover,
+    WebPush,
+}
+
+impl NotificationChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Bark => "bark",
+            NotificationChannel::Telegram => "telegram",
+            NotificationChannel::Email => "email",
+            NotificationChannel::Webhook => "webhook",
+            NotificationChannel::Discord => "discord",
+            NotificationChannel::Slack => "slack",
+            NotificationChannel::DingTalk => "dingtalk",
+            NotificationChannel::Feishu => "feishu",
+            NotificationChannel::Lark => "lark",
+            NotificationChannel::WeCom => "wecom",
+            NotificationChannel::Ntfy => "ntfy",
+            NotificationChannel::Gotify => "gotify",
+            NotificationChannel::Pushover => "pushover",
+            NotificationChannel::WebPush => "webpush",
+        }
+    }
+}
+
+impl Display for NotificationChannel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for NotificationChannel {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bark" => Ok(NotificationChannel::Bark),
+            "telegram" => Ok(NotificationChannel::Telegram),
+            "email" => Ok(NotificationChannel::Email),
+            "webhook" => Ok(NotificationChannel::Webhook),
+            "discord" => Ok(NotificationChannel::Discord),
+            "slack" => Ok(NotificationChannel::Slack),
+            "dingtalk" => Ok(NotificationChannel::DingTalk),
+            "feishu" => Ok(NotificationChannel::Feishu),
+            "lark" => Ok(NotificationChannel::Lark),
+            "wecom" => Ok(NotificationChannel::WeCom),
+            "ntfy" => Ok(NotificationChannel::Ntfy),
+            "gotify" => Ok(NotificationChannel::Gotify),
+            "pushover" => Ok(NotificationChannel::Pushover),
+            "webpush" => Ok(NotificationChannel::WebPush),
+            _ => Err(()),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -65,6 +136,7 @@ impl NotificationMessage {
 
 #[derive(Debug, Clone)]
 pub struct NotificationAddress {
+    id: Option<i32>,
     channel: NotificationChannel,
     destination: String,
     metadata: HashMap<String, String>,
@@ -73,12 +145,24 @@ pub struct NotificationAddress {
 impl NotificationAddress {
     pub fn new(channel: NotificationChannel, destination: impl Into<String>) -> Self {
         Self {
+            id: None,
             channel,
             destination: destination.into(),
             metadata: HashMap::new(),
         }
     }
 
+    /// Ties the address to the stored channel it was read from, so delivery
+    /// reports can point back at it.
+    pub fn with_id(mut self, id: i32) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
     pub fn channel(&self) -> NotificationChannel {
         self.channel
     }
@@ -103,23 +187,23 @@ impl NotificationAddress {
 
 #[derive(Debug, Clone)]
 pub struct NotificationTarget {
-    user_id: Uuid,
+    user_id: i32,
     addresses: Vec<NotificationAddress>,
 }
 
 impl NotificationTarget {
-    pub fn new(user_id: Uuid, addresses: Vec<NotificationAddress>) -> Self {
+    pub fn new(user_id: i32, addresses: Vec<NotificationAddress>) -> Self {
         Self { user_id, addresses }
     }
 
-    pub fn single(user_id: Uuid, address: NotificationAddress) -> Self {
+    pub fn single(user_id: i32, address: NotificationAddress) -> Self {
         Self {
             user_id,
             addresses: vec![address],
         }
     }
 
-    pub fn user_id(&self) -> Uuid {
+    pub fn user_id(&self) -> i32 {
         self.user_id
     }
 
@@ -139,23 +223,37 @@ pub trait NotificationProvider: Send + Sync + 'static {
     ) -> Result<()>;
 }
 
-#[derive(Default)]
+/// Routes messages to the provider of each address. Sends run concurrently,
+/// at most `concurrency` at a time across all targets, and each is bounded by
+/// the timeout of its channel.
 pub struct NotificationCenter {
     providers: HashMap<NotificationChannel, Arc<dyn NotificationProvider>>,
+    permits: Arc<Semaphore>,
+    timeout: Duration,
+    timeouts: HashMap<NotificationChannel, Duration>,
 }
 
 impl NotificationCenter {
-    pub fn new() -> Self {
+    pub fn new(config: &DeliveryConfig) -> Self {
         Self {
             providers: HashMap::new(),
+            permits: Arc::new(Semaphore::new(config.concurrency)),
+            timeout: Duration::from_secs(config.timeout_secs),
+            timeouts: config
+                .timeouts
+                .iter()
+                .filter_map(|(channel, secs)| {
+                    Some((channel.parse().ok()?, Duration::from_secs(*secs)))
+                })
+                .collect(),
         }
     }
 
-    pub fn with_providers<I>(providers: I) -> Self
+    pub fn with_providers<I>(config: &DeliveryConfig, providers: I) -> Self
     where
         I: IntoIterator<Item = Arc<dyn NotificationProvider>>,
     {
-        let mut center = Self::new();
+        let mut center = Self::new(config);
         for provider in providers {
             center.register(provider);
         }
@@ -167,32 +265,79 @@ impl NotificationCenter {
         self
     }
 
+    /// Sends the message to every address of the target and reports the
+    /// outcome of each; one failing address does not hold back the others.
     pub async fn notify_target(
         &self,
         target: &NotificationTarget,
         message: &NotificationMessage,
-    ) -> Result<()> {
-        for address in target.addresses() {
-            self.notify_address(address, message)
-                .await
-                .with_context(|| format!("failed to notify user {}", target.user_id()))?;
+    ) -> DeliveryReport {
+        let message = Arc::new(message.clone());
+        let mut results: Vec<Option<Result<(), DeliveryFailure>>> =
+            Vec::with_capacity(target.addresses().len());
+        let mut sends = JoinSet::new();
+        for (index, address) in target.addresses().iter().enumerate() {
+            let Some(provider) = self.providers.get(&address.channel()).cloned() else {
+                results.push(Some(Err(DeliveryFailure {
+                    class: ErrorClass::Unconfigured,
+                    error: anyhow!("no provider configured for channel {:?}", address.channel()),
+                })));
+                continue;
+            };
+            results.push(None);
+
+            let timeout = self.timeout_for(address.channel());
+            let permits = self.permits.clone();
+            let address = address.clone();
+            let message = message.clone();
+            sends.spawn(async move {
+                // The semaphore is never closed.
+                let _permit = permits.acquire_owned().await;
+                let result =
+                    match tokio::time::timeout(timeout, provider.send(&address, &message)).await {
+                        Ok(Ok(())) => Ok(()),
+                        Ok(Err(err)) => Err(DeliveryFailure::new(
+                            err.context(format!("channel {:?}", address.channel())),
+                        )),
+                        Err(_) => Err(DeliveryFailure {
+                            class: ErrorClass::Timeout,
+                            error: anyhow!(
+                                "channel {:?}: no answer within {}s",
+                                address.channel(),
+                                timeout.as_secs()
+                            ),
+                        }),
+                    };
+                (index, result)
+            });
         }
-        Ok(())
+
+        while let Some(joined) = sends.join_next().await {
+            match joined {
+                Ok((index, result)) => results[index] = Some(result),
+                Err(err) => tracing::error!(?err, "notification send task failed"),
+            }
+        }
+        let deliveries = target
+            .addresses()
+            .iter()
+            .zip(results)
+            .map(|(address, result)| Delivery {
+                address_id: address.id(),
+                channel: address.channel(),
+                // Only a panicking provider leaves its slot empty.
+                result: result.unwrap_or_else(|| {
+                    Err(DeliveryFailure::new(anyhow!(
+                        "channel {:?}: provider panicked",
+                        address.channel()
+                    )))
+                }),
+            })
+            .collect();
+        DeliveryReport::new(target.user_id(), deliveries)
     }
 
-    pub async fn notify_address(
-        &self,
-        address: &NotificationAddress,
-        message: &NotificationMessage,
-    ) -> Result<()> {
-        let provider = self
-            .providers
-            .get(&address.channel())
-            .ok_or_else(|| anyhow!("no provider configured for channel {:?}", address.channel()))?;
-
-        provider
-            .send(address, message)
-            .await
-            .with_context(|| format!("channel {:?}", address.channel()))
+    fn timeout_for(&self, channel: NotificationChannel) -> Duration {
+        self.timeouts.get(&channel).copied().unwrap_or(self.timeout)
     }
 }
@@ -0,0 +1,112 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+
+use crate::config::PushoverConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+use crate::notification::pushover::msg::{PushoverMessage, PushoverResponse};
+
+const USER_AGENT: &str = "Fusion-Pushover";
+
+/// Pushes notifications through Pushover with the configured application
+/// token. The address destination is the user or group key.
+pub struct PushoverProvider {
+    client: reqwest::Client,
+    config: PushoverConfig,
+}
+
+impl PushoverProvider {
+    pub fn new(config: PushoverConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self { client, config })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for PushoverProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Pushover
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let body = PushoverMessage::new(self.config.app_token.trim(), message, address);
+        let response = self
+            .client
+            .post(format!(
+                "{}/1/messages.json",
+                self.config.base_url.trim_end_matches('/')
+            ))
+            .json(&body)
+            .send()
+            .await
+            .context("pushover: request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            // Pushover lists the reasons of rejections in JSON; proxies in
+            // front of it answer with HTML, which is kept as is.
+            let body = response.text().await.unwrap_or_default();
+            let reason = serde_json::from_str::<PushoverResponse>(&body)
+                .map(|response| response.errors.join("; "))
+                .unwrap_or(body);
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("pushover: message rejected ({status}): {}", reason.trim()),
+            )
+            .into());
+        }
+
+        let response: PushoverResponse = response
+            .json()
+            .await
+            .context("pushover: invalid response")?;
+        if response.status != 1 {
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!(
+                    "pushover: message rejected ({status}): {}",
+                    response.errors.join("; ")
+                ),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::delivery::ErrorClass;
+    use crate::notification::test_server::answer_once;
+
+    #[tokio::test]
+    async fn retries_error_pages_of_proxies() {
+        let base_url = answer_once("502 Bad Gateway", "<html>Bad Gateway</html>").await;
+        let provider = PushoverProvider::new(PushoverConfig {
+            app_token: "app".to_string(),
+            base_url,
+            ..Default::default()
+        })
+        .unwrap();
+        let address = NotificationAddress::new(NotificationChannel::Pushover, "user");
+        let err = provider
+            .send(&address, &NotificationMessage::new("title", "body"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(ErrorClass::of(&err), ErrorClass::ServerError);
+        assert!(format!("{err:#}").contains("<html>Bad Gateway</html>"));
+    }
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+use crate::notification::priority::Priority;
+use crate::notification::provider::{NotificationAddress, NotificationMessage};
+
+const TITLE_LIMIT: usize = 250;
+const MESSAGE_LIMIT: usize = 1024;
+const URL_LIMIT: usize = 512;
+
+/// Emergency pushes repeat every `retry` seconds until acknowledged or until
+/// `expire` seconds have passed.
+const EMERGENCY_RETRY_SECS: u32 = 60;
+const EMERGENCY_EXPIRE_SECS: u32 = 3600;
+
+/// Body of a `POST /1/messages.json` request.
+#[derive(Debug, Serialize)]
+pub struct PushoverMessage<'a> {
+    pub token: &'a str,
+    /// User or group key.
+    pub user: &'a str,
+    pub title: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_title: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expire: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sound: Option<&'a str>,
+    /// Limits the push to one of the user's devices.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<&'a str>,
+}
+
+/// `status` is 1 on success; rejections list their reasons in `errors`.
+#[derive(Debug, Deserialize)]
+pub struct PushoverResponse {
+    pub status: i64,
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+impl<'a> PushoverMessage<'a> {
+    pub fn new(
+        token: &'a str,
+        message: &'a NotificationMessage,
+        address: &'a NotificationAddress,
+    ) -> Self {
+        let value = |value: Option<&'a str>| value.map(str::trim).filter(|value| !value.is_empty());
+        let url = value(message.url())
+            .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+            .filter(|url| url.len() <= URL_LIMIT);
+        let body = message.body().trim();
+        let priority = Priority::of(message, address).map(|priority| match priority {
+            Priority::Min => -2,
+            Priority::Low => -1,
+            Priority::Default => 0,
+            Priority::High => 1,
+            Priority::Urgent => 2,
+        });
+        let emergency = priority == Some(2);
+
+        Self {
+            token,
+            user: address.destination().trim(),
+            title: truncate(message.title(), TITLE_LIMIT),
+            // Pushover rejects messages without text.
+            message: truncate(
+                if body.is_empty() {
+                    message.title()
+                } else {
+                    body
+                },
+                MESSAGE_LIMIT,
+            ),
+            url,
+            url_title: url.map(|_| "Open the room"),
+            priority,
+            retry: emergency.then_some(EMERGENCY_RETRY_SECS),
+            expire: emergency.then_some(EMERGENCY_EXPIRE_SECS),
+            sound: value(message.metadata_value("sound"))
+                .or_else(|| value(address.metadata_value("sound"))),
+            device: value(address.metadata_value("device")),
+        }
+    }
+}
+
+/// Cuts `text` to `limit` characters, ending with an ellipsis when shortened.
+fn truncate(text: &str, limit: usize) -> String {
+    match text.char_indices().nth(limit.saturating_sub(1)) {
+        Some((end, _)) if text[end..].chars().count() > 1 => format!("{}…", &text[..end]),
+        _ => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::NotificationChannel;
+
+    #[test]
+    fn maps_priority_sound_and_link() {
+        let address = NotificationAddress::new(
+            NotificationChannel::Pushover,
+            " uQiRzpo4DXghDmr9QzzfQu27cmVRsG ",
+        )
+        .insert_metadata("sound", "pushover")
+        .insert_metadata("device", "pixel");
+        let mut message = NotificationMessage::new("alice is live", "Ranked");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("priority", "high")
+            .insert_metadata("sound", "siren");
+        let body = serde_json::to_value(PushoverMessage::new("app", &message, &address)).unwrap();
+
+        assert_eq!(body["token"], "app");
+        assert_eq!(body["user"], "uQiRzpo4DXghDmr9QzzfQu27cmVRsG");
+        assert_eq!(body["message"], "Ranked");
+        assert_eq!(body["url"], "https://live.bilibili.com/7734200");
+        assert_eq!(body["url_title"], "Open the room");
+        assert_eq!(body["priority"], 1);
+        assert!(body.get("retry").is_none());
+        assert_eq!(body["sound"], "siren");
+        assert_eq!(body["device"], "pixel");
+    }
+
+    #[test]
+    fn repeats_urgent_pushes_until_acknowledged() {
+        let address = NotificationAddress::new(NotificationChannel::Pushover, "user")
+            .insert_metadata("priority", "urgent");
+        let message = NotificationMessage::new("alice is live", "");
+        let body = serde_json::to_value(PushoverMessage::new("app", &message, &address)).unwrap();
+
+        assert_eq!(body["message"], "alice is live");
+        assert_eq!(body["priority"], 2);
+        assert_eq!(body["retry"], EMERGENCY_RETRY_SECS);
+        assert_eq!(body["expire"], EMERGENCY_EXPIRE_SECS);
+        assert!(body.get("url").is_none());
+        assert!(body.get("sound").is_none());
+    }
+}
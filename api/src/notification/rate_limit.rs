@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use tokio::time::Instant;
+
+/// Remembers, per webhook, until when its platform asked us to hold off, so
+/// the next message waits instead of running into a 429.
+#[derive(Default)]
+pub struct RateLimits {
+    blocked_until: Mutex<HashMap<String, Instant>>,
+}
+
+impl RateLimits {
+    /// Sleeps until `key` may be called again.
+    pub async fn wait(&self, key: &str) {
+        let until = {
+            let mut blocked = self.blocked_until.lock().unwrap();
+            let now = Instant::now();
+            blocked.retain(|_, until| *until > now);
+            blocked.get(key).copied()
+        };
+        if let Some(until) = until {
+            tokio::time::sleep_until(until).await;
+        }
+    }
+
+    /// Holds `key` back for `duration`, keeping a longer block already in place.
+    pub fn block(&self, key: &str, duration: Duration) {
+        let until = Instant::now() + duration;
+        let mut blocked = self.blocked_until.lock().unwrap();
+        let entry = blocked.entry(key.to_string()).or_insert(until);
+        if *entry < until {
+            *entry = until;
+        }
+    }
+}
+
+/// Reads a header holding seconds, which Discord sends with a fraction.
+pub fn seconds_header(headers: &HeaderMap, name: &str) -> Option<Duration> {
+    headers
+        .get(name)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|secs| secs.is_finite() && *secs >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    seconds_header(headers, RETRY_AFTER.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn parses_fractional_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        headers.insert("x-ratelimit-reset-after", HeaderValue::from_static("0.25"));
+        headers.insert("x-bogus", HeaderValue::from_static("-1"));
+
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+        assert_eq!(
+            seconds_header(&headers, "x-ratelimit-reset-after"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(seconds_header(&headers, "x-bogus"), None);
+        assert_eq!(seconds_header(&headers, "x-missing"), None);
+    }
+
+    #[tokio::test]
+    async fn waits_out_blocked_keys_only() {
+        let limits = RateLimits::default();
+        limits.block("a", Duration::from_millis(200));
+        limits.block("a", Duration::from_millis(10));
+
+        let start = Instant::now();
+        limits.wait("b").await;
+        assert!(start.elapsed() < Duration::from_millis(100));
+        limits.wait("a").await;
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+}
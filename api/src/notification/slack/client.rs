@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+use reqwest::StatusCode;
+use tracing::warn;
+
+use crate::config::ChatWebhookConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+use crate::notification::rate_limit::{self, RateLimits};
+use crate::notification::slack::msg::WebhookMessage;
+
+const USER_AGENT: &str = "Fusion-Slack";
+
+/// Posts notifications as Block Kit messages to the Slack incoming webhook
+/// url in the address destination.
+pub struct SlackProvider {
+    client: reqwest::Client,
+    config: ChatWebhookConfig,
+    limits: RateLimits,
+}
+
+impl SlackProvider {
+    pub fn new(config: ChatWebhookConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self {
+            client,
+            config,
+            limits: RateLimits::default(),
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for SlackProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Slack
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let url = address.destination().trim();
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            anyhow::bail!("slack: destination is not an http(s) url");
+        }
+        let body = WebhookMessage::new(message);
+
+        let mut retries = 0;
+        loop {
+            self.limits.wait(url).await;
+            // The webhook url is its token; keep it out of the logs.
+            let response = self
+                .client
+                .post(url)
+                .json(&body)
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url)
+                .context("slack: request failed")?;
+
+            let status = response.status();
+            if status.is_success() {
+                return Ok(());
+            }
+            if status == StatusCode::TOO_MANY_REQUESTS
+                && let Some(wait) = rate_limit::retry_after(response.headers())
+            {
+                // Later messages to this webhook wait as well, retried or not.
+                self.limits.block(url, wait);
+                if retries < self.config.max_retries
+                    && wait <= Duration::from_secs(self.config.max_retry_after_secs)
+                {
+                    retries += 1;
+                    warn!(?wait, retries, "slack: rate limited, retrying");
+                    continue;
+                }
+            }
+
+            // Slack answers errors with a short code such as `invalid_payload`.
+            let error = response.text().await.unwrap_or_default();
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("slack: webhook answered {status}: {}", error.trim()),
+            )
+            .into());
+        }
+    }
+}
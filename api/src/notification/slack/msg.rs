@@ -0,0 +1,203 @@
+use serde::Serialize;
+
+use crate::notification::provider::NotificationMessage;
+
+const SECTION_LIMIT: usize = 3000;
+const FIELD_LIMIT: usize = 2000;
+
+/// Body of an incoming-webhook request: Block Kit blocks, plus `text` as the
+/// fallback shown in notifications.
+#[derive(Debug, Serialize)]
+pub struct WebhookMessage {
+    pub text: String,
+    pub blocks: Vec<Block>,
+    pub unfurl_links: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Block {
+    Section {
+        text: Text,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        accessory: Option<Element>,
+    },
+    #[serde(rename = "section")]
+    Fields {
+        fields: Vec<Text>,
+    },
+    Actions {
+        elements: Vec<Element>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Text {
+    PlainText { text: String },
+    Mrkdwn { text: String },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Element {
+    Image { image_url: String, alt_text: String },
+    Button { text: Text, url: String },
+}
+
+impl WebhookMessage {
+    pub fn new(message: &NotificationMessage) -> Self {
+        let text = |key: &str| {
+            message
+                .metadata_value(key)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+        };
+        let url = link(message.url());
+
+        let title = escape_within(message.title(), SECTION_LIMIT);
+        let mut section = match url {
+            Some(url) => format!("*<{}|{}>*", escape_within(url, SECTION_LIMIT), title),
+            None => format!("*{title}*"),
+        };
+        let body = message.body().trim();
+        let room = SECTION_LIMIT.saturating_sub(section.chars().count() + 1);
+        if !body.is_empty() && room > 0 {
+            section.push('\n');
+            section.push_str(&escape_within(body, room));
+        }
+        let mut blocks = vec![Block::Section {
+            text: Text::Mrkdwn { text: section },
+            accessory: link(text("cover")).map(|cover| Element::Image {
+                image_url: cover.to_string(),
+                alt_text: "Cover".to_string(),
+            }),
+        }];
+
+        let fields: Vec<Text> = [("Category", text("category")), ("Viewers", text("viewers"))]
+            .into_iter()
+            .filter_map(|(name, value)| {
+                let value = value?;
+                let room = FIELD_LIMIT - name.len() - 3;
+                Some(Text::Mrkdwn {
+                    text: format!("*{name}*\n{}", escape_within(value, room)),
+                })
+            })
+            .collect();
+        if !fields.is_empty() {
+            blocks.push(Block::Fields { fields });
+        }
+
+        if let Some(url) = url {
+            blocks.push(Block::Actions {
+                elements: vec![Element::Button {
+                    text: Text::PlainText {
+                        text: "Open the room".to_string(),
+                    },
+                    url: url.to_string(),
+                }],
+            });
+        }
+
+        Self {
+            text: message.title().to_string(),
+            blocks,
+            unfurl_links: false,
+        }
+    }
+}
+
+/// Slack refuses blocks with malformed urls, so only http(s) ones are sent.
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+/// Escapes the mrkdwn control characters of `text`, cutting it so the result
+/// stays within `limit` characters, ellipsis included.
+fn escape_within(text: &str, limit: usize) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut count = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let piece = match c {
+            '&' => "&amp;",
+            '<' => "&lt;",
+            '>' => "&gt;",
+            _ => "",
+        };
+        let width = if piece.is_empty() { 1 } else { piece.len() };
+        // Keep one character free for the ellipsis unless this is the end.
+        let reserve = usize::from(chars.peek().is_some());
+        if count + width + reserve > limit {
+            escaped.push('…');
+            break;
+        }
+        count += width;
+        match piece {
+            "" => escaped.push(c),
+            piece => escaped.push_str(piece),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_blocks_with_cover_fields_and_button() {
+        let mut message = NotificationMessage::new("alice <3 is live", "Ranked & chill");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("category", "League of Legends")
+            .insert_metadata("viewers", "1200")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg");
+        let body = serde_json::to_value(WebhookMessage::new(&message)).unwrap();
+
+        assert_eq!(body["text"], "alice <3 is live");
+        let section = &body["blocks"][0];
+        assert_eq!(section["type"], "section");
+        assert_eq!(section["text"]["type"], "mrkdwn");
+        assert_eq!(
+            section["text"]["text"],
+            "*<https://live.bilibili.com/7734200|alice &lt;3 is live>*\nRanked &amp; chill"
+        );
+        assert_eq!(section["accessory"]["type"], "image");
+        assert_eq!(
+            section["accessory"]["image_url"],
+            "https://i0.hdslb.com/cover.jpg"
+        );
+
+        let fields = &body["blocks"][1];
+        assert_eq!(fields["type"], "section");
+        assert_eq!(fields["fields"][0]["text"], "*Category*\nLeague of Legends");
+        assert_eq!(fields["fields"][1]["text"], "*Viewers*\n1200");
+
+        let button = &body["blocks"][2]["elements"][0];
+        assert_eq!(body["blocks"][2]["type"], "actions");
+        assert_eq!(button["type"], "button");
+        assert_eq!(button["text"]["type"], "plain_text");
+        assert_eq!(button["url"], "https://live.bilibili.com/7734200");
+    }
+
+    #[test]
+    fn renders_only_the_title_for_bare_messages() {
+        let body =
+            serde_json::to_value(WebhookMessage::new(&NotificationMessage::new("hello", "")))
+                .unwrap();
+
+        assert_eq!(body["blocks"].as_array().unwrap().len(), 1);
+        assert_eq!(body["blocks"][0]["text"]["text"], "*hello*");
+        assert!(body["blocks"][0].get("accessory").is_none());
+    }
+
+    #[test]
+    fn escapes_within_the_limit() {
+        assert_eq!(escape_within("a&b", 7), "a&amp;b");
+        assert_eq!(escape_within("a&b", 6), "a…");
+        assert_eq!(escape_within("abcd", 4), "abcd");
+        assert_eq!(escape_within("abcde", 4), "abc…");
+    }
+}
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::config::TelegramConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+use crate::notification::telegram::msg::{
+    self, CAPTION_LIMIT, Chat, MESSAGE_LIMIT, SendMessage, SendPhoto, TelegramResponse,
+};
+
+const PARSE_MODE: &str = "MarkdownV2";
+
+/// Sends notifications through a Telegram bot. The address destination is the
+/// chat id; `thread_id` and `silent` in the address metadata pick a forum topic
+/// and mute the message.
+pub struct TelegramProvider {
+    client: reqwest::Client,
+    config: TelegramConfig,
+}
+
+impl TelegramProvider {
+    pub fn new(config: TelegramConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self { client, config })
+    }
+
+    /// Calls a Bot API method, waiting out `retry_after` on 429 responses.
+    async fn call<T: Serialize>(&self, method: &str, body: &T) -> anyhow::Result<()> {
+        let url = format!(
+            "{}/bot{}/{}",
+            self.config.base_url.trim_end_matches('/'),
+            self.config.bot_token.trim(),
+            method
+        );
+        let mut retries = 0;
+        loop {
+            // Errors carry the url, which contains the bot token.
+            let response = self
+                .client
+                .post(&url)
+                .json(body)
+                .send()
+                .await
+                .map_err(reqwest::Error::without_url)
+                .with_context(|| format!("telegram: failed to call {method}"))?;
+            let status = response.status();
+            let text = response
+                .text()
+                .await
+                .map_err(reqwest::Error::without_url)
+                .with_context(|| format!("telegram: failed to read the response to {method}"))?;
+            let response = match serde_json::from_str::<TelegramResponse>(&text) {
+                Ok(response) => response,
+                // The Bot API explains errors in JSON, which also carries the
+                // `retry_after` of 429s; proxies in front of it answer with HTML.
+                Err(_) if !status.is_success() => {
+                    return Err(StatusError::new(
+                        status.as_u16(),
+                        format!("telegram: {method} answered {status}: {}", text.trim()),
+                    )
+                    .into());
+                }
+                Err(err) => {
+                    return Err(err)
+                        .with_context(|| format!("telegram: invalid response to {method}"));
+                }
+            };
+            if response.ok {
+                return Ok(());
+            }
+
+            let code = response.error_code.unwrap_or(status.as_u16());
+            let retry_after = response
+                .parameters
+                .and_then(|parameters| parameters.retry_after);
+            if code == 429
+                && let Some(secs) = retry_after
+                && retries < self.config.max_retries
+                && secs <= self.config.max_retry_after_secs
+            {
+                retries += 1;
+                warn!(method, secs, retries, "telegram: rate limited, retrying");
+                tokio::time::sleep(Duration::from_secs(secs)).await;
+                continue;
+            }
+
+            // Bot API error codes follow the HTTP status codes.
+            return Err(StatusError::new(
+                code,
+                format!(
+                    "telegram: error {code}: {}",
+                    response.description.unwrap_or_default()
+                ),
+            )
+            .into());
+        }
+    }
+
+    fn chat(address: &NotificationAddress) -> anyhow::Result<Chat> {
+        let message_thread_id = address
+            .metadata_value("thread_id")
+            .map(|id| id.trim().parse::<i64>())
+            .transpose()
+            .context("telegram: thread_id must be a number")?;
+        Ok(Chat {
+            chat_id: address.destination().trim().to_string(),
+            message_thread_id,
+            disable_notification: address.metadata_value("silent") == Some("true"),
+        })
+    }
+
+    async fn send_photo(
+        &self,
+        chat: &Chat,
+        photo: &str,
+        notification: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let request = SendPhoto {
+            chat,
+            photo,
+            caption: msg::format(
+                notification.title(),
+                notification.body(),
+                notification.url(),
+                CAPTION_LIMIT,
+            ),
+            parse_mode: PARSE_MODE,
+        };
+        self.call("sendPhoto", &request).await
+    }
+
+    async fn send_message(
+        &self,
+        chat: &Chat,
+        notification: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let request = SendMessage {
+            chat,
+            text: msg::format(
+                notification.title(),
+                notification.body(),
+                notification.url(),
+                MESSAGE_LIMIT,
+            ),
+            parse_mode: PARSE_MODE,
+        };
+        self.call("sendMessage", &request).await
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for TelegramProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Telegram
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let chat = Self::chat(address)?;
+        let cover = message
+            .metadata_value("cover")
+            .filter(|cover| cover.starts_with("https://") || cover.starts_with("http://"));
+        let Some(cover) = cover else {
+            return self.send_message(&chat, message).await;
+        };
+
+        match self.send_photo(&chat, cover, message).await {
+            // Telegram could not fetch or use the image; the text still matters.
+            Err(err)
+                if err
+                    .downcast_ref::<StatusError>()
+                    .is_some_and(|err| err.status == 400) =>
+            {
+                warn!(?err, "telegram: cover rejected, sending text only");
+                self.send_message(&chat, message).await
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notification::delivery::ErrorClass;
+    use crate::notification::test_server::answer_once;
+
+    #[tokio::test]
+    async fn retries_error_pages_of_proxies() {
+        let base_url = answer_once("502 Bad Gateway", "<html>Bad Gateway</html>").await;
+        let provider = TelegramProvider::new(TelegramConfig {
+            bot_token: "123:token".to_string(),
+            base_url,
+            ..Default::default()
+        })
+        .unwrap();
+        let address = NotificationAddress::new(NotificationChannel::Telegram, "42");
+        let err = provider
+            .send(&address, &NotificationMessage::new("title", "body"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(ErrorClass::of(&err), ErrorClass::ServerError);
+        assert!(format!("{err:#}").contains("<html>Bad Gateway</html>"));
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+/// Longest text of `sendMessage`.
+pub const MESSAGE_LIMIT: usize = 4096;
+
+/// Longest caption of `sendPhoto`.
+pub const CAPTION_LIMIT: usize = 1024;
+
+/// Characters MarkdownV2 reserves outside of entities.
+const RESERVED: &[char] = &[
+    '_', '*', '[', ']', '(', ')', '~', '`', '>', '#', '+', '-', '=', '|', '{', '}', '.', '!', '\\',
+];
+
+const ELLIPSIS: char = '…';
+
+/// Chat a message goes to.
+#[derive(Debug, Serialize)]
+pub struct Chat {
+    /// Numeric chat id or `@channelusername`.
+    pub chat_id: String,
+    /// Topic of a forum supergroup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_thread_id: Option<i64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub disable_notification: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SendMessage<'a> {
+    #[serde(flatten)]
+    pub chat: &'a Chat,
+    pub text: String,
+    pub parse_mode: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SendPhoto<'a> {
+    #[serde(flatten)]
+    pub chat: &'a Chat,
+    /// HTTP URL Telegram downloads the photo from.
+    pub photo: &'a str,
+    pub caption: String,
+    pub parse_mode: &'static str,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TelegramResponse {
+    pub ok: bool,
+    #[serde(default)]
+    pub error_code: Option<u16>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: Option<ResponseParameters>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResponseParameters {
+    /// Seconds to wait before repeating a request rejected with 429.
+    #[serde(default)]
+    pub retry_after: Option<u64>,
+}
+
+/// Escapes the URL of an inline link, where only `)` and `\` are reserved.
+pub fn escape_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        if c == ')' || c == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Formats a notification as MarkdownV2: the title in bold, the body, and a
+/// link to `url`. The body, then the title, is cut with an ellipsis so the
+/// result stays within `limit` UTF-16 units, the unit Telegram counts in.
+pub fn format(title: &str, body: &str, url: Option<&str>, limit: usize) -> String {
+    let link = url
+        .filter(|url| !url.trim().is_empty())
+        .map(|url| format!("\n\n[Open]({})", escape_url(url)))
+        .unwrap_or_default();
+    let mut budget = limit.saturating_sub(utf16_len(&link));
+
+    // Two `*` around the title and the line break before the body.
+    let title = escape_within(title, budget.saturating_sub(3));
+    budget = budget.saturating_sub(utf16_len(&title) + 3);
+    let body = escape_within(body, budget);
+
+    if body.is_empty() {
+        format!("*{title}*{link}")
+    } else {
+        format!("*{title}*\n{body}{link}")
+    }
+}
+
+/// Escapes plain text for MarkdownV2, cutting it with an ellipsis when the
+/// escaped text would be longer than `budget` UTF-16 units. Escapes are never
+/// split.
+fn escape_within(text: &str, budget: usize) -> String {
+    let text = text.trim();
+    let width = |c: char| c.len_utf16() + usize::from(RESERVED.contains(&c));
+    let fits = text.chars().map(width).sum::<usize>() <= budget;
+    let budget = if fits {
+        budget
+    } else {
+        budget.saturating_sub(ELLIPSIS.len_utf16())
+    };
+
+    let mut escaped = String::with_capacity(text.len());
+    let mut used = 0;
+    for c in text.chars() {
+        used += width(c);
+        if used > budget {
+            break;
+        }
+        if RESERVED.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    if !fits && budget > 0 {
+        escaped.push(ELLIPSIS);
+    }
+    escaped
+}
+
+fn utf16_len(text: &str) -> usize {
+    text.chars().map(char::len_utf16).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escape(text: &str) -> String {
+        escape_within(text, usize::MAX)
+    }
+
+    #[test]
+    fn escapes_reserved_characters() {
+        assert_eq!(escape("1+1=2. (really!)"), r"1\+1\=2\. \(really\!\)");
+        assert_eq!(escape(r"a_b*c\d"), r"a\_b\*c\\d");
+        assert_eq!(escape("直播中"), "直播中");
+        assert_eq!(
+            escape_url("https://example.com/a_(b)"),
+            r"https://example.com/a_(b\)"
+        );
+    }
+
+    #[test]
+    fn formats_title_body_and_link() {
+        assert_eq!(
+            format(
+                "alice is live",
+                "Ranked - day 3",
+                Some("https://live.bilibili.com/1"),
+                MESSAGE_LIMIT
+            ),
+            "*alice is live*\nRanked \\- day 3\n\n[Open](https://live.bilibili.com/1)"
+        );
+        assert_eq!(
+            format("alice is live", "", None, MESSAGE_LIMIT),
+            "*alice is live*"
+        );
+    }
+
+    #[test]
+    fn truncates_the_body_to_the_limit() {
+        let body = "a.".repeat(2000);
+        let text = format("title", &body, Some("https://example.com"), CAPTION_LIMIT);
+
+        assert!(utf16_len(&text) <= CAPTION_LIMIT);
+        assert!(text.contains('…'));
+        // The cut never leaves a dangling escape.
+        assert!(!text.contains("\\…"));
+        assert!(text.ends_with("[Open](https://example.com)"));
+
+        let short = format("title", "short", None, CAPTION_LIMIT);
+        assert!(!short.contains('…'));
+    }
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+use reqwest::{StatusCode, Url};
+
+use crate::config::WebPushConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::priority::Priority;
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+use crate::notification::web_push::ece::{self, SubscriptionKeys};
+use crate::notification::web_push::msg::PushPayload;
+use crate::notification::web_push::vapid::VapidKey;
+
+const USER_AGENT: &str = "Fusion-WebPush";
+
+/// The push service no longer knows the subscription (404 or 410), so it
+/// should be forgotten. The endpoint is a capability url and is kept out of
+/// the message.
+#[derive(Debug, thiserror::Error)]
+#[error("web push: subscription is gone ({status})")]
+pub struct SubscriptionGone {
+    pub endpoint: String,
+    pub status: StatusCode,
+}
+
+/// Sends encrypted pushes to browser subscriptions. The address destination
+/// is the subscription endpoint and its `p256dh` and `auth` keys go in the
+/// address metadata.
+pub struct WebPushProvider {
+    client: reqwest::Client,
+    key: VapidKey,
+    config: WebPushConfig,
+}
+
+impl WebPushProvider {
+    pub fn new(config: WebPushConfig) -> anyhow::Result<Self> {
+        let key = VapidKey::from_base64(&config.vapid_private_key)?;
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self {
+            client,
+            key,
+            config,
+        })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for WebPushProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::WebPush
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let endpoint = address.destination().trim();
+        let url = Url::parse(endpoint)
+            .ok()
+            .filter(|url| url.scheme() == "https")
+            .context("web push: endpoint is not an https url")?;
+        let keys = address
+            .metadata_value("p256dh")
+            .zip(address.metadata_value("auth"))
+            .and_then(|(p256dh, auth)| SubscriptionKeys::parse(p256dh, auth))
+            .context("web push: missing or malformed subscription keys")?;
+        let body = ece::encrypt(&keys, &PushPayload::new(message).encode())?;
+        let urgency = match Priority::of(message, address) {
+            Some(Priority::Min) => "very-low",
+            Some(Priority::Low) => "low",
+            Some(Priority::Default) | None => "normal",
+            Some(Priority::High | Priority::Urgent) => "high",
+        };
+        let authorization =
+            self.key
+                .authorization(&url, &self.config.subject, chrono::Utc::now().timestamp());
+
+        let response = self
+            .client
+            .post(url)
+            .header("TTL", self.config.ttl_secs)
+            .header("Urgency", urgency)
+            .header(reqwest::header::CONTENT_ENCODING, "aes128gcm")
+            .header(reqwest::header::CONTENT_TYPE, "application/octet-stream")
+            .header(reqwest::header::AUTHORIZATION, authorization)
+            .body(body)
+            .send()
+            .await
+            .map_err(reqwest::Error::without_url)
+            .context("web push: request failed")?;
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND || status == StatusCode::GONE {
+            return Err(SubscriptionGone {
+                endpoint: endpoint.to_string(),
+                status,
+            }
+            .into());
+        }
+        if !status.is_success() {
+            let error = response.text().await.unwrap_or_default();
+            return Err(StatusError::new(
+                status.as_u16(),
+                format!("web push: push service answered {status}: {}", error.trim()),
+            )
+            .into());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,149 @@
+//! `aes128gcm` message encryption for Web Push (RFC 8188 and RFC 8291).
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes128Gcm, Nonce};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hkdf::Hkdf;
+use p256::ecdh::diffie_hellman;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use p256::{PublicKey, SecretKey};
+use rand_core::{OsRng, RngCore};
+use sha2::Sha256;
+
+/// Size of the single record a push message is sent in.
+const RECORD_SIZE: u32 = 4096;
+const SALT_LEN: usize = 16;
+const PUBLIC_KEY_LEN: usize = 65;
+const HEADER_LEN: usize = SALT_LEN + 4 + 1 + PUBLIC_KEY_LEN;
+const TAG_LEN: usize = 16;
+
+/// Longest payload that keeps the encrypted body within the 4096 bytes every
+/// push service accepts: the header, the tag and the padding delimiter take
+/// the rest.
+pub const MAX_PLAINTEXT: usize = RECORD_SIZE as usize - HEADER_LEN - TAG_LEN - 1;
+
+/// The `keys` of a browser `PushSubscription`.
+#[derive(Debug, Clone)]
+pub struct SubscriptionKeys {
+    p256dh: PublicKey,
+    auth: [u8; 16],
+}
+
+impl SubscriptionKeys {
+    /// Decodes the base64url `p256dh` and `auth` keys, padded or not.
+    pub fn parse(p256dh: &str, auth: &str) -> Option<Self> {
+        let p256dh = PublicKey::from_sec1_bytes(&decode(p256dh)?).ok()?;
+        let auth = decode(auth)?.try_into().ok()?;
+        Some(Self { p256dh, auth })
+    }
+}
+
+fn decode(value: &str) -> Option<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim().trim_end_matches('='))
+        .ok()
+}
+
+/// Encrypts `plaintext` for the subscription with a fresh key pair and salt.
+pub fn encrypt(keys: &SubscriptionKeys, plaintext: &[u8]) -> anyhow::Result<Vec<u8>> {
+    let mut salt = [0; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    encrypt_with(&SecretKey::random(&mut OsRng), &salt, keys, plaintext)
+}
+
+fn encrypt_with(
+    as_secret: &SecretKey,
+    salt: &[u8; SALT_LEN],
+    keys: &SubscriptionKeys,
+    plaintext: &[u8],
+) -> anyhow::Result<Vec<u8>> {
+    if plaintext.len() > MAX_PLAINTEXT {
+        anyhow::bail!("web push payload exceeds {MAX_PLAINTEXT} bytes");
+    }
+
+    let as_public = as_secret.public_key().to_encoded_point(false);
+    let ua_public = keys.p256dh.to_encoded_point(false);
+    let shared = diffie_hellman(as_secret.to_nonzero_scalar(), keys.p256dh.as_affine());
+
+    // IKM = HKDF(auth, ECDH, "WebPush: info" || 0x00 || ua_public || as_public)
+    let mut key_info = b"WebPush: info\0".to_vec();
+    key_info.extend_from_slice(ua_public.as_bytes());
+    key_info.extend_from_slice(as_public.as_bytes());
+    let mut ikm = [0; 32];
+    Hkdf::<Sha256>::new(Some(&keys.auth), shared.raw_secret_bytes())
+        .expand(&key_info, &mut ikm)
+        .expect("32 bytes is a valid HKDF-SHA256 length");
+
+    let prk = Hkdf::<Sha256>::new(Some(salt), &ikm);
+    let mut cek = [0; 16];
+    let mut nonce = [0; 12];
+    prk.expand(b"Content-Encoding: aes128gcm\0", &mut cek)
+        .expect("16 bytes is a valid HKDF-SHA256 length");
+    prk.expand(b"Content-Encoding: nonce\0", &mut nonce)
+        .expect("12 bytes is a valid HKDF-SHA256 length");
+
+    // A single record ends with the 0x02 delimiter and no padding.
+    let mut record = Vec::with_capacity(plaintext.len() + 1);
+    record.extend_from_slice(plaintext);
+    record.push(2);
+    let ciphertext = Aes128Gcm::new(&cek.into())
+        .encrypt(Nonce::from_slice(&nonce), record.as_slice())
+        .map_err(|_| anyhow::anyhow!("web push payload encryption failed"))?;
+
+    let mut body = Vec::with_capacity(HEADER_LEN + ciphertext.len());
+    body.extend_from_slice(salt);
+    body.extend_from_slice(&RECORD_SIZE.to_be_bytes());
+    body.push(PUBLIC_KEY_LEN as u8);
+    body.extend_from_slice(as_public.as_bytes());
+    body.extend_from_slice(&ciphertext);
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_the_rfc_8291_example() {
+        let as_secret =
+            SecretKey::from_slice(&decode("yfWPiYE-n46HLnH0KqZOF1fJJU3MYrct3AELtAQ-oRw").unwrap())
+                .unwrap();
+        let salt = decode("DGv6ra1nlYgDCS1FRnbzlw")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let keys = SubscriptionKeys::parse(
+            "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4",
+            "BTBZMqHH6r4Tts7J_aSIgg==",
+        )
+        .unwrap();
+
+        let body = encrypt_with(
+            &as_secret,
+            &salt,
+            &keys,
+            b"When I grow up, I want to be a watermelon",
+        )
+        .unwrap();
+
+        assert_eq!(
+            URL_SAFE_NO_PAD.encode(body),
+            "DGv6ra1nlYgDCS1FRnbzlwAAEABBBP4z9KsN6nGRTbVYI_c7VJSPQTBtkgcy27mlmlMoZIIgDll6e3vC\
+             YLocInmYWAmS6TlzAC8wEqKK6PBru3jl7A_yl95bQpu6cVPTpK4Mqgkf1CXztLVBSt2Ks3oZwbuwXPXLWyou\
+             BWLVWGNWQexSgSxsj_Qulcy4a-fN"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        assert!(SubscriptionKeys::parse("BCVx", "BTBZMqHH6r4Tts7J_aSIgg").is_none());
+        assert!(
+            SubscriptionKeys::parse(
+                "BCVxsr7N_eNgVRqvHtD0zTZsEc6-VV-JvLexhqUzORcxaOzi6-AYWXvTBHm4bjyPjs7Vd8pZGH6SRpkNtoIAiw4",
+                "BTBZMqHH6r4T",
+            )
+            .is_none()
+        );
+    }
+}
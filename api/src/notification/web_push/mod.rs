@@ -0,0 +1,7 @@
+mod client;
+pub mod ece;
+pub mod msg;
+mod vapid;
+
+pub use client::{SubscriptionGone, WebPushProvider};
+pub use vapid::VapidKey;
@@ -0,0 +1,116 @@
+use serde::Serialize;
+
+use crate::notification::provider::NotificationMessage;
+use crate::notification::web_push::ece::MAX_PLAINTEXT;
+
+/// JSON handed to the service worker's `push` event, which shows it with
+/// `showNotification(title, { body, image, tag, data: { url } })`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PushPayload {
+    pub title: String,
+    pub body: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    /// Lets a newer push about the same streamer replace the older one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub streamer_id: Option<String>,
+}
+
+impl PushPayload {
+    pub fn new(message: &NotificationMessage) -> Self {
+        let text = |key: &str| {
+            message
+                .metadata_value(key)
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let streamer_id = text("streamer_id");
+        Self {
+            title: message.title().to_string(),
+            body: message.body().trim().to_string(),
+            url: link(message.url()).map(str::to_string),
+            image: link(message.metadata_value("cover")).map(str::to_string),
+            tag: streamer_id.as_ref().map(|id| format!("streamer-{id}")),
+            event: text("event"),
+            streamer_id,
+        }
+    }
+
+    /// Serializes the payload, dropping the image and then shortening the
+    /// body and title until it fits in a single encrypted record.
+    pub fn encode(mut self) -> Vec<u8> {
+        loop {
+            let bytes = serde_json::to_vec(&self).expect("payload serializes to JSON");
+            if bytes.len() <= MAX_PLAINTEXT {
+                return bytes;
+            }
+            if self.image.take().is_some() {
+                continue;
+            }
+            let text = if self.body.is_empty() {
+                &mut self.title
+            } else {
+                &mut self.body
+            };
+            let keep = text.chars().count() / 2;
+            *text = match text.char_indices().nth(keep) {
+                Some((end, _)) if keep > 0 => format!("{}…", &text[..end]),
+                _ => String::new(),
+            };
+        }
+    }
+}
+
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn carries_the_link_cover_and_streamer_tag() {
+        let mut message = NotificationMessage::new("alice is live", " Ranked ");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("event", "live")
+            .insert_metadata("streamer_id", "42")
+            .insert_metadata("cover", "javascript:alert(1)");
+        let payload: serde_json::Value =
+            serde_json::from_slice(&PushPayload::new(&message).encode()).unwrap();
+
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "title": "alice is live",
+                "body": "Ranked",
+                "url": "https://live.bilibili.com/7734200",
+                "tag": "streamer-42",
+                "event": "live",
+                "streamer_id": "42",
+            })
+        );
+    }
+
+    #[test]
+    fn shrinks_to_fit_a_single_record() {
+        let mut message = NotificationMessage::new("alice is live", "直播".repeat(2000));
+        message.insert_metadata("cover", "https://i0.hdslb.com/cover.jpg");
+        let bytes = PushPayload::new(&message).encode();
+        let payload: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+
+        assert!(bytes.len() <= MAX_PLAINTEXT);
+        assert!(payload.get("image").is_none());
+        assert_eq!(payload["title"], "alice is live");
+        assert!(payload["body"].as_str().unwrap().ends_with('…'));
+    }
+}
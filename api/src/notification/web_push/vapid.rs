@@ -0,0 +1,122 @@
+//! VAPID application server identification (RFC 8292).
+
+use anyhow::Context;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{Signature, SigningKey};
+use rand_core::OsRng;
+use reqwest::Url;
+use serde::Serialize;
+
+/// Push services refuse tokens valid for more than a day.
+const TOKEN_LIFETIME_SECS: i64 = 12 * 60 * 60;
+
+/// The P-256 key pair identifying this server to push services. Browsers get
+/// the public key as `applicationServerKey` when subscribing.
+#[derive(Clone)]
+pub struct VapidKey {
+    key: SigningKey,
+    public_key: String,
+}
+
+#[derive(Serialize)]
+struct Claims<'a> {
+    aud: String,
+    exp: i64,
+    sub: &'a str,
+}
+
+impl VapidKey {
+    /// Reads a base64url encoded private key, as printed by `fusion vapid-keys`.
+    pub fn from_base64(private_key: &str) -> anyhow::Result<Self> {
+        let bytes = URL_SAFE_NO_PAD
+            .decode(private_key.trim().trim_end_matches('='))
+            .context("VAPID private key is not base64url")?;
+        let key = SigningKey::from_slice(&bytes).context("invalid VAPID private key")?;
+        Ok(Self::from_key(key))
+    }
+
+    pub fn generate() -> Self {
+        Self::from_key(SigningKey::random(&mut OsRng))
+    }
+
+    fn from_key(key: SigningKey) -> Self {
+        let point = key.verifying_key().to_encoded_point(false);
+        Self {
+            public_key: URL_SAFE_NO_PAD.encode(point.as_bytes()),
+            key,
+        }
+    }
+
+    pub fn private_key(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.key.to_bytes())
+    }
+
+    /// Uncompressed public key, base64url encoded.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// `Authorization` header value for a push to `endpoint` at `now` (unix
+    /// seconds).
+    pub fn authorization(&self, endpoint: &Url, subject: &str, now: i64) -> String {
+        let header = URL_SAFE_NO_PAD.encode(br#"{"typ":"JWT","alg":"ES256"}"#);
+        let claims = Claims {
+            aud: endpoint.origin().ascii_serialization(),
+            exp: now + TOKEN_LIFETIME_SECS,
+            sub: subject,
+        };
+        let claims =
+            URL_SAFE_NO_PAD.encode(serde_json::to_vec(&claims).expect("claims serialize to JSON"));
+        let unsigned = format!("{header}.{claims}");
+        let signature: Signature = self.key.sign(unsigned.as_bytes());
+        let token = format!(
+            "{unsigned}.{}",
+            URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        );
+        format!("vapid t={token}, k={}", self.public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::VerifyingKey;
+    use p256::ecdsa::signature::Verifier;
+
+    #[test]
+    fn signs_a_token_for_the_push_service_origin() {
+        let key = VapidKey::generate();
+        let restored = VapidKey::from_base64(&key.private_key()).unwrap();
+        assert_eq!(restored.public_key(), key.public_key());
+
+        let endpoint = Url::parse("https://fcm.googleapis.com/fcm/send/abc:def").unwrap();
+        let header = key.authorization(&endpoint, "mailto:admin@example.com", 1_700_000_000);
+        let (token, public_key) = header
+            .strip_prefix("vapid t=")
+            .and_then(|rest| rest.split_once(", k="))
+            .unwrap();
+        assert_eq!(public_key, key.public_key());
+
+        let (unsigned, signature) = token.rsplit_once('.').unwrap();
+        let claims: serde_json::Value = serde_json::from_slice(
+            &URL_SAFE_NO_PAD
+                .decode(unsigned.split_once('.').unwrap().1)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(claims["aud"], "https://fcm.googleapis.com");
+        assert_eq!(claims["exp"], 1_700_000_000 + TOKEN_LIFETIME_SECS);
+        assert_eq!(claims["sub"], "mailto:admin@example.com");
+
+        let verifying_key =
+            VerifyingKey::from_sec1_bytes(&URL_SAFE_NO_PAD.decode(public_key).unwrap()).unwrap();
+        let signature = Signature::from_slice(&URL_SAFE_NO_PAD.decode(signature).unwrap()).unwrap();
+        assert!(
+            verifying_key
+                .verify(unsigned.as_bytes(), &signature)
+                .is_ok()
+        );
+    }
+}
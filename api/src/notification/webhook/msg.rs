@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::notification::provider::NotificationMessage;
+
+/// Event of messages that do not name one in their `event` metadata.
+const DEFAULT_EVENT: &str = "notification";
+
+/// Longest wait between two attempts.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// JSON body POSTed to a webhook.
+#[derive(Debug, Serialize)]
+pub struct Envelope<'a> {
+    /// Unique per notification and repeated on retries, like the
+    /// `Idempotency-Key` header.
+    pub id: &'a str,
+    /// `live`, `change`, or `notification` for messages without an event.
+    pub event: &'a str,
+    /// When the notification was created.
+    pub timestamp: DateTime<Utc>,
+    pub message: EnvelopeMessage<'a>,
+    /// The streamer the notification is about, if any.
+    pub streamer: Option<EnvelopeStreamer<'a>>,
+    /// Every metadata entry of the message, as strings.
+    pub metadata: &'a HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnvelopeMessage<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    pub url: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EnvelopeStreamer<'a> {
+    pub id: i32,
+    pub name: Option<&'a str>,
+    pub platform: Option<&'a str>,
+    pub room_id: Option<&'a str>,
+    pub room_url: Option<&'a str>,
+    pub title: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub cover_image: Option<&'a str>,
+    pub viewer_count: Option<i64>,
+    pub creator_id: Option<i32>,
+}
+
+impl<'a> Envelope<'a> {
+    pub fn new(id: &'a str, message: &'a NotificationMessage, timestamp: DateTime<Utc>) -> Self {
+        let text = |key: &str| {
+            message
+                .metadata_value(key)
+                .filter(|value| !value.is_empty())
+        };
+        let streamer = text("streamer_id")
+            .and_then(|id| id.parse().ok())
+            .map(|id| EnvelopeStreamer {
+                id,
+                name: text("streamer_name"),
+                platform: text("platform"),
+                room_id: text("room_id"),
+                room_url: message.url(),
+                title: text("title"),
+                category: text("category"),
+                cover_image: text("cover"),
+                viewer_count: text("viewers").and_then(|viewers| viewers.parse().ok()),
+                creator_id: text("creator_id").and_then(|id| id.parse().ok()),
+            });
+
+        Self {
+            id,
+            event: event(message),
+            timestamp,
+            message: EnvelopeMessage {
+                title: message.title(),
+                body: message.body(),
+                url: message.url(),
+            },
+            streamer,
+            metadata: message.metadata(),
+        }
+    }
+}
+
+/// Id of the notification, sent as the envelope `id` and the `Idempotency-Key`:
+/// the `delivery_id` of the message, which the outbox keeps when it retries a
+/// notification, or a random one for messages sent outside of it.
+pub fn delivery_id(message: &NotificationMessage) -> String {
+    message
+        .metadata_value("delivery_id")
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map_or_else(|| Uuid::new_v4().to_string(), str::to_string)
+}
+
+pub fn event(message: &NotificationMessage) -> &str {
+    message
+        .metadata_value("event")
+        .filter(|event| !event.is_empty())
+        .unwrap_or(DEFAULT_EVENT)
+}
+
+/// Wait before retry number `retries + 1`: `base_secs` doubled per earlier
+/// retry, capped at [`MAX_RETRY_BACKOFF`].
+pub fn retry_backoff(base_secs: u64, retries: u32) -> Duration {
+    let factor = 2u64.checked_pow(retries).unwrap_or(u64::MAX);
+    Duration::from_secs(base_secs.saturating_mul(factor)).min(MAX_RETRY_BACKOFF)
+}
+
+/// Value of the signature header: the hex HMAC-SHA256 of `{timestamp}.{body}`
+/// keyed with the channel secret, prefixed with `sha256=`.
+pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={:x}", mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn signs_timestamp_and_body() {
+        assert_eq!(
+            signature("whsec", 1767225600, br#"{"id":"1"}"#),
+            "sha256=c166b09566b8ca1e2d11dcb6fddb0520c6d50c3132b7a33d06aa6bd924692954"
+        );
+        assert_ne!(
+            signature("whsec", 1767225601, br#"{"id":"1"}"#),
+            signature("whsec", 1767225600, br#"{"id":"1"}"#)
+        );
+    }
+
+    #[test]
+    fn doubles_the_retry_backoff_up_to_the_cap() {
+        assert_eq!(retry_backoff(2, 0), Duration::from_secs(2));
+        assert_eq!(retry_backoff(2, 3), Duration::from_secs(16));
+        assert_eq!(retry_backoff(2, 20), MAX_RETRY_BACKOFF);
+        assert_eq!(retry_backoff(2, 64), MAX_RETRY_BACKOFF);
+        assert_eq!(retry_backoff(u64::MAX, 1), MAX_RETRY_BACKOFF);
+    }
+
+    #[test]
+    fn keeps_the_delivery_id_of_the_message() {
+        let mut message = NotificationMessage::new("alice is live", "Ranked");
+        message.insert_metadata("delivery_id", "outbox-12-3");
+        assert_eq!(delivery_id(&message), "outbox-12-3");
+
+        let plain = NotificationMessage::new("hello", "world");
+        assert_eq!(delivery_id(&plain).len(), 36);
+        assert_ne!(delivery_id(&plain), delivery_id(&plain));
+    }
+
+    #[test]
+    fn builds_the_envelope_from_message_metadata() {
+        let mut message = NotificationMessage::new("alice is live", "Ranked");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("event", "live")
+            .insert_metadata("streamer_id", "3")
+            .insert_metadata("streamer_name", "alice")
+            .insert_metadata("viewers", "1200")
+            .insert_metadata("cover", "");
+        let timestamp = Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap();
+        let envelope = serde_json::to_value(Envelope::new("d-1", &message, timestamp)).unwrap();
+
+        assert_eq!(envelope["id"], "d-1");
+        assert_eq!(envelope["event"], "live");
+        assert_eq!(envelope["timestamp"], "2026-01-01T00:00:00Z");
+        assert_eq!(envelope["message"]["title"], "alice is live");
+        assert_eq!(envelope["streamer"]["id"], 3);
+        assert_eq!(envelope["streamer"]["name"], "alice");
+        assert_eq!(
+            envelope["streamer"]["room_url"],
+            "https://live.bilibili.com/7734200"
+        );
+        assert_eq!(envelope["streamer"]["viewer_count"], 1200);
+        assert_eq!(envelope["streamer"]["cover_image"], serde_json::Value::Null);
+        assert_eq!(envelope["metadata"]["viewers"], "1200");
+
+        let plain = NotificationMessage::new("hello", "world");
+        let envelope = serde_json::to_value(Envelope::new("d-2", &plain, timestamp)).unwrap();
+        assert_eq!(envelope["event"], "notification");
+        assert_eq!(envelope["streamer"], serde_json::Value::Null);
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use migration::async_trait::async_trait;
+
+use crate::config::WeComConfig;
+use crate::notification::delivery::StatusError;
+use crate::notification::provider::{
+    NotificationAddress, NotificationChannel, NotificationMessage, NotificationProvider,
+};
+use crate::notification::wecom::msg::{RobotMessage, RobotResponse};
+
+const USER_AGENT: &str = "Fusion-WeCom";
+
+/// Posts notifications as markdown to a WeCom group robot. The address
+/// destination is the robot `key`.
+pub struct WeComProvider {
+    client: reqwest::Client,
+    config: WeComConfig,
+}
+
+impl WeComProvider {
+    pub fn new(config: WeComConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .context("failed to build reqwest client")?;
+        Ok(Self { client, config })
+    }
+}
+
+#[async_trait]
+impl NotificationProvider for WeComProvider {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::WeCom
+    }
+
+    async fn send(
+        &self,
+        address: &NotificationAddress,
+        message: &NotificationMessage,
+    ) -> anyhow::Result<()> {
+        let url = format!(
+            "{}/cgi-bin/webhook/send",
+            self.config.base_url.trim_end_matches('/')
+        );
+        // The query carries the robot key; keep it out of the logs.
+        let response: RobotResponse = self
+            .client
+            .post(url)
+            .query(&[("key", address.destination().trim())])
+            .json(&RobotMessage::new(message))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(reqwest::Error::without_url)
+            .context("wecom: request failed")?
+            .json()
+            .await
+            .context("wecom: invalid response")?;
+
+        if response.errcode != 0 {
+            let message = format!(
+                "wecom: robot rejected the message ({}): {}",
+                response.errcode, response.errmsg
+            );
+            // Reported as a 429 so the delivery is retried later.
+            if response.is_rate_limited() {
+                return Err(StatusError::new(429, message).into());
+            }
+            anyhow::bail!(message);
+        }
+        Ok(())
+    }
+}
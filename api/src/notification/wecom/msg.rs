@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+
+use crate::notification::provider::NotificationMessage;
+
+/// Limit of the markdown content, in UTF-8 bytes.
+const CONTENT_LIMIT: usize = 4096;
+
+/// Body of a group robot `send` request. WeCom robot markdown has no images,
+/// so the cover is linked next to the room.
+#[derive(Debug, Serialize)]
+pub struct RobotMessage {
+    pub msgtype: &'static str,
+    pub markdown: Markdown,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Markdown {
+    pub content: String,
+}
+
+/// WeCom answers every request with 200 and reports failures here.
+#[derive(Debug, Deserialize)]
+pub struct RobotResponse {
+    pub errcode: i64,
+    #[serde(default)]
+    pub errmsg: String,
+}
+
+impl RobotResponse {
+    /// Whether the robot refused the message for exceeding its frequency
+    /// limit (45009) or the sending limit of the group (45033).
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.errcode, 45009 | 45033)
+    }
+}
+
+impl RobotMessage {
+    pub fn new(message: &NotificationMessage) -> Self {
+        Self {
+            msgtype: "markdown",
+            markdown: Markdown {
+                content: markdown(message),
+            },
+        }
+    }
+}
+
+/// Title, body, quoted category and viewer lines, then the links. The body is
+/// shortened to keep the whole within [`CONTENT_LIMIT`].
+pub fn markdown(message: &NotificationMessage) -> String {
+    let text = |key: &str| {
+        message
+            .metadata_value(key)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+
+    let head = format!("**{}**", message.title().trim());
+    let mut tail = Vec::new();
+    for (name, value) in [("Category", text("category")), ("Viewers", text("viewers"))] {
+        if let Some(value) = value {
+            tail.push(format!("> {name}: <font color=\"comment\">{value}</font>"));
+        }
+    }
+    let links: Vec<String> = [("Open the room", message.url()), ("Cover", text("cover"))]
+        .into_iter()
+        .filter_map(|(name, url)| Some(format!("[{name}]({})", link(url)?)))
+        .collect();
+    if !links.is_empty() {
+        tail.push(links.join("  "));
+    }
+
+    let mut lines = vec![head];
+    let body = message.body().trim();
+    let used = lines[0].len() + tail.iter().map(|line| line.len() + 1).sum::<usize>();
+    if !body.is_empty() && used + 1 < CONTENT_LIMIT {
+        lines.push(truncate(body, CONTENT_LIMIT - used - 1));
+    }
+    lines.extend(tail);
+    lines.join("\n")
+}
+
+/// Cuts `text` to at most `limit` bytes on a character boundary, ending with
+/// an ellipsis when shortened.
+fn truncate(text: &str, limit: usize) -> String {
+    if text.len() <= limit {
+        return text.to_string();
+    }
+    let mut end = limit.saturating_sub('…'.len_utf8());
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…", &text[..end])
+}
+
+fn link(url: Option<&str>) -> Option<&str> {
+    url.map(str::trim)
+        .filter(|url| url.starts_with("https://") || url.starts_with("http://"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_markdown_with_facts_and_links() {
+        let mut message = NotificationMessage::new("alice is live", "Ranked");
+        message
+            .set_url("https://live.bilibili.com/7734200")
+            .insert_metadata("cover", "https://i0.hdslb.com/cover.jpg")
+            .insert_metadata("category", "League of Legends")
+            .insert_metadata("viewers", "1200");
+        let body = serde_json::to_value(RobotMessage::new(&message)).unwrap();
+
+        assert_eq!(body["msgtype"], "markdown");
+        assert_eq!(
+            body["markdown"]["content"],
+            "**alice is live**\nRanked\n\
+             > Category: <font color=\"comment\">League of Legends</font>\n\
+             > Viewers: <font color=\"comment\">1200</font>\n\
+             [Open the room](https://live.bilibili.com/7734200)  [Cover](https://i0.hdslb.com/cover.jpg)"
+        );
+        assert_eq!(
+            markdown(&NotificationMessage::new("hello", "")),
+            "**hello**"
+        );
+    }
+
+    #[test]
+    fn tells_rate_limits_from_other_errors() {
+        let response = |errcode| RobotResponse {
+            errcode,
+            errmsg: String::new(),
+        };
+
+        assert!(response(45009).is_rate_limited());
+        assert!(response(45033).is_rate_limited());
+        assert!(!response(93000).is_rate_limited());
+    }
+
+    #[test]
+    fn keeps_content_within_the_byte_limit() {
+        let mut message = NotificationMessage::new("title", "直".repeat(2000));
+        message.set_url("https://live.bilibili.com/1");
+        let content = markdown(&message);
+
+        assert!(content.len() <= CONTENT_LIMIT);
+        assert!(content.contains("…\n[Open the room]"));
+        assert_eq!(truncate("abcdef", 5), "ab…");
+    }
+}
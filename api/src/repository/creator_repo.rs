@@ -0,0 +1,54 @@
+use crate::error::{AppResult, IntoAppResult};
+use chrono::Utc;
+use entity::creator::{ActiveModel, Model};
+use entity::prelude::Creator;
+use sea_orm::{ActiveModelTrait, DbConn, EntityTrait, PaginatorTrait, QueryOrder, Set};
+
+pub struct CreatorRepository {
+    db: DbConn,
+}
+
+impl CreatorRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, name: &str) -> AppResult<Model> {
+        let creator = ActiveModel {
+            name: Set(name.to_string()),
+            ..Default::default()
+        };
+        Creator::insert(creator)
+            .exec_with_returning(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<Model>> {
+        Creator::find_by_id(id)
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn list(&self, page: u64, page_size: u64) -> AppResult<(u64, Vec<Model>)> {
+        let paginator = Creator::find()
+            .order_by_asc(entity::creator::Column::Id)
+            .paginate(&self.db, page_size);
+        let total = paginator.num_items().await?;
+        let creators = paginator.fetch_page(page).await?;
+        Ok((total, creators))
+    }
+
+    pub async fn rename(&self, model: Model, name: &str) -> AppResult<Model> {
+        let mut creator: ActiveModel = model.into();
+        creator.name = Set(name.to_string());
+        creator.updated_at = Set(Utc::now().naive_utc());
+        creator.update(&self.db).await.into_app_result()
+    }
+
+    pub async fn delete(&self, id: i32) -> AppResult<u64> {
+        let result = Creator::delete_by_id(id).exec(&self.db).await?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,177 @@
+use crate::domain::notification_outbox::OutboxEvent;
+use crate::error::{AppResult, IntoAppResult};
+use crate::repository::NotificationOutboxRepository;
+use chrono::NaiveDateTime;
+use entity::live_session::{ActiveModel, Column, Model};
+use entity::prelude::{LiveSession, Streamer, Subscription};
+use entity::streamer::Model as StreamerModel;
+use sea_orm::sea_query::{Expr, Query, SimpleExpr};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbConn, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set,
+};
+
+pub struct LiveSessionRepository {
+    db: DbConn,
+}
+
+impl LiveSessionRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    /// Opens a session and queues the live notification with it. Takes the
+    /// connection so both commit in the transaction that stores the live state,
+    /// and a session never exists without its notification.
+    pub async fn open<C: ConnectionTrait>(
+        db: &C,
+        streamer_id: i32,
+        started_at: NaiveDateTime,
+        title: &str,
+        category: &str,
+        cover_image: &str,
+    ) -> AppResult<Model> {
+        let session = ActiveModel {
+            streamer_id: Set(streamer_id),
+            started_at: Set(started_at),
+            ended_at: Set(None),
+            title: Set(title.to_string()),
+            category: Set(category.to_string()),
+            cover_image: Set(cover_image.to_string()),
+            ..Default::default()
+        };
+        let session = LiveSession::insert(session).exec_with_returning(db).await?;
+        NotificationOutboxRepository::enqueue(
+            db,
+            streamer_id,
+            OutboxEvent::Live,
+            Some(session.id),
+            &[],
+        )
+        .await?;
+        Ok(session)
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<Model>> {
+        LiveSession::find_by_id(id)
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn close_open<C: ConnectionTrait>(
+        db: &C,
+        streamer_id: i32,
+        ended_at: NaiveDateTime,
+    ) -> AppResult<u64> {
+        let result = LiveSession::update_many()
+            .col_expr(Column::EndedAt, Expr::value(ended_at))
+            .filter(Column::StreamerId.eq(streamer_id))
+            .filter(Column::EndedAt.is_null())
+            .exec(db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    pub async fn find_open(&self, streamer_ids: Vec<i32>) -> AppResult<Vec<Model>> {
+        LiveSession::find()
+            .filter(Column::StreamerId.is_in(streamer_ids))
+            .filter(Column::EndedAt.is_null())
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Latest sessions of the streamers the user subscribes to, newest first.
+    pub async fn find_recent_for_user(
+        &self,
+        user_id: i32,
+        limit: u64,
+    ) -> AppResult<Vec<(Model, Option<StreamerModel>)>> {
+        LiveSession::find()
+            .filter(Self::subscribed_by(user_id))
+            .order_by_desc(Column::StartedAt)
+            .limit(limit)
+            .find_also_related(Streamer)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Sessions of the streamers the user subscribes to that started after `since`.
+    pub async fn find_for_user_since(
+        &self,
+        user_id: i32,
+        since: NaiveDateTime,
+    ) -> AppResult<Vec<(Model, Option<StreamerModel>)>> {
+        LiveSession::find()
+            .filter(Self::subscribed_by(user_id))
+            .filter(Column::StartedAt.gte(since))
+            .order_by_asc(Column::StartedAt)
+            .find_also_related(Streamer)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    fn subscribed_by(user_id: i32) -> SimpleExpr {
+        Column::StreamerId.in_subquery(
+            Query::select()
+                .column(entity::subscription::Column::StreamerId)
+                .from(Subscription)
+                .and_where(entity::subscription::Column::UserId.eq(user_id))
+                .to_owned(),
+        )
+    }
+
+    pub async fn find_started_since(
+        &self,
+        streamer_ids: Vec<i32>,
+        since: NaiveDateTime,
+    ) -> AppResult<Vec<Model>> {
+        if streamer_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        LiveSession::find()
+            .filter(Column::StreamerId.is_in(streamer_ids))
+            .filter(Column::StartedAt.gte(since))
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Sessions of the streamers that were running at `at`: started by then and
+    /// not ended before.
+    pub async fn find_running_at(
+        &self,
+        streamer_ids: Vec<i32>,
+        at: NaiveDateTime,
+    ) -> AppResult<Vec<Model>> {
+        LiveSession::find()
+            .filter(Column::StreamerId.is_in(streamer_ids))
+            .filter(Column::StartedAt.lte(at))
+            .filter(
+                Condition::any()
+                    .add(Column::EndedAt.is_null())
+                    .add(Column::EndedAt.gt(at)),
+            )
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Finds the latest session of any of the streamers that started before `before`.
+    pub async fn find_previous(
+        &self,
+        streamer_ids: Vec<i32>,
+        before: NaiveDateTime,
+    ) -> AppResult<Option<Model>> {
+        LiveSession::find()
+            .filter(Column::StreamerId.is_in(streamer_ids))
+            .filter(Column::StartedAt.lt(before))
+            .order_by_desc(Column::StartedAt)
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+}
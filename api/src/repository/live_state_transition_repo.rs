@@ -0,0 +1,55 @@
+use crate::domain::live_state::LiveState;
+use crate::error::{AppResult, IntoAppResult};
+use chrono::NaiveDateTime;
+use entity::live_state_transition::{ActiveModel, Column, Model};
+use entity::prelude::LiveStateTransition;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, PaginatorTrait,
+    QueryFilter, QueryOrder, Set,
+};
+
+pub struct LiveStateTransitionRepository {
+    db: DbConn,
+}
+
+impl LiveStateTransitionRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn record<C: ConnectionTrait>(
+        db: &C,
+        streamer_id: i32,
+        from: LiveState,
+        to: LiveState,
+        created_at: NaiveDateTime,
+    ) -> AppResult<Model> {
+        ActiveModel {
+            streamer_id: Set(streamer_id),
+            from_state: Set(from.as_str().to_string()),
+            to_state: Set(to.as_str().to_string()),
+            created_at: Set(created_at),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .into_app_result()
+    }
+
+    /// Lists the transitions of a streamer, newest first.
+    pub async fn list_for_streamer(
+        &self,
+        streamer_id: i32,
+        page: u64,
+        page_size: u64,
+    ) -> AppResult<(u64, Vec<Model>)> {
+        let paginator = LiveStateTransition::find()
+            .filter(Column::StreamerId.eq(streamer_id))
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .paginate(&self.db, page_size);
+        let total = paginator.num_items().await?;
+        let transitions = paginator.fetch_page(page).await?;
+        Ok((total, transitions))
+    }
+}
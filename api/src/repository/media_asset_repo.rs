@@ -0,0 +1,56 @@
+use crate::domain::media::{self, MediaKind};
+use crate::error::{AppResult, IntoAppResult};
+use chrono::NaiveDateTime;
+use entity::media_asset::{ActiveModel, Column, Model};
+use entity::prelude::MediaAsset;
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ColumnTrait, DbConn, EntityTrait, QueryFilter, Set};
+
+pub struct MediaAssetRepository {
+    db: DbConn,
+}
+
+impl MediaAssetRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    /// Makes the image available through the proxy. Known URLs are left as is.
+    pub async fn register(&self, kind: MediaKind, url: &str) -> AppResult<()> {
+        if url.is_empty() {
+            return Ok(());
+        }
+        MediaAsset::insert(ActiveModel {
+            kind: Set(kind.as_str().to_string()),
+            hash: Set(media::hash(url)),
+            source_url: Set(url.to_string()),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([Column::Kind, Column::Hash])
+                .do_nothing()
+                .to_owned(),
+        )
+        .exec_without_returning(&self.db)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn find(&self, kind: MediaKind, hash: &str) -> AppResult<Option<Model>> {
+        MediaAsset::find()
+            .filter(Column::Kind.eq(kind.as_str()))
+            .filter(Column::Hash.eq(hash))
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn mark_fetched(&self, id: i64, fetched_at: NaiveDateTime) -> AppResult<()> {
+        MediaAsset::update_many()
+            .col_expr(Column::FetchedAt, Expr::value(fetched_at))
+            .filter(Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+}
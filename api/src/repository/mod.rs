@@ -1,18 +1,66 @@
 use sea_orm::DbConn;
 use std::sync::Arc;
 
+mod creator_repo;
+mod live_session_repo;
+mod live_state_transition_repo;
+mod media_asset_repo;
+mod notification_channel_repo;
+mod notification_outbox_repo;
+mod share_page_repo;
+mod streamer_change_repo;
+mod streamer_repo;
+mod subscription_group_repo;
+mod subscription_repo;
 mod user_repo;
+mod viewer_sample_repo;
 
+pub use creator_repo::CreatorRepository;
+pub use live_session_repo::LiveSessionRepository;
+pub use live_state_transition_repo::LiveStateTransitionRepository;
+pub use media_asset_repo::MediaAssetRepository;
+pub use notification_channel_repo::NotificationChannelRepository;
+pub use notification_outbox_repo::NotificationOutboxRepository;
+pub use share_page_repo::SharePageRepository;
+pub use streamer_change_repo::StreamerChangeRepository;
+pub use streamer_repo::StreamerRepository;
+pub use subscription_group_repo::SubscriptionGroupRepository;
+pub use subscription_repo::{ChangeAlerts, SubscriptionRepository};
 pub use user_repo::UserRepository;
+pub use viewer_sample_repo::ViewerSampleRepository;
 
 pub struct Repositories {
     pub user: Arc<UserRepository>,
+    pub streamer: Arc<StreamerRepository>,
+    pub creator: Arc<CreatorRepository>,
+    pub streamer_change: Arc<StreamerChangeRepository>,
+    pub viewer_sample: Arc<ViewerSampleRepository>,
+    pub live_session: Arc<LiveSessionRepository>,
+    pub live_state_transition: Arc<LiveStateTransitionRepository>,
+    pub subscription: Arc<SubscriptionRepository>,
+    pub subscription_group: Arc<SubscriptionGroupRepository>,
+    pub notification_channel: Arc<NotificationChannelRepository>,
+    pub notification_outbox: Arc<NotificationOutboxRepository>,
+    pub share_page: Arc<SharePageRepository>,
+    pub media_asset: Arc<MediaAssetRepository>,
 }
 
 impl Repositories {
     pub fn new(db: DbConn) -> Self {
         Self {
-            user: Arc::new(UserRepository::new(db)),
+            user: Arc::new(UserRepository::new(db.clone())),
+            streamer: Arc::new(StreamerRepository::new(db.clone())),
+            creator: Arc::new(CreatorRepository::new(db.clone())),
+            streamer_change: Arc::new(StreamerChangeRepository::new(db.clone())),
+            viewer_sample: Arc::new(ViewerSampleRepository::new(db.clone())),
+            live_session: Arc::new(LiveSessionRepository::new(db.clone())),
+            live_state_transition: Arc::new(LiveStateTransitionRepository::new(db.clone())),
+            subscription: Arc::new(SubscriptionRepository::new(db.clone())),
+            subscription_group: Arc::new(SubscriptionGroupRepository::new(db.clone())),
+            notification_channel: Arc::new(NotificationChannelRepository::new(db.clone())),
+            notification_outbox: Arc::new(NotificationOutboxRepository::new(db.clone())),
+            share_page: Arc::new(SharePageRepository::new(db.clone())),
+            media_asset: Arc::new(MediaAssetRepository::new(db)),
         }
     }
 }
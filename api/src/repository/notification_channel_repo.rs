@@ -0,0 +1,90 @@
+use crate::error::{AppResult, IntoAppResult};
+use entity::notification_channel::{ActiveModel, Column, Model};
+use entity::prelude::NotificationChannel;
+use sea_orm::{ColumnTrait, DbConn, EntityTrait, JsonValue, QueryFilter, QueryOrder, Set};
+
+pub struct NotificationChannelRepository {
+    db: DbConn,
+}
+
+impl NotificationChannelRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        channel: &str,
+        destination: &str,
+        metadata: JsonValue,
+        enabled: bool,
+    ) -> AppResult<Model> {
+        let notification_channel = ActiveModel {
+            user_id: Set(user_id),
+            channel: Set(channel.to_string()),
+            destination: Set(destination.to_string()),
+            metadata: Set(metadata),
+            enabled: Set(enabled),
+            ..Default::default()
+        };
+        NotificationChannel::insert(notification_channel)
+            .exec_with_returning(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn list_for_user(&self, user_id: i32) -> AppResult<Vec<Model>> {
+        NotificationChannel::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_enabled_for_user(&self, user_id: i32) -> AppResult<Vec<Model>> {
+        NotificationChannel::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Enabled.eq(true))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn delete_for_user(&self, id: i32, user_id: i32) -> AppResult<u64> {
+        let result = NotificationChannel::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    /// Removes every channel of `channel` kind sending to `destination`,
+    /// whichever user owns it.
+    pub async fn delete_destination(&self, channel: &str, destination: &str) -> AppResult<u64> {
+        let result = NotificationChannel::delete_many()
+            .filter(Column::Channel.eq(channel))
+            .filter(Column::Destination.eq(destination))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    pub async fn delete_destination_for_user(
+        &self,
+        user_id: i32,
+        channel: &str,
+        destination: &str,
+    ) -> AppResult<u64> {
+        let result = NotificationChannel::delete_many()
+            .filter(Column::UserId.eq(user_id))
+            .filter(Column::Channel.eq(channel))
+            .filter(Column::Destination.eq(destination))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
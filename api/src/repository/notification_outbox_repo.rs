@@ -0,0 +1,153 @@
+use crate::domain::notification_outbox::{OutboxEvent, OutboxStatus};
+use crate::error::{AppResult, IntoAppResult};
+use chrono::{NaiveDateTime, Utc};
+use entity::notification_outbox::{ActiveModel, Column, Model};
+use entity::prelude::NotificationOutbox;
+use sea_orm::sea_query::{Expr, LockBehavior, LockType, Order, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbConn, EntityTrait, JsonValue, PaginatorTrait,
+    QueryFilter, QueryOrder, Set,
+};
+
+pub struct NotificationOutboxRepository {
+    db: DbConn,
+}
+
+impl NotificationOutboxRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    /// Queues a notification for a detection event. Takes the connection so the
+    /// entry commits in the same transaction as the event it points at.
+    pub async fn enqueue<C: ConnectionTrait>(
+        db: &C,
+        streamer_id: i32,
+        event: OutboxEvent,
+        live_session_id: Option<i32>,
+        change_ids: &[i64],
+    ) -> AppResult<()> {
+        // Set here rather than by the column defaults, which follow the database
+        // time zone while `claim_due` compares in UTC.
+        let now = Utc::now().naive_utc();
+        ActiveModel {
+            streamer_id: Set(streamer_id),
+            event: Set(event.as_str().to_string()),
+            live_session_id: Set(live_session_id),
+            change_ids: Set(serde_json::json!(change_ids)),
+            next_attempt_at: Set(now),
+            created_at: Set(now),
+            updated_at: Set(now),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Takes up to `limit` pending entries that are due and pushes their next
+    /// attempt to `lease_until`, so an entry whose dispatch is cut short by a
+    /// restart is picked up again once the lease ran out. Entries claimed by
+    /// another instance are skipped.
+    pub async fn claim_due(
+        &self,
+        now: NaiveDateTime,
+        limit: u64,
+        lease_until: NaiveDateTime,
+    ) -> AppResult<Vec<Model>> {
+        let due = Query::select()
+            .column(Column::Id)
+            .from(NotificationOutbox)
+            .and_where(Column::Status.eq(OutboxStatus::Pending.as_str()))
+            .and_where(Column::NextAttemptAt.lte(now))
+            .order_by(Column::NextAttemptAt, Order::Asc)
+            .limit(limit)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .to_owned();
+        let mut entries = NotificationOutbox::update_many()
+            .col_expr(Column::NextAttemptAt, Expr::value(lease_until))
+            .filter(Column::Id.in_subquery(due))
+            .exec_with_returning(&self.db)
+            .await?;
+        entries.sort_by_key(|entry| entry.id);
+        Ok(entries)
+    }
+
+    /// Stores the recipients worked out for the entry, ahead of sending to them.
+    pub async fn save_recipients(
+        &self,
+        model: Model,
+        recipients: JsonValue,
+        now: NaiveDateTime,
+    ) -> AppResult<Model> {
+        let mut entry: ActiveModel = model.into();
+        entry.recipients = Set(Some(recipients));
+        entry.updated_at = Set(now);
+        entry.update(&self.db).await.into_app_result()
+    }
+
+    /// Stores the outcome of an attempt and counts it. `reached` tells whether
+    /// the attempt got the notification to anyone.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn finish_attempt(
+        &self,
+        model: Model,
+        status: OutboxStatus,
+        recipients: JsonValue,
+        last_error: Option<String>,
+        reached: bool,
+        next_attempt_at: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> AppResult<Model> {
+        let attempts = model.attempts.saturating_add(1);
+        let first_reached = reached && model.delivered_at.is_none();
+        let mut entry: ActiveModel = model.into();
+        entry.status = Set(status.as_str().to_string());
+        entry.attempts = Set(attempts);
+        entry.recipients = Set(Some(recipients));
+        entry.last_error = Set(last_error);
+        entry.next_attempt_at = Set(next_attempt_at);
+        entry.updated_at = Set(now);
+        if first_reached {
+            entry.delivered_at = Set(Some(now));
+        }
+        entry.update(&self.db).await.into_app_result()
+    }
+
+    /// Makes the entry due again with a fresh set of attempts. Recipients it
+    /// already reached are not notified again.
+    pub async fn replay(&self, model: Model, now: NaiveDateTime) -> AppResult<Model> {
+        let mut entry: ActiveModel = model.into();
+        entry.status = Set(OutboxStatus::Pending.as_str().to_string());
+        entry.attempts = Set(0);
+        entry.next_attempt_at = Set(now);
+        entry.updated_at = Set(now);
+        entry.update(&self.db).await.into_app_result()
+    }
+
+    pub async fn find_by_id(&self, id: i64) -> AppResult<Option<Model>> {
+        NotificationOutbox::find_by_id(id)
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Lists the entries, newest first.
+    pub async fn list(
+        &self,
+        status: Option<OutboxStatus>,
+        page: u64,
+        page_size: u64,
+    ) -> AppResult<(u64, Vec<Model>)> {
+        let mut query = NotificationOutbox::find();
+        if let Some(status) = status {
+            query = query.filter(Column::Status.eq(status.as_str()));
+        }
+        let paginator = query
+            .order_by_desc(Column::Id)
+            .paginate(&self.db, page_size);
+        let total = paginator.num_items().await?;
+        let entries = paginator.fetch_page(page).await?;
+        Ok((total, entries))
+    }
+}
@@ -0,0 +1,78 @@
+use crate::error::{AppResult, IntoAppResult};
+use chrono::{NaiveDateTime, Utc};
+use entity::prelude::SharePage;
+use entity::share_page::{ActiveModel, Column, Model};
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, QueryFilter, QueryOrder, Set};
+
+pub struct SharePageRepository {
+    db: DbConn,
+}
+
+impl SharePageRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        group_id: i32,
+        slug: &str,
+        title: &str,
+        expires_at: Option<NaiveDateTime>,
+    ) -> AppResult<Model> {
+        let page = ActiveModel {
+            user_id: Set(user_id),
+            group_id: Set(group_id),
+            slug: Set(slug.to_string()),
+            title: Set(title.to_string()),
+            expires_at: Set(expires_at),
+            ..Default::default()
+        };
+        SharePage::insert(page)
+            .exec_with_returning(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_for_user(&self, id: i32, user_id: i32) -> AppResult<Option<Model>> {
+        SharePage::find_by_id(id)
+            .filter(Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_by_slug(&self, slug: &str) -> AppResult<Option<Model>> {
+        SharePage::find()
+            .filter(Column::Slug.eq(slug))
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn list_for_user(&self, user_id: i32) -> AppResult<Vec<Model>> {
+        SharePage::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn set_slug(&self, model: Model, slug: &str) -> AppResult<Model> {
+        let mut page: ActiveModel = model.into();
+        page.slug = Set(slug.to_string());
+        page.updated_at = Set(Utc::now().naive_utc());
+        page.update(&self.db).await.into_app_result()
+    }
+
+    pub async fn delete_for_user(&self, id: i32, user_id: i32) -> AppResult<u64> {
+        let result = SharePage::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
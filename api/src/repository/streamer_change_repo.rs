@@ -0,0 +1,138 @@
+use crate::domain::notification_outbox::OutboxEvent;
+use crate::domain::streamer_changes::{ChangedField, FieldChange};
+use crate::error::{AppResult, IntoAppResult};
+use crate::repository::NotificationOutboxRepository;
+use chrono::NaiveDateTime;
+use entity::prelude::{Streamer, StreamerChange, Subscription};
+use entity::streamer::Model as StreamerModel;
+use entity::streamer_change::{ActiveModel, Column, Model};
+use sea_orm::sea_query::Query;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbConn, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait,
+};
+
+pub struct StreamerChangeRepository {
+    db: DbConn,
+}
+
+impl StreamerChangeRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    /// Records the changes and, when any is worth telling subscribers about,
+    /// queues the change notification in the same transaction.
+    pub async fn record(
+        &self,
+        streamer_id: i32,
+        changes: &[FieldChange],
+        while_live: bool,
+        changed_at: NaiveDateTime,
+    ) -> AppResult<Vec<Model>> {
+        let txn = self.db.begin().await?;
+        let changes = Self::insert(&txn, streamer_id, changes, while_live, changed_at).await?;
+        txn.commit().await?;
+        Ok(changes)
+    }
+
+    /// Like [`Self::record`], on the caller's connection.
+    pub async fn insert<C: ConnectionTrait>(
+        db: &C,
+        streamer_id: i32,
+        changes: &[FieldChange],
+        while_live: bool,
+        changed_at: NaiveDateTime,
+    ) -> AppResult<Vec<Model>> {
+        let rows = changes.iter().map(|change| ActiveModel {
+            streamer_id: Set(streamer_id),
+            field: Set(change.field.as_str().to_string()),
+            old_value: Set(change.old_value.clone()),
+            new_value: Set(change.new_value.clone()),
+            while_live: Set(while_live),
+            changed_at: Set(changed_at),
+            ..Default::default()
+        });
+        let changes = StreamerChange::insert_many(rows)
+            .exec_with_returning(db)
+            .await?;
+        let notable: Vec<i64> = changes
+            .iter()
+            .filter(|change| {
+                change
+                    .field
+                    .parse::<ChangedField>()
+                    .is_ok_and(|field| field.is_notable(change.while_live, &change.new_value))
+            })
+            .map(|change| change.id)
+            .collect();
+        if !notable.is_empty() {
+            NotificationOutboxRepository::enqueue(
+                db,
+                streamer_id,
+                OutboxEvent::Change,
+                None,
+                &notable,
+            )
+            .await?;
+        }
+        Ok(changes)
+    }
+
+    pub async fn find_by_ids(&self, ids: Vec<i64>) -> AppResult<Vec<Model>> {
+        StreamerChange::find()
+            .filter(Column::Id.is_in(ids))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Lists the changes of a streamer, newest first.
+    pub async fn list_for_streamer(
+        &self,
+        streamer_id: i32,
+        field: Option<ChangedField>,
+        page: u64,
+        page_size: u64,
+    ) -> AppResult<(u64, Vec<Model>)> {
+        let mut query = StreamerChange::find().filter(Column::StreamerId.eq(streamer_id));
+        if let Some(field) = field {
+            query = query.filter(Column::Field.eq(field.as_str()));
+        }
+        let paginator = query
+            .order_by_desc(Column::ChangedAt)
+            .order_by_desc(Column::Id)
+            .paginate(&self.db, page_size);
+        let total = paginator.num_items().await?;
+        let changes = paginator.fetch_page(page).await?;
+        Ok((total, changes))
+    }
+
+    /// Latest changes of one field across the streamers the user subscribes to,
+    /// newest first.
+    pub async fn find_recent_for_user(
+        &self,
+        user_id: i32,
+        field: ChangedField,
+        limit: u64,
+    ) -> AppResult<Vec<(Model, Option<StreamerModel>)>> {
+        StreamerChange::find()
+            .filter(Column::Field.eq(field.as_str()))
+            .filter(
+                Column::StreamerId.in_subquery(
+                    Query::select()
+                        .column(entity::subscription::Column::StreamerId)
+                        .from(Subscription)
+                        .and_where(entity::subscription::Column::UserId.eq(user_id))
+                        .to_owned(),
+                ),
+            )
+            .order_by_desc(Column::ChangedAt)
+            .limit(limit)
+            .find_also_related(Streamer)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+}
@@ -0,0 +1,278 @@
+use crate::domain::live_state::{LiveEdge, LiveState, LiveStep, LiveTracker};
+use crate::domain::streamer_changes;
+use crate::error::{AppResult, IntoAppResult};
+use crate::repository::{
+    LiveSessionRepository, LiveStateTransitionRepository, StreamerChangeRepository,
+};
+use chrono::{NaiveDateTime, Utc};
+use entity::live_session::Model as LiveSessionModel;
+use entity::prelude::Streamer;
+use entity::streamer::{ActiveModel, Column, Model};
+use entity::streamer_change::Model as StreamerChangeModel;
+use live_platform::{LiveStatus, Platform, StreamerInfo};
+use sea_orm::sea_query::{Expr, NullOrdering};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DbConn, EntityTrait, Order, PaginatorTrait,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait, TryIntoModel,
+};
+
+pub struct StreamerRepository {
+    db: DbConn,
+}
+
+impl StreamerRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<Option<Model>> {
+        Streamer::find_by_id(id)
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_by_room(
+        &self,
+        platform: Platform,
+        room_id: &str,
+    ) -> AppResult<Option<Model>> {
+        Streamer::find_by_platform_room_id((platform.to_string(), room_id.to_string()))
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_all(&self) -> AppResult<Vec<Model>> {
+        Streamer::find()
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Rooms whose next poll is due, never polled ones first, then the most overdue.
+    pub async fn find_due(&self, now: NaiveDateTime) -> AppResult<Vec<Model>> {
+        Streamer::find()
+            .filter(
+                Condition::any()
+                    .add(Column::NextCheckAt.is_null())
+                    .add(Column::NextCheckAt.lte(now)),
+            )
+            .order_by_with_nulls(Column::NextCheckAt, Order::Asc, NullOrdering::First)
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Number of rooms per platform polled since `since`.
+    pub async fn count_checked_since(&self, since: NaiveDateTime) -> AppResult<Vec<(String, i64)>> {
+        Streamer::find()
+            .select_only()
+            .column(Column::Platform)
+            .column_as(Column::Id.count(), "count")
+            .filter(Column::LastCheckedAt.gt(since))
+            .group_by(Column::Platform)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Platform and current polling interval of every room.
+    pub async fn find_poll_intervals(&self) -> AppResult<Vec<(String, Option<i32>)>> {
+        Streamer::find()
+            .select_only()
+            .column(Column::Platform)
+            .column(Column::PollIntervalSecs)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn schedule_check(
+        &self,
+        id: i32,
+        next_check_at: NaiveDateTime,
+        interval_secs: i32,
+    ) -> AppResult<()> {
+        Streamer::update_many()
+            .col_expr(Column::NextCheckAt, Expr::value(next_check_at))
+            .col_expr(Column::PollIntervalSecs, Expr::value(interval_secs))
+            .filter(Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_by_ids(&self, ids: Vec<i32>) -> AppResult<Vec<Model>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Streamer::find()
+            .filter(Column::Id.is_in(ids))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Rooms linked to any of the creators.
+    pub async fn find_by_creators(&self, creator_ids: Vec<i32>) -> AppResult<Vec<Model>> {
+        if creator_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Streamer::find()
+            .filter(Column::CreatorId.is_in(creator_ids))
+            .order_by_asc(Column::Id)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Makes `streamer_ids` the rooms of the creator, unlinking its other rooms.
+    pub async fn link_creator(&self, creator_id: i32, streamer_ids: Vec<i32>) -> AppResult<()> {
+        let txn = self.db.begin().await?;
+        Streamer::update_many()
+            .col_expr(Column::CreatorId, Expr::value(Option::<i32>::None))
+            .filter(Column::CreatorId.eq(creator_id))
+            .filter(Column::Id.is_not_in(streamer_ids.clone()))
+            .exec(&txn)
+            .await?;
+        if !streamer_ids.is_empty() {
+            Streamer::update_many()
+                .col_expr(Column::CreatorId, Expr::value(creator_id))
+                .filter(Column::Id.is_in(streamer_ids))
+                .exec(&txn)
+                .await?;
+        }
+        txn.commit().await.into_app_result()
+    }
+
+    pub async fn list(&self, page: u64, page_size: u64) -> AppResult<(u64, Vec<Model>)> {
+        let paginator = Streamer::find()
+            .order_by_asc(Column::Id)
+            .paginate(&self.db, page_size);
+        let total = paginator.num_items().await?;
+        let streamer_list = paginator.fetch_page(page).await?;
+        Ok((total, streamer_list))
+    }
+
+    /// Inserts the streamer or refreshes the profile of an already known room.
+    pub async fn upsert_info(&self, info: &StreamerInfo) -> AppResult<Model> {
+        let existing = self
+            .find_by_room(info.platform, &info.platform_streamer_id)
+            .await?;
+
+        let mut streamer = match existing {
+            Some(model) => {
+                let mut active: ActiveModel = model.into();
+                active.updated_at = Set(Utc::now().naive_utc());
+                active
+            }
+            None => ActiveModel {
+                platform: Set(info.platform.to_string()),
+                room_id: Set(info.platform_streamer_id.clone()),
+                ..Default::default()
+            },
+        };
+        streamer.name = Set(info.name.clone());
+        streamer.avatar = Set(info.avatar.clone());
+        streamer.description = Set(info.description.clone());
+        streamer.room_url = Set(info.room_url.clone());
+
+        streamer
+            .save(&self.db)
+            .await?
+            .try_into_model()
+            .into_app_result()
+    }
+
+    /// Stores a poll with what it detected, in one transaction: the state
+    /// transition, the session the edge opens or closes, with its notification,
+    /// and the metadata changes. When any of it fails nothing is stored, so the
+    /// next poll sees the same edge again. While an offline change is
+    /// unconfirmed the room keeps the metadata of its last live poll.
+    pub async fn update_live_status(
+        &self,
+        model: Model,
+        status: &LiveStatus,
+        step: &LiveStep,
+        checked_at: NaiveDateTime,
+    ) -> AppResult<(Model, Option<LiveSessionModel>, Vec<StreamerChangeModel>)> {
+        let previous = model.clone();
+        let from = LiveTracker::of(&previous).state;
+        let tracker = &step.tracker;
+        let is_live = tracker.state.is_live();
+        let mut streamer: ActiveModel = model.into();
+        streamer.is_live = Set(is_live);
+        if tracker.state != LiveState::MaybeOffline {
+            streamer.title = Set(status.title.clone());
+            streamer.category = Set(status.game_name.clone());
+            streamer.cover_image = Set(status.cover_image.clone());
+            streamer.viewer_count = Set(i64::try_from(status.viewer_count).unwrap_or(i64::MAX));
+            streamer.live_started_at = Set(status.start_time.filter(|_| is_live));
+        }
+        streamer.live_state = Set(tracker.state.as_str().to_string());
+        streamer.live_state_since = Set(tracker.since);
+        streamer.live_state_polls = Set(i32::try_from(tracker.polls).unwrap_or(i32::MAX));
+        streamer.last_checked_at = Set(Some(checked_at));
+        streamer.updated_at = Set(checked_at);
+
+        let txn = self.db.begin().await?;
+        let streamer = streamer.update(&txn).await?;
+        if tracker.state != from {
+            LiveStateTransitionRepository::record(
+                &txn,
+                streamer.id,
+                from,
+                tracker.state,
+                checked_at,
+            )
+            .await?;
+        }
+        let session = match step.edge {
+            Some(LiveEdge::WentLive { since }) => Some(
+                LiveSessionRepository::open(
+                    &txn,
+                    streamer.id,
+                    streamer.live_started_at.unwrap_or(since),
+                    &streamer.title,
+                    &streamer.category,
+                    &streamer.cover_image,
+                )
+                .await?,
+            ),
+            Some(LiveEdge::WentOffline { since }) => {
+                LiveSessionRepository::close_open(&txn, streamer.id, since).await?;
+                None
+            }
+            None => None,
+        };
+        let changes = streamer_changes::diff(&previous, &streamer);
+        let changes = if changes.is_empty() {
+            Vec::new()
+        } else {
+            StreamerChangeRepository::insert(
+                &txn,
+                streamer.id,
+                &changes,
+                previous.is_live && streamer.is_live,
+                checked_at,
+            )
+            .await?
+        };
+        txn.commit().await?;
+        Ok((streamer, session, changes))
+    }
+
+    pub async fn set_announcement(&self, model: Model, announcement: String) -> AppResult<Model> {
+        let mut streamer: ActiveModel = model.into();
+        streamer.announcement = Set(Some(announcement));
+        streamer.updated_at = Set(Utc::now().naive_utc());
+
+        streamer.update(&self.db).await.into_app_result()
+    }
+}
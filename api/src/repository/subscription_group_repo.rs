@@ -0,0 +1,84 @@
+use crate::error::{AppResult, IntoAppResult};
+use chrono::Utc;
+use entity::prelude::SubscriptionGroup;
+use entity::subscription_group::{ActiveModel, Column, Model};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, JsonValue, QueryFilter, QueryOrder, Set,
+};
+
+pub struct SubscriptionGroupRepository {
+    db: DbConn,
+}
+
+impl SubscriptionGroupRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(&self, user_id: i32, name: &str, rules: JsonValue) -> AppResult<Model> {
+        let group = ActiveModel {
+            user_id: Set(user_id),
+            name: Set(name.to_string()),
+            rules: Set(rules),
+            ..Default::default()
+        };
+        SubscriptionGroup::insert(group)
+            .exec_with_returning(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_for_user(&self, id: i32, user_id: i32) -> AppResult<Option<Model>> {
+        SubscriptionGroup::find_by_id(id)
+            .filter(Column::UserId.eq(user_id))
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn list_for_user(&self, user_id: i32) -> AppResult<Vec<Model>> {
+        SubscriptionGroup::find()
+            .filter(Column::UserId.eq(user_id))
+            .order_by_asc(Column::Name)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_by_ids(&self, ids: Vec<i32>) -> AppResult<Vec<Model>> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        SubscriptionGroup::find()
+            .filter(Column::Id.is_in(ids))
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn update(
+        &self,
+        model: Model,
+        name: Option<&str>,
+        rules: Option<JsonValue>,
+    ) -> AppResult<Model> {
+        let mut group: ActiveModel = model.into();
+        if let Some(name) = name {
+            group.name = Set(name.to_string());
+        }
+        if let Some(rules) = rules {
+            group.rules = Set(rules);
+        }
+        group.updated_at = Set(Utc::now().naive_utc());
+        group.update(&self.db).await.into_app_result()
+    }
+
+    pub async fn delete_for_user(&self, id: i32, user_id: i32) -> AppResult<u64> {
+        let result = SubscriptionGroup::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+}
@@ -0,0 +1,306 @@
+use crate::dto::SubscriptionFilter;
+use crate::error::{AppResult, IntoAppResult};
+use chrono::{NaiveDateTime, Utc};
+use entity::prelude::{Streamer, Subscription, SubscriptionGroup, SubscriptionTag};
+use entity::streamer::{Column as StreamerColumn, Model as StreamerModel};
+use entity::subscription::{ActiveModel, Column, Model};
+use entity::subscription_group::Model as SubscriptionGroupModel;
+use entity::subscription_tag::{
+    ActiveModel as SubscriptionTagActiveModel, Column as SubscriptionTagColumn,
+    Model as SubscriptionTagModel,
+};
+use sea_orm::sea_query::{Expr, Query};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, JsonValue, PaginatorTrait, QueryFilter,
+    QueryOrder, QuerySelect, Select, Set, TransactionTrait,
+};
+
+pub struct SubscriptionRepository {
+    db: DbConn,
+}
+
+/// Change notifications a new subscription opts into.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChangeAlerts {
+    pub title: bool,
+    pub category: bool,
+    pub announcement: bool,
+}
+
+impl SubscriptionRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        streamer_id: i32,
+        rules: JsonValue,
+        alerts: ChangeAlerts,
+        group_id: Option<i32>,
+    ) -> AppResult<Model> {
+        let subscription = ActiveModel {
+            user_id: Set(user_id),
+            streamer_id: Set(streamer_id),
+            group_id: Set(group_id),
+            rules: Set(rules),
+            notify_title_change: Set(alerts.title),
+            notify_category_change: Set(alerts.category),
+            notify_announcement_change: Set(alerts.announcement),
+            ..Default::default()
+        };
+        Subscription::insert(subscription)
+            .exec_with_returning(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_for_user(
+        &self,
+        id: i32,
+        user_id: i32,
+    ) -> AppResult<Option<(Model, Option<StreamerModel>)>> {
+        Subscription::find_by_id(id)
+            .filter(Column::UserId.eq(user_id))
+            .find_also_related(Streamer)
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn list_for_user(
+        &self,
+        user_id: i32,
+        filter: &SubscriptionFilter,
+        page: u64,
+        page_size: u64,
+    ) -> AppResult<(u64, Vec<(Model, Option<StreamerModel>)>)> {
+        let paginator = Self::find_filtered(user_id, filter)
+            .order_by_asc(Column::Id)
+            .find_also_related(Streamer)
+            .paginate(&self.db, page_size);
+        let total = paginator.num_items().await?;
+        let subscriptions = paginator.fetch_page(page).await?;
+        Ok((total, subscriptions))
+    }
+
+    pub async fn find_all_for_user(
+        &self,
+        user_id: i32,
+        filter: &SubscriptionFilter,
+    ) -> AppResult<Vec<(Model, Option<StreamerModel>)>> {
+        Self::find_filtered(user_id, filter)
+            .order_by_asc(Column::Id)
+            .find_also_related(Streamer)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Subscriptions of the streamers together with their group, if any.
+    pub async fn find_by_streamers(
+        &self,
+        streamer_ids: Vec<i32>,
+    ) -> AppResult<Vec<(Model, Option<SubscriptionGroupModel>)>> {
+        Subscription::find()
+            .filter(Column::StreamerId.is_in(streamer_ids))
+            .order_by_asc(Column::Id)
+            .find_also_related(SubscriptionGroup)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn count_by_streamers(&self, streamer_ids: Vec<i32>) -> AppResult<Vec<(i32, i64)>> {
+        if streamer_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        Subscription::find()
+            .select_only()
+            .column(Column::StreamerId)
+            .column_as(Column::Id.count(), "count")
+            .filter(Column::StreamerId.is_in(streamer_ids))
+            .group_by(Column::StreamerId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Subscription of the user to any room of the creator.
+    pub async fn find_for_user_by_creator(
+        &self,
+        user_id: i32,
+        creator_id: i32,
+    ) -> AppResult<Option<Model>> {
+        Subscription::find()
+            .filter(Column::UserId.eq(user_id))
+            .filter(
+                Column::StreamerId.in_subquery(
+                    Query::select()
+                        .column(StreamerColumn::Id)
+                        .from(Streamer)
+                        .and_where(StreamerColumn::CreatorId.eq(creator_id))
+                        .to_owned(),
+                ),
+            )
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_user_ids_by_streamer(&self, streamer_id: i32) -> AppResult<Vec<i32>> {
+        Subscription::find()
+            .select_only()
+            .column(Column::UserId)
+            .filter(Column::StreamerId.eq(streamer_id))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn update_rules(&self, model: Model, rules: JsonValue) -> AppResult<Model> {
+        let mut subscription: ActiveModel = model.into();
+        subscription.rules = Set(rules);
+        subscription.updated_at = Set(Utc::now().naive_utc());
+        subscription.update(&self.db).await.into_app_result()
+    }
+
+    pub async fn update_change_alerts(
+        &self,
+        model: Model,
+        notify_title_change: Option<bool>,
+        notify_category_change: Option<bool>,
+        notify_announcement_change: Option<bool>,
+    ) -> AppResult<Model> {
+        let mut subscription: ActiveModel = model.into();
+        if let Some(enabled) = notify_title_change {
+            subscription.notify_title_change = Set(enabled);
+        }
+        if let Some(enabled) = notify_category_change {
+            subscription.notify_category_change = Set(enabled);
+        }
+        if let Some(enabled) = notify_announcement_change {
+            subscription.notify_announcement_change = Set(enabled);
+        }
+        subscription.updated_at = Set(Utc::now().naive_utc());
+        subscription.update(&self.db).await.into_app_result()
+    }
+
+    pub async fn update_group(&self, model: Model, group_id: Option<i32>) -> AppResult<Model> {
+        let mut subscription: ActiveModel = model.into();
+        subscription.group_id = Set(group_id);
+        subscription.updated_at = Set(Utc::now().naive_utc());
+        subscription.update(&self.db).await.into_app_result()
+    }
+
+    /// Number of subscriptions of the user per group; ungrouped ones count under `None`.
+    pub async fn count_by_group(&self, user_id: i32) -> AppResult<Vec<(Option<i32>, i64)>> {
+        Subscription::find()
+            .select_only()
+            .column(Column::GroupId)
+            .column_as(Column::Id.count(), "count")
+            .filter(Column::UserId.eq(user_id))
+            .group_by(Column::GroupId)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_tags(
+        &self,
+        subscription_ids: Vec<i32>,
+    ) -> AppResult<Vec<SubscriptionTagModel>> {
+        if subscription_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        SubscriptionTag::find()
+            .filter(SubscriptionTagColumn::SubscriptionId.is_in(subscription_ids))
+            .order_by_asc(SubscriptionTagColumn::Tag)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn replace_tags(&self, subscription_id: i32, tags: &[String]) -> AppResult<()> {
+        let txn = self.db.begin().await?;
+
+        SubscriptionTag::delete_many()
+            .filter(SubscriptionTagColumn::SubscriptionId.eq(subscription_id))
+            .exec(&txn)
+            .await?;
+        if !tags.is_empty() {
+            SubscriptionTag::insert_many(tags.iter().map(|tag| SubscriptionTagActiveModel {
+                subscription_id: Set(subscription_id),
+                tag: Set(tag.clone()),
+            }))
+            .exec(&txn)
+            .await?;
+        }
+
+        txn.commit().await.into_app_result()
+    }
+
+    /// Tags used by the user with the number of subscriptions carrying each.
+    pub async fn count_tags(&self, user_id: i32) -> AppResult<Vec<(String, i64)>> {
+        SubscriptionTag::find()
+            .select_only()
+            .column(SubscriptionTagColumn::Tag)
+            .column_as(SubscriptionTagColumn::SubscriptionId.count(), "count")
+            .filter(SubscriptionTagColumn::SubscriptionId.in_subquery(Self::owned_by(user_id)))
+            .group_by(SubscriptionTagColumn::Tag)
+            .order_by_asc(SubscriptionTagColumn::Tag)
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn mark_notified(&self, id: i32, notified_at: NaiveDateTime) -> AppResult<()> {
+        Subscription::update_many()
+            .col_expr(Column::LastNotifiedAt, Expr::value(notified_at))
+            .filter(Column::Id.eq(id))
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn delete_for_user(&self, id: i32, user_id: i32) -> AppResult<u64> {
+        let result = Subscription::delete_many()
+            .filter(Column::Id.eq(id))
+            .filter(Column::UserId.eq(user_id))
+            .exec(&self.db)
+            .await?;
+        Ok(result.rows_affected)
+    }
+
+    fn find_filtered(user_id: i32, filter: &SubscriptionFilter) -> Select<Subscription> {
+        let mut select = Subscription::find().filter(Column::UserId.eq(user_id));
+        if let Some(group_id) = filter.group_id {
+            select = select.filter(Column::GroupId.eq(group_id));
+        }
+        if let Some(tag) = filter.tag.as_deref() {
+            select = select.filter(
+                Column::Id.in_subquery(
+                    Query::select()
+                        .column(SubscriptionTagColumn::SubscriptionId)
+                        .from(SubscriptionTag)
+                        .and_where(SubscriptionTagColumn::Tag.eq(tag.trim().to_lowercase()))
+                        .to_owned(),
+                ),
+            );
+        }
+        select
+    }
+
+    fn owned_by(user_id: i32) -> sea_orm::sea_query::SelectStatement {
+        Query::select()
+            .column(Column::Id)
+            .from(Subscription)
+            .and_where(Column::UserId.eq(user_id))
+            .to_owned()
+    }
+}
@@ -1,8 +1,11 @@
 use crate::dto::CreateUserDto;
 use crate::error::{AppResult, IntoAppResult};
+use chrono::Utc;
 use entity::prelude::User;
-use entity::user::{ActiveModel, Model};
-use sea_orm::{DbConn, EntityTrait, PaginatorTrait, Set};
+use entity::user::{ActiveModel, Column, Model};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DbConn, EntityTrait, PaginatorTrait, QueryFilter, Set,
+};
 
 pub struct UserRepository {
     db: DbConn,
@@ -19,6 +22,7 @@ impl UserRepository {
             username: Set(data.username.clone()),
             email: Set(data.email.clone()),
             password: Set(password_hash.to_string()),
+            feed_token: Default::default(),
             created_at: Default::default(),
             updated_at: Default::default(),
         };
@@ -46,6 +50,21 @@ impl UserRepository {
             .into_app_result()
     }
 
+    pub async fn find_by_feed_token(&self, token: &str) -> AppResult<Option<Model>> {
+        User::find()
+            .filter(Column::FeedToken.eq(token))
+            .one(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn set_feed_token(&self, model: Model, token: &str) -> AppResult<Model> {
+        let mut user: ActiveModel = model.into();
+        user.feed_token = Set(Some(token.to_string()));
+        user.updated_at = Set(Utc::now().naive_utc());
+        user.update(&self.db).await.into_app_result()
+    }
+
     pub async fn list(&self, page: u64, page_size: u64) -> AppResult<(u64, Vec<Model>)> {
         let paginator = User::find().paginate(&self.db, page_size);
         let total = paginator.num_items().await?;
@@ -0,0 +1,169 @@
+use crate::domain::viewer_series::ViewerBucket;
+use crate::error::{AppResult, IntoAppResult};
+use chrono::NaiveDateTime;
+use entity::prelude::ViewerSample;
+use entity::viewer_sample::{ActiveModel, Column, Model};
+use sea_orm::sea_query::{Alias, Expr, ExprTrait, Func, OnConflict};
+use sea_orm::{
+    ColumnTrait, DbConn, EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+
+pub struct ViewerSampleRepository {
+    db: DbConn,
+}
+
+impl ViewerSampleRepository {
+    pub fn new(db: DbConn) -> Self {
+        Self { db }
+    }
+
+    /// Merges the bucket into the stored row of the same streamer, resolution and start.
+    pub async fn record(
+        &self,
+        streamer_id: i32,
+        resolution_secs: i32,
+        bucket: &ViewerBucket,
+    ) -> AppResult<()> {
+        ViewerSample::insert(Self::active_model(streamer_id, resolution_secs, bucket))
+            .on_conflict(Self::merge_on_conflict())
+            .exec(&self.db)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn find_range(
+        &self,
+        streamer_id: i32,
+        from: NaiveDateTime,
+        to: NaiveDateTime,
+    ) -> AppResult<Vec<Model>> {
+        ViewerSample::find()
+            .filter(Column::StreamerId.eq(streamer_id))
+            .filter(Column::BucketStart.gte(from))
+            .filter(Column::BucketStart.lt(to))
+            .order_by_asc(Column::BucketStart)
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_streamers_with_samples_before(
+        &self,
+        resolution_secs: i32,
+        cutoff: NaiveDateTime,
+    ) -> AppResult<Vec<i32>> {
+        ViewerSample::find()
+            .select_only()
+            .column(Column::StreamerId)
+            .distinct()
+            .filter(Column::ResolutionSecs.eq(resolution_secs))
+            .filter(Column::BucketStart.lt(cutoff))
+            .into_tuple()
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    pub async fn find_before(
+        &self,
+        streamer_id: i32,
+        resolution_secs: i32,
+        cutoff: NaiveDateTime,
+    ) -> AppResult<Vec<Model>> {
+        ViewerSample::find()
+            .filter(Column::StreamerId.eq(streamer_id))
+            .filter(Column::ResolutionSecs.eq(resolution_secs))
+            .filter(Column::BucketStart.lt(cutoff))
+            .all(&self.db)
+            .await
+            .into_app_result()
+    }
+
+    /// Replaces a streamer's samples older than `cutoff` at `from_resolution_secs`
+    /// with the given coarser buckets, atomically.
+    pub async fn replace_with_downsampled(
+        &self,
+        streamer_id: i32,
+        from_resolution_secs: i32,
+        cutoff: NaiveDateTime,
+        to_resolution_secs: i32,
+        buckets: &[ViewerBucket],
+    ) -> AppResult<()> {
+        let txn = self.db.begin().await?;
+
+        for bucket in buckets {
+            ViewerSample::insert(Self::active_model(streamer_id, to_resolution_secs, bucket))
+                .on_conflict(Self::merge_on_conflict())
+                .exec(&txn)
+                .await?;
+        }
+
+        ViewerSample::delete_many()
+            .filter(Column::StreamerId.eq(streamer_id))
+            .filter(Column::ResolutionSecs.eq(from_resolution_secs))
+            .filter(Column::BucketStart.lt(cutoff))
+            .exec(&txn)
+            .await?;
+
+        txn.commit().await.into_app_result()
+    }
+
+    fn active_model(streamer_id: i32, resolution_secs: i32, bucket: &ViewerBucket) -> ActiveModel {
+        ActiveModel {
+            streamer_id: Set(streamer_id),
+            bucket_start: Set(bucket.start),
+            resolution_secs: Set(resolution_secs),
+            sample_count: Set(i32::try_from(bucket.sample_count).unwrap_or(i32::MAX)),
+            min_viewers: Set(bucket.min),
+            max_viewers: Set(bucket.max),
+            sum_viewers: Set(bucket.sum),
+            ..Default::default()
+        }
+    }
+
+    fn merge_on_conflict() -> OnConflict {
+        let excluded = Alias::new("excluded");
+        OnConflict::columns([
+            Column::StreamerId,
+            Column::ResolutionSecs,
+            Column::BucketStart,
+        ])
+        .value(
+            Column::SampleCount,
+            Expr::col((ViewerSample, Column::SampleCount))
+                .add(Expr::col((excluded.clone(), Column::SampleCount))),
+        )
+        .value(
+            Column::MinViewers,
+            Func::least([
+                Expr::col((ViewerSample, Column::MinViewers)),
+                Expr::col((excluded.clone(), Column::MinViewers)),
+            ]),
+        )
+        .value(
+            Column::MaxViewers,
+            Func::greatest([
+                Expr::col((ViewerSample, Column::MaxViewers)),
+                Expr::col((excluded.clone(), Column::MaxViewers)),
+            ]),
+        )
+        .value(
+            Column::SumViewers,
+            Expr::col((ViewerSample, Column::SumViewers))
+                .add(Expr::col((excluded, Column::SumViewers))),
+        )
+        .to_owned()
+    }
+}
+
+impl From<&Model> for ViewerBucket {
+    fn from(value: &Model) -> Self {
+        Self {
+            start: value.bucket_start,
+            sample_count: i64::from(value.sample_count),
+            min: value.min_viewers,
+            max: value.max_viewers,
+            sum: value.sum_viewers,
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use crate::config::CalendarConfig;
+use crate::domain::calendar::{Calendar, CalendarEvent};
+use crate::domain::schedule::{self, PastSession, PredictionSettings};
+use crate::error::{AppError, AppResult, Entity};
+use crate::repository::{LiveSessionRepository, UserRepository};
+use chrono::{Duration, Utc};
+use chrono_tz::Tz;
+use entity::streamer::Model as StreamerModel;
+use entity::user::Model as UserModel;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Exports past and predicted live sessions of a user's subscriptions as iCalendar.
+pub struct CalendarService {
+    user_repo: Arc<UserRepository>,
+    live_session_repo: Arc<LiveSessionRepository>,
+    config: CalendarConfig,
+    tz: Tz,
+    app_name: String,
+}
+
+impl CalendarService {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        live_session_repo: Arc<LiveSessionRepository>,
+        config: CalendarConfig,
+        app_name: String,
+    ) -> anyhow::Result<Self> {
+        Ok(Self {
+            user_repo,
+            live_session_repo,
+            tz: config.tz()?,
+            config,
+            app_name,
+        })
+    }
+
+    pub async fn render(&self, token: &str, min_confidence: Option<f64>) -> AppResult<String> {
+        let min_confidence = min_confidence.unwrap_or(self.config.min_confidence);
+        if !(0.0..=1.0).contains(&min_confidence) {
+            return Err(AppError::BadRequest(
+                "min_confidence must be between 0 and 1".to_string(),
+            ));
+        }
+
+        let user = self
+            .user_repo
+            .find_by_feed_token(token)
+            .await?
+            .ok_or_else(|| UserModel::not_found_by("feed_token", token))?;
+
+        let now = Utc::now().naive_utc();
+        let history_start = now - Duration::days(i64::from(self.config.history_days));
+        let lookback_start = now - Duration::weeks(i64::from(self.config.lookback_weeks));
+        let sessions = self
+            .live_session_repo
+            .find_for_user_since(user.id, history_start.min(lookback_start))
+            .await?;
+
+        let mut events = Vec::new();
+        let mut by_streamer: BTreeMap<i32, (StreamerModel, Vec<PastSession>)> = BTreeMap::new();
+        for (session, streamer) in sessions {
+            let Some(streamer) = streamer else { continue };
+
+            if session.started_at >= history_start {
+                let ends_at = session
+                    .ended_at
+                    .unwrap_or(now)
+                    .max(session.started_at + Duration::minutes(1));
+                events.push(CalendarEvent {
+                    uid: format!("live-session-{}@fusion", session.id),
+                    summary: if session.title.is_empty() {
+                        streamer.name.clone()
+                    } else {
+                        format!("{}: {}", streamer.name, session.title)
+                    },
+                    description: session.category.clone(),
+                    url: streamer.room_url.clone(),
+                    starts_at: session.started_at,
+                    ends_at,
+                    tentative: false,
+                });
+            }
+
+            if session.started_at >= lookback_start {
+                by_streamer
+                    .entry(streamer.id)
+                    .or_insert_with(|| (streamer, Vec::new()))
+                    .1
+                    .push(PastSession {
+                        started_at: session.started_at,
+                        ended_at: session.ended_at,
+                    });
+            }
+        }
+
+        let settings = PredictionSettings {
+            lookback_weeks: self.config.lookback_weeks,
+            horizon_days: self.config.horizon_days,
+            min_confidence,
+            min_sessions: self.config.min_sessions,
+            tolerance_minutes: self.config.tolerance_minutes,
+            default_duration: Duration::hours(2),
+        };
+        for (streamer, past) in by_streamer.values() {
+            for prediction in schedule::predict(past, self.tz, now, &settings) {
+                events.push(CalendarEvent {
+                    uid: format!(
+                        "predicted-{}-{}@fusion",
+                        streamer.id,
+                        prediction.starts_at.format("%Y%m%dT%H%M")
+                    ),
+                    summary: format!("{} (predicted)", streamer.name),
+                    description: format!(
+                        "Usually live around this time (confidence {:.0}%)",
+                        prediction.confidence * 100.0
+                    ),
+                    url: streamer.room_url.clone(),
+                    starts_at: prediction.starts_at,
+                    ends_at: prediction.ends_at,
+                    tentative: true,
+                });
+            }
+        }
+        events.sort_by_key(|event| event.starts_at);
+
+        let calendar = Calendar {
+            name: format!("{} · {}", self.app_name, user.username),
+            description: format!("Live sessions followed by {}", user.username),
+            generated_at: now,
+            events,
+        };
+        Ok(calendar.to_ics())
+    }
+}
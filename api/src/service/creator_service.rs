@@ -0,0 +1,143 @@
+use crate::dto::{CreateCreatorRequest, CreatorDto, PagedResponse, UpdateCreatorRequest};
+use crate::error::{AppError, AppResult, Entity};
+use crate::repository::{CreatorRepository, StreamerRepository};
+use entity::creator::Model;
+use entity::streamer::Model as StreamerModel;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+/// Manages creators, the people behind one or more rooms on different platforms.
+pub struct CreatorService {
+    repo: Arc<CreatorRepository>,
+    streamer_repo: Arc<StreamerRepository>,
+}
+
+impl CreatorService {
+    pub fn new(repo: Arc<CreatorRepository>, streamer_repo: Arc<StreamerRepository>) -> Self {
+        Self {
+            repo,
+            streamer_repo,
+        }
+    }
+
+    pub async fn create(&self, data: CreateCreatorRequest) -> AppResult<CreatorDto> {
+        let name = Self::name(&data.name)?;
+        let streamer_ids = self.check_rooms(None, &data.streamer_ids).await?;
+        let creator = self.repo.create(name).await?;
+        self.streamer_repo
+            .link_creator(creator.id, streamer_ids)
+            .await?;
+        self.to_dto(creator).await
+    }
+
+    pub async fn list(
+        &self,
+        page: u64,
+        page_size: NonZeroU64,
+    ) -> AppResult<PagedResponse<CreatorDto>> {
+        let (total, creators) = self.repo.list(page - 1, page_size.get()).await?;
+        let mut streamers: HashMap<i32, Vec<StreamerModel>> = HashMap::new();
+        for streamer in self
+            .streamer_repo
+            .find_by_creators(creators.iter().map(|creator| creator.id).collect())
+            .await?
+        {
+            if let Some(creator_id) = streamer.creator_id {
+                streamers.entry(creator_id).or_default().push(streamer);
+            }
+        }
+
+        let items = creators
+            .into_iter()
+            .map(|creator| {
+                let linked = streamers.remove(&creator.id).unwrap_or_default();
+                CreatorDto::new(creator, linked)
+            })
+            .collect();
+        Ok(PagedResponse::new(items, total, page, page_size))
+    }
+
+    pub async fn find(&self, id: i32) -> AppResult<CreatorDto> {
+        let creator = self.find_by_id(id).await?;
+        self.to_dto(creator).await
+    }
+
+    pub async fn update(&self, id: i32, data: UpdateCreatorRequest) -> AppResult<CreatorDto> {
+        let name = data.name.as_deref().map(Self::name).transpose()?;
+        let mut creator = self.find_by_id(id).await?;
+        if let Some(streamer_ids) = &data.streamer_ids {
+            let streamer_ids = self.check_rooms(Some(id), streamer_ids).await?;
+            self.streamer_repo.link_creator(id, streamer_ids).await?;
+        }
+        if let Some(name) = name {
+            creator = self.repo.rename(creator, name).await?;
+        }
+        self.to_dto(creator).await
+    }
+
+    /// Deletes the creator; its rooms are kept and become unlinked.
+    pub async fn delete(&self, id: i32) -> AppResult<()> {
+        match self.repo.delete(id).await? {
+            0 => Err(Model::not_found_by("id", id)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn find_by_id(&self, id: i32) -> AppResult<Model> {
+        self.repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| Model::not_found_by("id", id))
+    }
+
+    async fn to_dto(&self, creator: Model) -> AppResult<CreatorDto> {
+        let streamers = self
+            .streamer_repo
+            .find_by_creators(vec![creator.id])
+            .await?;
+        Ok(CreatorDto::new(creator, streamers))
+    }
+
+    /// Checks that the rooms exist, are on different platforms and are not
+    /// linked to another creator.
+    async fn check_rooms(&self, creator_id: Option<i32>, ids: &[i32]) -> AppResult<Vec<i32>> {
+        let mut seen = HashSet::new();
+        let ids: Vec<i32> = ids.iter().copied().filter(|id| seen.insert(*id)).collect();
+        let streamers = self.streamer_repo.find_by_ids(ids.clone()).await?;
+        if let Some(missing) = ids
+            .iter()
+            .find(|id| !streamers.iter().any(|streamer| streamer.id == **id))
+        {
+            return Err(StreamerModel::not_found_by("id", missing));
+        }
+
+        let mut platforms = HashSet::new();
+        for streamer in &streamers {
+            if !platforms.insert(streamer.platform.as_str()) {
+                return Err(AppError::BadRequest(format!(
+                    "A creator can have only one {} room",
+                    streamer.platform
+                )));
+            }
+            if let Some(linked) = streamer.creator_id
+                && Some(linked) != creator_id
+            {
+                return Err(AppError::BadRequest(format!(
+                    "Streamer {} is already linked to creator {linked}",
+                    streamer.id
+                )));
+            }
+        }
+        Ok(ids)
+    }
+
+    fn name(name: &str) -> AppResult<&str> {
+        match name.trim() {
+            "" => Err(AppError::BadRequest(
+                "Creator name must not be blank".to_string(),
+            )),
+            name => Ok(name),
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::config::FeedsConfig;
+use crate::domain::feed::{Feed, FeedEntry, FeedFormat};
+use crate::domain::media::{self, MediaKind};
+use crate::domain::streamer_changes::ChangedField;
+use crate::dto::FeedTokenDto;
+use crate::error::{AppResult, Entity};
+use crate::repository::{LiveSessionRepository, StreamerChangeRepository, UserRepository};
+use chrono::Utc;
+use entity::user::Model as UserModel;
+use std::cmp::Reverse;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Renders the per-user Atom and RSS feeds of go-live and title change events.
+pub struct FeedService {
+    user_repo: Arc<UserRepository>,
+    live_session_repo: Arc<LiveSessionRepository>,
+    change_repo: Arc<StreamerChangeRepository>,
+    config: FeedsConfig,
+    app_name: String,
+}
+
+impl FeedService {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        live_session_repo: Arc<LiveSessionRepository>,
+        change_repo: Arc<StreamerChangeRepository>,
+        config: FeedsConfig,
+        app_name: String,
+    ) -> Self {
+        Self {
+            user_repo,
+            live_session_repo,
+            change_repo,
+            config,
+            app_name,
+        }
+    }
+
+    /// Returns the user's feed token, creating one on first use.
+    pub async fn token(&self, user_id: i32, base_url: &str) -> AppResult<FeedTokenDto> {
+        let user = self.find_user(user_id).await?;
+        let token = match user.feed_token {
+            Some(token) => token,
+            None => self.assign_token(user).await?,
+        };
+        Ok(FeedTokenDto::new(token, base_url))
+    }
+
+    /// Replaces the user's feed token, invalidating previously shared feed URLs.
+    pub async fn rotate_token(&self, user_id: i32, base_url: &str) -> AppResult<FeedTokenDto> {
+        let user = self.find_user(user_id).await?;
+        let token = self.assign_token(user).await?;
+        Ok(FeedTokenDto::new(token, base_url))
+    }
+
+    pub async fn render(
+        &self,
+        token: &str,
+        format: FeedFormat,
+        base_url: &str,
+    ) -> AppResult<String> {
+        let user = self
+            .user_repo
+            .find_by_feed_token(token)
+            .await?
+            .ok_or_else(|| UserModel::not_found_by("feed_token", token))?;
+
+        let limit = self.config.max_entries;
+        let sessions = self
+            .live_session_repo
+            .find_recent_for_user(user.id, limit)
+            .await?;
+        let changes = self
+            .change_repo
+            .find_recent_for_user(user.id, ChangedField::Title, limit)
+            .await?;
+
+        // Feed readers fetch images from elsewhere, and platforms refuse
+        // hot-linked ones, so entries link the absolute proxied copy.
+        let cover = |url: &str| {
+            media::proxy_path(MediaKind::Cover, url).map(|path| format!("{base_url}{path}"))
+        };
+        let live_entries = sessions.into_iter().filter_map(|(session, streamer)| {
+            let streamer = streamer?;
+            let image = [session.cover_image.as_str(), streamer.cover_image.as_str()]
+                .into_iter()
+                .find_map(cover);
+            Some(FeedEntry {
+                id: format!("urn:fusion:live-session:{}", session.id),
+                title: format!("{} is live", streamer.name),
+                author: streamer.name,
+                link: streamer.room_url,
+                summary: vec![session.title, session.category],
+                image,
+                published: session.started_at,
+            })
+        });
+        let change_entries = changes.into_iter().filter_map(|(change, streamer)| {
+            let streamer = streamer?;
+            Some(FeedEntry {
+                id: format!("urn:fusion:streamer-change:{}", change.id),
+                title: format!("{} changed the title", streamer.name),
+                author: streamer.name,
+                link: streamer.room_url,
+                summary: vec![
+                    change.new_value,
+                    format!("Previously: {}", change.old_value),
+                ],
+                image: cover(&streamer.cover_image),
+                published: change.changed_at,
+            })
+        });
+
+        let mut entries: Vec<FeedEntry> = live_entries.chain(change_entries).collect();
+        entries.sort_by_key(|entry| Reverse(entry.published));
+        entries.truncate(limit as usize);
+
+        let feed = Feed {
+            title: format!("{} · {}", self.app_name, user.username),
+            description: format!("Live and title change events followed by {}", user.username),
+            self_url: format!("{base_url}/feeds/{token}.{}", format.extension()),
+            site_url: base_url.to_string(),
+            updated: entries
+                .first()
+                .map(|entry| entry.published)
+                .unwrap_or_else(|| Utc::now().naive_utc()),
+            entries,
+        };
+        Ok(feed.render(format))
+    }
+
+    async fn find_user(&self, user_id: i32) -> AppResult<UserModel> {
+        self.user_repo
+            .find_by_id(user_id)
+            .await?
+            .ok_or_else(|| UserModel::not_found_by("id", user_id))
+    }
+
+    async fn assign_token(&self, user: UserModel) -> AppResult<String> {
+        let token = Uuid::new_v4().simple().to_string();
+        self.user_repo.set_feed_token(user, &token).await?;
+        Ok(token)
+    }
+}
@@ -0,0 +1,193 @@
+use crate::config::MediaConfig;
+use crate::domain::media::{self, MediaKind};
+use crate::error::{AppError, AppResult, Entity};
+use crate::repository::MediaAssetRepository;
+use anyhow::Context;
+use chrono::Utc;
+use entity::media_asset::Model;
+use reqwest::header::CONTENT_TYPE;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+const USER_AGENT: &str = "Mozilla/5.0 (compatible; Fusion media proxy)";
+
+/// Proxies streamer avatars and covers, keeping a local copy of each image.
+///
+/// Images are fetched without a `Referer`, which the platform image hosts
+/// accept, and served from our origin so browsers never hot-link them.
+pub struct MediaService {
+    repo: Arc<MediaAssetRepository>,
+    client: reqwest::Client,
+    config: MediaConfig,
+}
+
+/// An image ready to be served.
+pub struct MediaFile {
+    pub bytes: Vec<u8>,
+    pub content_type: &'static str,
+}
+
+impl MediaService {
+    pub fn new(repo: Arc<MediaAssetRepository>, config: MediaConfig) -> anyhow::Result<Self> {
+        let client = reqwest::ClientBuilder::default()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(config.fetch_timeout_secs))
+            .build()
+            .context("failed to build media client")?;
+        Ok(Self {
+            repo,
+            client,
+            config,
+        })
+    }
+
+    pub fn cache_max_age_secs(&self) -> u64 {
+        self.config.cache_max_age_secs
+    }
+
+    /// Returns the cached image, fetching it on first use. `width` picks one of
+    /// the configured widths; narrower images are served unchanged.
+    pub async fn get(&self, kind: &str, hash: &str, width: Option<u32>) -> AppResult<MediaFile> {
+        let not_found = || Model::not_found_by("hash", hash);
+        let kind: MediaKind = kind.parse().map_err(|_| not_found())?;
+        if let Some(width) = width
+            && !self.config.widths.contains(&width)
+        {
+            return Err(AppError::BadRequest(format!(
+                "Unsupported width {width}, expected one of {:?}",
+                self.config.widths
+            )));
+        }
+        let asset = self.repo.find(kind, hash).await?.ok_or_else(not_found)?;
+
+        let original = self.path(kind, hash, None);
+        let bytes = match read(&original).await? {
+            Some(bytes) => bytes,
+            None => {
+                let bytes = self.download(&asset.source_url).await?;
+                write(&original, &bytes).await?;
+                self.repo
+                    .mark_fetched(asset.id, Utc::now().naive_utc())
+                    .await?;
+                bytes
+            }
+        };
+
+        let bytes = match width {
+            Some(width) => self.variant(kind, hash, width, bytes).await?,
+            None => bytes,
+        };
+        let content_type = media::sniff_content_type(&bytes)
+            .ok_or_else(|| anyhow::anyhow!("cached media {kind}/{hash} is not an image"))?;
+        Ok(MediaFile {
+            bytes,
+            content_type,
+        })
+    }
+
+    async fn variant(
+        &self,
+        kind: MediaKind,
+        hash: &str,
+        width: u32,
+        original: Vec<u8>,
+    ) -> AppResult<Vec<u8>> {
+        let path = self.path(kind, hash, Some(width));
+        if let Some(bytes) = read(&path).await? {
+            return Ok(bytes);
+        }
+
+        let resized = tokio::task::spawn_blocking(move || {
+            media::resize(&original, width).map(|resized| resized.unwrap_or(original))
+        })
+        .await
+        .context("resize task failed")?
+        .with_context(|| format!("failed to resize media {kind}/{hash}"))?;
+        write(&path, &resized).await?;
+        Ok(resized)
+    }
+
+    async fn download(&self, url: &str) -> AppResult<Vec<u8>> {
+        let upstream = |reason: String| AppError::BadGateway(format!("{url}: {reason}"));
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(upstream("not an http url".to_string()));
+        }
+
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|err| upstream(err.to_string()))?;
+        if let Some(content_type) = response.headers().get(CONTENT_TYPE)
+            && !content_type.as_bytes().starts_with(b"image/")
+        {
+            return Err(upstream(format!(
+                "unexpected content type {content_type:?}"
+            )));
+        }
+        let too_large = || upstream(format!("larger than {} bytes", self.config.max_bytes));
+        if response
+            .content_length()
+            .is_some_and(|length| length > self.config.max_bytes)
+        {
+            return Err(too_large());
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| upstream(err.to_string()))?
+        {
+            if (bytes.len() + chunk.len()) as u64 > self.config.max_bytes {
+                return Err(too_large());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        if media::sniff_content_type(&bytes).is_none() {
+            return Err(upstream("not a supported image".to_string()));
+        }
+        Ok(bytes)
+    }
+
+    fn path(&self, kind: MediaKind, hash: &str, width: Option<u32>) -> PathBuf {
+        let file = match width {
+            Some(width) => format!("{hash}-{width}"),
+            None => hash.to_string(),
+        };
+        Path::new(&self.config.cache_dir)
+            .join(kind.as_str())
+            .join(file)
+    }
+}
+
+async fn read(path: &Path) -> AppResult<Option<Vec<u8>>> {
+    match tokio::fs::read(path).await {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(anyhow::Error::new(err)
+            .context(format!("failed to read {}", path.display()))
+            .into()),
+    }
+}
+
+/// Writes through a temporary file so concurrent requests never see a partial image.
+async fn write(path: &Path, bytes: &[u8]) -> AppResult<()> {
+    let dir = path.parent().context("media path has no parent")?;
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("failed to create {}", dir.display()))?;
+    let temp = dir.join(format!(".{}.tmp", Uuid::new_v4().simple()));
+    tokio::fs::write(&temp, bytes)
+        .await
+        .with_context(|| format!("failed to write {}", temp.display()))?;
+    tokio::fs::rename(&temp, path)
+        .await
+        .with_context(|| format!("failed to move {}", path.display()))?;
+    Ok(())
+}
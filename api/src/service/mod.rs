@@ -1,28 +1,172 @@
 mod auth_service;
+mod calendar_service;
+mod creator_service;
+mod feed_service;
+mod media_service;
+mod notification_channel_service;
+mod notification_service;
+mod polling_service;
+mod share_page_service;
+mod streamer_service;
+mod subscription_group_service;
+mod subscription_service;
+mod transfer_service;
 mod user_service;
+mod viewer_stats_service;
 
+use crate::config::Config;
+use crate::notification::NotificationCenter;
 use crate::repository::Repositories;
 use crate::utils::jwt::JwtUtil;
 use anyhow::Result;
 #[allow(unused_imports)]
 pub use auth_service::{AuthService, AuthTokens, LoginIdentifier};
+pub use calendar_service::CalendarService;
+pub use creator_service::CreatorService;
+pub use feed_service::FeedService;
+use live_platform::LivePlatformProvider;
+pub use media_service::MediaService;
+pub use notification_channel_service::NotificationChannelService;
+pub use notification_service::{NotificationService, OutboxRun};
+pub use polling_service::{DueStreamers, PollingService};
+pub use share_page_service::SharePageService;
 use std::sync::Arc;
+pub use streamer_service::{LiveCheck, LiveTransition, StreamerService};
+pub use subscription_group_service::SubscriptionGroupService;
+pub use subscription_service::SubscriptionService;
+pub use transfer_service::TransferService;
 pub use user_service::UserService;
+pub use viewer_stats_service::ViewerStatsService;
 
 #[derive(Clone)]
 pub struct Services {
     pub auth: Arc<AuthService>,
     pub user: Arc<UserService>,
+    pub streamer: Arc<StreamerService>,
+    pub polling: Arc<PollingService>,
+    pub creator: Arc<CreatorService>,
+    pub viewer_stats: Arc<ViewerStatsService>,
+    pub subscription: Arc<SubscriptionService>,
+    pub subscription_group: Arc<SubscriptionGroupService>,
+    pub notification_channel: Arc<NotificationChannelService>,
+    pub notification: Arc<NotificationService>,
+    pub feed: Arc<FeedService>,
+    pub calendar: Arc<CalendarService>,
+    pub transfer: Arc<TransferService>,
+    pub share_page: Arc<SharePageService>,
+    pub media: Arc<MediaService>,
 }
 
 impl Services {
-    pub async fn build(repos: Arc<Repositories>, jwt: Arc<JwtUtil>) -> Result<Self> {
+    pub async fn build(
+        config: &Config,
+        repos: Arc<Repositories>,
+        jwt: Arc<JwtUtil>,
+        live_platform_provider: Arc<LivePlatformProvider>,
+        notification_center: Arc<NotificationCenter>,
+    ) -> Result<Self> {
         let user_service = Arc::new(UserService::new(repos.user.clone()));
         let auth_service = Arc::new(AuthService::new(repos.user.clone(), jwt));
+        let streamer_service = Arc::new(StreamerService::new(
+            repos.streamer.clone(),
+            repos.streamer_change.clone(),
+            repos.live_state_transition.clone(),
+            repos.media_asset.clone(),
+            live_platform_provider.clone(),
+            config.live_state.clone(),
+        ));
+        let polling_service = Arc::new(PollingService::new(
+            repos.streamer.clone(),
+            repos.subscription.clone(),
+            repos.live_session.clone(),
+            config.polling.clone(),
+        ));
+        let creator_service = Arc::new(CreatorService::new(
+            repos.creator.clone(),
+            repos.streamer.clone(),
+        ));
+        let viewer_stats_service = Arc::new(ViewerStatsService::new(
+            repos.viewer_sample.clone(),
+            config.viewer_stats.clone(),
+        ));
+        let subscription_service = Arc::new(SubscriptionService::new(
+            repos.subscription.clone(),
+            repos.streamer.clone(),
+            repos.live_session.clone(),
+            repos.subscription_group.clone(),
+        ));
+        let subscription_group_service = Arc::new(SubscriptionGroupService::new(
+            repos.subscription_group.clone(),
+            repos.subscription.clone(),
+        ));
+        let notification_channel_service = Arc::new(NotificationChannelService::new(
+            repos.notification_channel.clone(),
+            &config.notification,
+        )?);
+        let notification_service = Arc::new(NotificationService::new(
+            repos.subscription.clone(),
+            repos.streamer.clone(),
+            repos.notification_channel.clone(),
+            repos.live_session.clone(),
+            repos.streamer_change.clone(),
+            repos.notification_outbox.clone(),
+            notification_center,
+            config.notification.outbox.clone(),
+        ));
+
+        let feed_service = Arc::new(FeedService::new(
+            repos.user.clone(),
+            repos.live_session.clone(),
+            repos.streamer_change.clone(),
+            config.feeds.clone(),
+            config.application.name.clone(),
+        ));
+
+        let calendar_service = Arc::new(CalendarService::new(
+            repos.user.clone(),
+            repos.live_session.clone(),
+            config.calendar.clone(),
+            config.application.name.clone(),
+        )?);
+
+        let transfer_service = Arc::new(TransferService::new(
+            repos.user.clone(),
+            repos.subscription.clone(),
+            repos.subscription_group.clone(),
+            repos.streamer.clone(),
+            repos.notification_channel.clone(),
+            repos.media_asset.clone(),
+            live_platform_provider,
+        ));
+
+        let share_page_service = Arc::new(SharePageService::new(
+            repos.share_page.clone(),
+            repos.subscription_group.clone(),
+            repos.subscription.clone(),
+            config.shares.clone(),
+        ));
+
+        let media_service = Arc::new(MediaService::new(
+            repos.media_asset.clone(),
+            config.media.clone(),
+        )?);
 
         Ok(Self {
             auth: auth_service,
             user: user_service,
+            streamer: streamer_service,
+            polling: polling_service,
+            creator: creator_service,
+            viewer_stats: viewer_stats_service,
+            subscription: subscription_service,
+            subscription_group: subscription_group_service,
+            notification_channel: notification_channel_service,
+            notification: notification_service,
+            feed: feed_service,
+            calendar: calendar_service,
+            transfer: transfer_service,
+            share_page: share_page_service,
+            media: media_service,
         })
     }
 }
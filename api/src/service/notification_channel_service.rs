@@ -0,0 +1,286 @@
+use crate::config::{BarkConfig, NotificationConfig};
+use crate::dto::{
+    CreateNotificationChannelRequest, NotificationChannelDto, PushSubscriptionRequest,
+    WebPushKeyDto,
+};
+use crate::error::{AppError, AppResult, Entity};
+use crate::notification::bark::BarkProvider;
+use crate::notification::bark::crypto::Cipher;
+use crate::notification::web_push::VapidKey;
+use crate::notification::web_push::ece::SubscriptionKeys;
+use crate::notification::{NotificationChannel, bark, ntfy};
+use crate::repository::NotificationChannelRepository;
+use anyhow::Context;
+use entity::notification_channel::Model;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct NotificationChannelService {
+    repo: Arc<NotificationChannelRepository>,
+    bark: BarkConfig,
+    /// VAPID public key, `None` while Web Push is not configured.
+    web_push_key: Option<String>,
+}
+
+impl NotificationChannelService {
+    pub fn new(
+        repo: Arc<NotificationChannelRepository>,
+        config: &NotificationConfig,
+    ) -> anyhow::Result<Self> {
+        let web_push_key = match config.web_push.is_enabled() {
+            true => Some(
+                VapidKey::from_base64(&config.web_push.vapid_private_key)?
+                    .public_key()
+                    .to_string(),
+            ),
+            false => None,
+        };
+        Ok(Self {
+            repo,
+            bark: config.bark.clone(),
+            web_push_key,
+        })
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        data: CreateNotificationChannelRequest,
+    ) -> AppResult<NotificationChannelDto> {
+        let channel = NotificationChannel::from_str(&data.channel).map_err(|_| {
+            AppError::BadRequest(format!(
+                "Unsupported notification channel: {}",
+                data.channel
+            ))
+        })?;
+        let mut metadata = data.metadata;
+        let mut destination = data.destination.trim().to_string();
+        match channel {
+            NotificationChannel::Bark => self.check_bark(&destination, &metadata)?,
+            NotificationChannel::Webhook => {
+                Self::prepare_webhook(&data.destination, &mut metadata)?
+            }
+            NotificationChannel::Discord | NotificationChannel::Slack => {
+                Self::check_incoming_webhook(channel, &data.destination)?
+            }
+            NotificationChannel::DingTalk
+            | NotificationChannel::Feishu
+            | NotificationChannel::Lark
+            | NotificationChannel::WeCom => destination = Self::robot_token(channel, &destination)?,
+            NotificationChannel::Ntfy
+            | NotificationChannel::Gotify
+            | NotificationChannel::Pushover
+            | NotificationChannel::WebPush => Self::check_push(channel, &destination, &metadata)?,
+            _ => {}
+        }
+        let metadata =
+            serde_json::to_value(&metadata).context("Failed to serialize channel metadata")?;
+
+        Ok(self
+            .repo
+            .create(user_id, channel.as_str(), &destination, metadata, true)
+            .await?
+            .into())
+    }
+
+    /// Checks the url and generates a signing secret when the user brought none.
+    fn prepare_webhook(url: &str, metadata: &mut HashMap<String, String>) -> AppResult<()> {
+        let url = url.trim();
+        if !url.starts_with("https://") && !url.starts_with("http://") {
+            return Err(AppError::BadRequest(
+                "Webhook destination must be an http(s) url".to_string(),
+            ));
+        }
+        if metadata
+            .get("secret")
+            .is_none_or(|secret| secret.trim().is_empty())
+        {
+            metadata.insert("secret".to_string(), Uuid::new_v4().simple().to_string());
+        }
+        Ok(())
+    }
+
+    /// Checks the device and encryption settings, and enforces encryption on
+    /// the public relay unless the config allows plaintext there.
+    fn check_bark(&self, destination: &str, metadata: &HashMap<String, String>) -> AppResult<()> {
+        let value = |key: &str| metadata.get(key).map(String::as_str);
+        let (server, _) =
+            bark::msg::split_device(destination, value("server").unwrap_or(&self.bark.base_url))
+                .ok_or_else(|| {
+                    AppError::BadRequest(
+                        "Bark destination must be a device key or device url".to_string(),
+                    )
+                })?;
+        let cipher = Cipher::new(value("key"), value("mode"), value("iv"))
+            .map_err(|err| AppError::BadRequest(err.to_string()))?;
+        if !self.bark.allow_plaintext_relay
+            && BarkProvider::is_plaintext_relay(&server, cipher.as_ref())
+        {
+            return Err(AppError::BadRequest(
+                "Bark channels on the public relay need an encryption `key`".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Discord and Slack webhook urls carry their token, so only https is accepted.
+    fn check_incoming_webhook(channel: NotificationChannel, url: &str) -> AppResult<()> {
+        if !url.trim().starts_with("https://") {
+            return Err(AppError::BadRequest(format!(
+                "{channel} destination must be an https webhook url"
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_push(
+        channel: NotificationChannel,
+        destination: &str,
+        metadata: &HashMap<String, String>,
+    ) -> AppResult<()> {
+        let error = match channel {
+            NotificationChannel::Ntfy
+                if ntfy::msg::split_topic(destination, "https://ntfy.sh").is_none() =>
+            {
+                "ntfy destination must be a topic or topic url"
+            }
+            NotificationChannel::Gotify
+                if !destination.starts_with("https://") && !destination.starts_with("http://") =>
+            {
+                "Gotify destination must be the http(s) server url"
+            }
+            NotificationChannel::Gotify
+                if metadata
+                    .get("token")
+                    .is_none_or(|token| token.trim().is_empty()) =>
+            {
+                "Gotify channels need the application token as metadata `token`"
+            }
+            NotificationChannel::Pushover
+                if destination.is_empty()
+                    || !destination.chars().all(|c| c.is_ascii_alphanumeric()) =>
+            {
+                "Pushover destination must be a user or group key"
+            }
+            NotificationChannel::WebPush if !destination.starts_with("https://") => {
+                "Web Push destination must be the https subscription endpoint"
+            }
+            NotificationChannel::WebPush
+                if metadata
+                    .get("p256dh")
+                    .zip(metadata.get("auth"))
+                    .and_then(|(p256dh, auth)| SubscriptionKeys::parse(p256dh, auth))
+                    .is_none() =>
+            {
+                "Web Push channels need the subscription keys as metadata `p256dh` and `auth`"
+            }
+            _ => return Ok(()),
+        };
+        Err(AppError::BadRequest(error.to_string()))
+    }
+
+    /// Robots are addressed by their token; a pasted robot webhook url is cut
+    /// down to it.
+    fn robot_token(channel: NotificationChannel, destination: &str) -> AppResult<String> {
+        let token = match reqwest::Url::parse(destination) {
+            Ok(url) => match channel {
+                NotificationChannel::Feishu | NotificationChannel::Lark => url
+                    .path_segments()
+                    .and_then(|mut segments| segments.next_back())
+                    .map(str::to_string),
+                _ => {
+                    let name = match channel {
+                        NotificationChannel::DingTalk => "access_token",
+                        _ => "key",
+                    };
+                    url.query_pairs()
+                        .find(|(key, _)| key == name)
+                        .map(|(_, value)| value.into_owned())
+                }
+            },
+            Err(_) => Some(destination.to_string()),
+        };
+        token
+            .filter(|token| {
+                !token.is_empty()
+                    && token
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            })
+            .ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "{channel} destination must be a robot token or webhook url"
+                ))
+            })
+    }
+
+    pub fn web_push_key(&self) -> AppResult<WebPushKeyDto> {
+        self.web_push_key
+            .clone()
+            .map(|public_key| WebPushKeyDto { public_key })
+            .ok_or_else(Self::web_push_disabled)
+    }
+
+    /// Stores a browser subscription. An endpoint belongs to one browser
+    /// profile, so any earlier channel for it is replaced, even another user's.
+    pub async fn subscribe_web_push(
+        &self,
+        user_id: i32,
+        data: PushSubscriptionRequest,
+    ) -> AppResult<NotificationChannelDto> {
+        if self.web_push_key.is_none() {
+            return Err(Self::web_push_disabled());
+        }
+        let channel = NotificationChannel::WebPush;
+        let endpoint = data.endpoint.trim();
+        let metadata = HashMap::from([
+            ("p256dh".to_string(), data.keys.p256dh.trim().to_string()),
+            ("auth".to_string(), data.keys.auth.trim().to_string()),
+        ]);
+        Self::check_push(channel, endpoint, &metadata)?;
+        let metadata =
+            serde_json::to_value(&metadata).context("Failed to serialize channel metadata")?;
+
+        self.repo
+            .delete_destination(channel.as_str(), endpoint)
+            .await?;
+        Ok(self
+            .repo
+            .create(user_id, channel.as_str(), endpoint, metadata, true)
+            .await?
+            .into())
+    }
+
+    pub async fn unsubscribe_web_push(&self, user_id: i32, endpoint: &str) -> AppResult<()> {
+        let endpoint = endpoint.trim();
+        match self
+            .repo
+            .delete_destination_for_user(user_id, NotificationChannel::WebPush.as_str(), endpoint)
+            .await?
+        {
+            0 => Err(Model::not_found_by("destination", endpoint)),
+            _ => Ok(()),
+        }
+    }
+
+    fn web_push_disabled() -> AppError {
+        AppError::BadRequest("Web Push is not configured on this server".to_string())
+    }
+
+    pub async fn list(&self, user_id: i32) -> AppResult<Vec<NotificationChannelDto>> {
+        let channels = self.repo.list_for_user(user_id).await?;
+        Ok(channels
+            .into_iter()
+            .map(NotificationChannelDto::from)
+            .collect())
+    }
+
+    pub async fn delete(&self, id: i32, user_id: i32) -> AppResult<()> {
+        match self.repo.delete_for_user(id, user_id).await? {
+            0 => Err(Model::not_found_by("id", id)),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,702 @@
+use crate::config::OutboxConfig;
+use crate::domain::notification_outbox::{self, OutboxEvent, OutboxRecipient, OutboxStatus};
+use crate::domain::notification_rules::{NotificationRules, RuleContext};
+use crate::domain::streamer_changes::ChangedField;
+use crate::dto::{NotificationOutboxDto, PagedResponse};
+use crate::error::{AppError, AppResult, Entity};
+use crate::notification::web_push::SubscriptionGone;
+use crate::notification::{
+    DeliveryReport, NotificationAddress, NotificationCenter, NotificationChannel,
+    NotificationMessage, NotificationTarget,
+};
+use crate::repository::{
+    LiveSessionRepository, NotificationChannelRepository, NotificationOutboxRepository,
+    StreamerChangeRepository, StreamerRepository, SubscriptionRepository,
+};
+use anyhow::Context;
+use chrono::Utc;
+use entity::live_session::Model as LiveSessionModel;
+use entity::notification_channel::Model as NotificationChannelModel;
+use entity::notification_outbox::Model as OutboxModel;
+use entity::streamer::Model as StreamerModel;
+use entity::streamer_change::Model as StreamerChangeModel;
+use entity::subscription::Model as SubscriptionModel;
+use entity::subscription_group::Model as SubscriptionGroupModel;
+use std::collections::HashMap;
+use std::num::NonZeroU64;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{debug, warn};
+
+/// Outcome of one dispatcher run, by the state the claimed entries ended in.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutboxRun {
+    pub claimed: usize,
+    pub delivered: usize,
+    pub retrying: usize,
+    pub dead: usize,
+}
+
+/// Turns detected streamer events into notifications for the subscribed users.
+///
+/// Events reach it through the notification outbox: the repositories queue an
+/// entry in the transaction that records the event, and [`Self::dispatch_outbox`]
+/// resolves the subscribers and sends, retrying failed channels with backoff.
+pub struct NotificationService {
+    subscription_repo: Arc<SubscriptionRepository>,
+    streamer_repo: Arc<StreamerRepository>,
+    channel_repo: Arc<NotificationChannelRepository>,
+    live_session_repo: Arc<LiveSessionRepository>,
+    change_repo: Arc<StreamerChangeRepository>,
+    outbox_repo: Arc<NotificationOutboxRepository>,
+    notification_center: Arc<NotificationCenter>,
+    outbox: OutboxConfig,
+}
+
+impl NotificationService {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        subscription_repo: Arc<SubscriptionRepository>,
+        streamer_repo: Arc<StreamerRepository>,
+        channel_repo: Arc<NotificationChannelRepository>,
+        live_session_repo: Arc<LiveSessionRepository>,
+        change_repo: Arc<StreamerChangeRepository>,
+        outbox_repo: Arc<NotificationOutboxRepository>,
+        notification_center: Arc<NotificationCenter>,
+        outbox: OutboxConfig,
+    ) -> Self {
+        Self {
+            subscription_repo,
+            streamer_repo,
+            channel_repo,
+            live_session_repo,
+            change_repo,
+            outbox_repo,
+            notification_center,
+            outbox,
+        }
+    }
+
+    /// Sends the outbox entries that are due. An entry is delivered once every
+    /// recipient was reached or failed for good, and dead when all of them
+    /// failed for good; channels that failed in a way that may pass are tried
+    /// again later, until the entry runs out of attempts.
+    pub async fn dispatch_outbox(&self) -> AppResult<OutboxRun> {
+        let now = Utc::now().naive_utc();
+        let lease = chrono::Duration::seconds(i64::try_from(self.outbox.lease_secs).unwrap_or(0));
+        let entries = self
+            .outbox_repo
+            .claim_due(now, self.outbox.batch_size, now + lease)
+            .await?;
+
+        let mut run = OutboxRun {
+            claimed: entries.len(),
+            ..Default::default()
+        };
+        for entry in entries {
+            let outbox_id = entry.id;
+            // Left claimed on errors; the lease running out makes it due again.
+            match self.dispatch(entry).await {
+                Ok(OutboxStatus::Delivered) => run.delivered += 1,
+                Ok(OutboxStatus::Pending) => run.retrying += 1,
+                Ok(OutboxStatus::Dead) => run.dead += 1,
+                Err(err) => warn!(outbox_id, ?err, "failed to dispatch outbox entry"),
+            }
+        }
+        Ok(run)
+    }
+
+    /// Sends a test message to every enabled channel of the user, so they can
+    /// see which ones work. Sent right away, bypassing the outbox.
+    pub async fn send_test(&self, user_id: i32) -> AppResult<DeliveryReport> {
+        let mut message =
+            NotificationMessage::new("Test notification", "Your notification channel works.");
+        message.insert_metadata("event", "test");
+        self.notify_user(user_id, None, &message).await
+    }
+
+    pub async fn list_outbox(
+        &self,
+        status: Option<OutboxStatus>,
+        page: u64,
+        page_size: NonZeroU64,
+    ) -> AppResult<PagedResponse<NotificationOutboxDto>> {
+        let (total, items) = self
+            .outbox_repo
+            .list(status, page - 1, page_size.get())
+            .await?;
+        let items = items.into_iter().map(NotificationOutboxDto::from).collect();
+        Ok(PagedResponse::new(items, total, page, page_size))
+    }
+
+    pub async fn find_outbox(&self, id: i64) -> AppResult<NotificationOutboxDto> {
+        self.outbox_repo
+            .find_by_id(id)
+            .await?
+            .map(NotificationOutboxDto::from)
+            .ok_or_else(|| OutboxModel::not_found_by("id", id))
+    }
+
+    /// Queues a dead or pending entry for an immediate attempt with a fresh
+    /// attempt budget.
+    pub async fn replay_outbox(&self, id: i64) -> AppResult<NotificationOutboxDto> {
+        let entry = self
+            .outbox_repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| OutboxModel::not_found_by("id", id))?;
+        if entry.status == OutboxStatus::Delivered.as_str() {
+            return Err(AppError::BadRequest(format!(
+                "Outbox entry {id} was already delivered"
+            )));
+        }
+        self.outbox_repo
+            .replay(entry, Utc::now().naive_utc())
+            .await
+            .map(NotificationOutboxDto::from)
+    }
+
+    /// Makes one attempt at the entry and stores the outcome.
+    async fn dispatch(&self, mut entry: OutboxModel) -> AppResult<OutboxStatus> {
+        let recipients: Vec<OutboxRecipient> = match &entry.recipients {
+            Some(recipients) => serde_json::from_value(recipients.clone())
+                .context("Failed to read outbox recipients")?,
+            None => {
+                let recipients = self.resolve(&entry).await?;
+                // Stored before sending, so an attempt that is cut short retries
+                // these recipients instead of resolving the subscribers again.
+                let stored = serde_json::to_value(&recipients)
+                    .context("Failed to serialize outbox recipients")?;
+                entry = self
+                    .outbox_repo
+                    .save_recipients(entry, stored, Utc::now().naive_utc())
+                    .await?;
+                recipients
+            }
+        };
+
+        let now = Utc::now().naive_utc();
+        let mut remaining = Vec::new();
+        let mut rejected = Vec::new();
+        let mut reached = false;
+        let mut last_error = None;
+        for mut recipient in recipients {
+            let message = Self::recipient_message(entry.id, &recipient);
+            let report = match self
+                .notify_user(
+                    recipient.user_id,
+                    recipient.channel_ids.as_deref(),
+                    &message,
+                )
+                .await
+            {
+                Ok(report) => report,
+                Err(err) => {
+                    warn!(
+                        outbox_id = entry.id,
+                        user_id = recipient.user_id,
+                        ?err,
+                        "failed to notify outbox recipient"
+                    );
+                    last_error = Some(format!("user {}: {}", recipient.user_id, err));
+                    remaining.push(recipient);
+                    continue;
+                }
+            };
+
+            if report.is_empty() {
+                debug!(
+                    user_id = recipient.user_id,
+                    "user has no enabled notification channels"
+                );
+            }
+            reached |= report.delivered() > 0;
+            // Reaching the user on some channel is enough for the rules. A
+            // failure here must not keep the attempt from being stored.
+            if report.delivered() > 0
+                && let Err(err) = self
+                    .subscription_repo
+                    .mark_notified(recipient.subscription_id, now)
+                    .await
+            {
+                warn!(
+                    subscription_id = recipient.subscription_id,
+                    ?err,
+                    "failed to mark subscription notified"
+                );
+            }
+
+            let mut retry = Vec::new();
+            let mut dropped = Vec::new();
+            for (delivery, failure) in report.failures() {
+                last_error = Some(format!(
+                    "user {} channel {} ({}): {:#}",
+                    recipient.user_id,
+                    delivery.channel.as_str(),
+                    failure.class,
+                    failure.error
+                ));
+                match delivery.address_id {
+                    Some(channel_id) if failure.is_retryable() => retry.push(channel_id),
+                    Some(channel_id) => dropped.push(channel_id),
+                    None => {}
+                }
+            }
+            if !dropped.is_empty() {
+                rejected.push(OutboxRecipient {
+                    channel_ids: Some(dropped),
+                    ..recipient.clone()
+                });
+            }
+            if !retry.is_empty() {
+                recipient.channel_ids = Some(retry);
+                remaining.push(recipient);
+            }
+        }
+
+        let attempts = u32::try_from(entry.attempts).unwrap_or(0) + 1;
+        let status = notification_outbox::status_after(
+            attempts,
+            self.outbox.max_attempts,
+            !remaining.is_empty(),
+            reached || entry.delivered_at.is_some(),
+            !rejected.is_empty(),
+        );
+        let next_attempt_at = match status {
+            OutboxStatus::Pending => now + self.backoff(attempts),
+            _ => now,
+        };
+        if status == OutboxStatus::Dead {
+            // Nobody was reached; keep the rejected channels for a replay once
+            // they are fixed.
+            if remaining.is_empty() {
+                remaining = rejected;
+            }
+            warn!(
+                outbox_id = entry.id,
+                attempts,
+                recipients = remaining.len(),
+                "giving up on outbox entry"
+            );
+        }
+        let recipients =
+            serde_json::to_value(&remaining).context("Failed to serialize outbox recipients")?;
+        self.outbox_repo
+            .finish_attempt(
+                entry,
+                status,
+                recipients,
+                last_error,
+                reached,
+                next_attempt_at,
+                now,
+            )
+            .await?;
+        Ok(status)
+    }
+
+    fn backoff(&self, attempts: u32) -> chrono::Duration {
+        let delay = notification_outbox::backoff(
+            attempts,
+            Duration::from_secs(self.outbox.base_backoff_secs),
+            Duration::from_secs(self.outbox.max_backoff_secs),
+        );
+        chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+    }
+
+    /// Works out who the event notifies and with what message. Runs once per
+    /// entry; retries reuse the stored result.
+    async fn resolve(&self, entry: &OutboxModel) -> AppResult<Vec<OutboxRecipient>> {
+        let Some(streamer) = self.streamer_repo.find_by_id(entry.streamer_id).await? else {
+            return Ok(Vec::new());
+        };
+        match OutboxEvent::from_str(&entry.event) {
+            Ok(OutboxEvent::Live) => {
+                let session = match entry.live_session_id {
+                    Some(id) => self.live_session_repo.find_by_id(id).await?,
+                    None => None,
+                };
+                match session {
+                    Some(session) => self.went_live_recipients(&streamer, &session).await,
+                    None => Ok(Vec::new()),
+                }
+            }
+            Ok(OutboxEvent::Change) => {
+                let change_ids: Vec<i64> = serde_json::from_value(entry.change_ids.clone())
+                    .context("Failed to read outbox change ids")?;
+                let changes = self.change_repo.find_by_ids(change_ids).await?;
+                self.change_recipients(&streamer, &changes).await
+            }
+            Err(err) => {
+                warn!(outbox_id = entry.id, %err, "skipping unknown outbox event");
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    /// Subscribers of the streamer, or of any room of its creator, to tell that
+    /// it went live. A creator going live on a second platform while already
+    /// live elsewhere does not notify again.
+    async fn went_live_recipients(
+        &self,
+        streamer: &StreamerModel,
+        session: &LiveSessionModel,
+    ) -> AppResult<Vec<OutboxRecipient>> {
+        let streamer_ids = match streamer.creator_id {
+            Some(creator_id) => {
+                let streamer_ids: Vec<i32> = self
+                    .streamer_repo
+                    .find_by_creators(vec![creator_id])
+                    .await?
+                    .iter()
+                    .map(|room| room.id)
+                    .collect();
+                // Decided by the sessions rather than the rooms' live flags, which
+                // by now may include rooms that went live after this one.
+                let running = self
+                    .live_session_repo
+                    .find_running_at(streamer_ids.clone(), session.started_at)
+                    .await?;
+                if notification_outbox::creator_already_live(session, &running) {
+                    debug!(
+                        streamer_id = streamer.id,
+                        creator_id, "creator is already live on another room"
+                    );
+                    return Ok(Vec::new());
+                }
+                streamer_ids
+            }
+            None => vec![streamer.id],
+        };
+
+        let subscriptions = Self::one_per_user(
+            self.subscription_repo
+                .find_by_streamers(streamer_ids.clone())
+                .await?,
+            streamer.id,
+        );
+        if subscriptions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let previous_session = self
+            .live_session_repo
+            .find_previous(streamer_ids, session.started_at)
+            .await?;
+        let message = Self::went_live_message(streamer);
+        let now = Utc::now().naive_utc();
+
+        let mut recipients = Vec::new();
+        for (subscription, group) in subscriptions {
+            let ctx = RuleContext {
+                now,
+                title: &streamer.title,
+                category: &streamer.category,
+                last_notified_at: subscription.last_notified_at,
+                previous_live_started_at: previous_session.as_ref().map(|s| s.started_at),
+            };
+            if let Err(reason) = Self::rules(&subscription, group.as_ref()).evaluate(&ctx) {
+                debug!(
+                    subscription_id = subscription.id,
+                    %reason,
+                    "notification skipped by subscription rules"
+                );
+                continue;
+            }
+            recipients.push(Self::recipient(&subscription, &message));
+        }
+
+        Ok(recipients)
+    }
+
+    /// Subscribers that opted into title or category changes, for the changes
+    /// recorded while the streamer was live, and those that opted into
+    /// announcements, for newly posted ones, live or not.
+    async fn change_recipients(
+        &self,
+        streamer: &StreamerModel,
+        changes: &[StreamerChangeModel],
+    ) -> AppResult<Vec<OutboxRecipient>> {
+        let changes: Vec<(ChangedField, &StreamerChangeModel)> = changes
+            .iter()
+            .filter_map(|change| change.field.parse().ok().map(|field| (field, change)))
+            .filter(|(field, change): &(ChangedField, &StreamerChangeModel)| {
+                field.is_notable(change.while_live, &change.new_value)
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let subscriptions = self
+            .subscription_repo
+            .find_by_streamers(vec![streamer.id])
+            .await?;
+        let now = Utc::now().naive_utc();
+
+        let mut recipients = Vec::new();
+        for (subscription, group) in subscriptions {
+            let wanted: Vec<_> = changes
+                .iter()
+                .filter(|(field, _)| match field {
+                    ChangedField::Title => subscription.notify_title_change,
+                    ChangedField::Category => subscription.notify_category_change,
+                    ChangedField::Announcement => subscription.notify_announcement_change,
+                    _ => false,
+                })
+                .collect();
+            if wanted.is_empty() {
+                continue;
+            }
+
+            let ctx = RuleContext {
+                now,
+                title: &streamer.title,
+                category: &streamer.category,
+                last_notified_at: subscription.last_notified_at,
+                previous_live_started_at: None,
+            };
+            if let Err(reason) = Self::rules(&subscription, group.as_ref()).evaluate(&ctx) {
+                debug!(
+                    subscription_id = subscription.id,
+                    %reason,
+                    "change notification skipped by subscription rules"
+                );
+                continue;
+            }
+
+            let message = Self::changed_message(streamer, &wanted);
+            recipients.push(Self::recipient(&subscription, &message));
+        }
+
+        Ok(recipients)
+    }
+
+    /// Sends the message to the enabled channels of the user, all of them or
+    /// only `channel_ids`. The report is empty when there is nothing to deliver to.
+    async fn notify_user(
+        &self,
+        user_id: i32,
+        channel_ids: Option<&[i32]>,
+        message: &NotificationMessage,
+    ) -> AppResult<DeliveryReport> {
+        let addresses: Vec<NotificationAddress> = self
+            .channel_repo
+            .find_enabled_for_user(user_id)
+            .await?
+            .iter()
+            .filter(|model| channel_ids.is_none_or(|ids| ids.contains(&model.id)))
+            .filter_map(Self::to_address)
+            .collect();
+        if addresses.is_empty() {
+            return Ok(DeliveryReport::default());
+        }
+
+        let target = NotificationTarget::new(user_id, addresses);
+        let report = self
+            .notification_center
+            .notify_target(&target, message)
+            .await;
+        for (delivery, failure) in report.failures() {
+            warn!(
+                user_id,
+                channel_id = delivery.address_id,
+                class = %failure.class,
+                retryable = failure.is_retryable(),
+                err = ?failure.error,
+                "failed to deliver notification"
+            );
+            // Browsers drop subscriptions on their own; forget them once the
+            // push service says so.
+            // Sending already happened, so a failed cleanup must not fail
+            // the attempt and have it sent again.
+            if let Some(gone) = failure.error.downcast_ref::<SubscriptionGone>() {
+                debug!(user_id, status = %gone.status, "removing expired web push subscription");
+                if let Err(err) = self
+                    .channel_repo
+                    .delete_destination_for_user(
+                        user_id,
+                        NotificationChannel::WebPush.as_str(),
+                        &gone.endpoint,
+                    )
+                    .await
+                {
+                    warn!(
+                        user_id,
+                        ?err,
+                        "failed to remove expired web push subscription"
+                    );
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Keeps a single subscription per user when they follow several rooms of the
+    /// same creator, preferring the room that triggered the notification.
+    fn one_per_user(
+        subscriptions: Vec<(SubscriptionModel, Option<SubscriptionGroupModel>)>,
+        streamer_id: i32,
+    ) -> Vec<(SubscriptionModel, Option<SubscriptionGroupModel>)> {
+        let mut by_user: HashMap<i32, (SubscriptionModel, Option<SubscriptionGroupModel>)> =
+            HashMap::new();
+        for (subscription, group) in subscriptions {
+            match by_user.get(&subscription.user_id) {
+                Some((kept, _))
+                    if kept.streamer_id == streamer_id
+                        || subscription.streamer_id != streamer_id => {}
+                _ => {
+                    by_user.insert(subscription.user_id, (subscription, group));
+                }
+            }
+        }
+        let mut subscriptions: Vec<_> = by_user.into_values().collect();
+        subscriptions.sort_by_key(|(subscription, _)| subscription.id);
+        subscriptions
+    }
+
+    /// Rules of the subscription, with unset rules inherited from its group.
+    fn rules(
+        subscription: &SubscriptionModel,
+        group: Option<&SubscriptionGroupModel>,
+    ) -> NotificationRules {
+        let own: NotificationRules = serde_json::from_value(subscription.rules.clone())
+            .unwrap_or_else(|err| {
+                warn!(
+                    subscription_id = subscription.id,
+                    ?err,
+                    "ignoring unreadable subscription rules"
+                );
+                NotificationRules::default()
+            });
+        let Some(group) = group else {
+            return own;
+        };
+        match serde_json::from_value::<NotificationRules>(group.rules.clone()) {
+            Ok(group_rules) => own.inherit(&group_rules),
+            Err(err) => {
+                warn!(group_id = group.id, ?err, "ignoring unreadable group rules");
+                own
+            }
+        }
+    }
+
+    fn recipient(
+        subscription: &SubscriptionModel,
+        message: &NotificationMessage,
+    ) -> OutboxRecipient {
+        OutboxRecipient {
+            subscription_id: subscription.id,
+            user_id: subscription.user_id,
+            title: message.title().to_string(),
+            body: message.body().to_string(),
+            url: message.url().map(str::to_string),
+            metadata: message.metadata().clone(),
+            channel_ids: None,
+        }
+    }
+
+    fn recipient_message(outbox_id: i64, recipient: &OutboxRecipient) -> NotificationMessage {
+        let mut message =
+            NotificationMessage::new(recipient.title.as_str(), recipient.body.as_str());
+        if let Some(url) = &recipient.url {
+            message.set_url(url.as_str());
+        }
+        for (key, value) in &recipient.metadata {
+            message.insert_metadata(key.as_str(), value.as_str());
+        }
+        // The same on every attempt, so receivers can drop repeats of a retry.
+        message.insert_metadata(
+            "delivery_id",
+            format!("outbox-{outbox_id}-{}", recipient.user_id),
+        );
+        message
+    }
+
+    fn to_address(model: &NotificationChannelModel) -> Option<NotificationAddress> {
+        let Ok(channel) = NotificationChannel::from_str(&model.channel) else {
+            warn!(
+                channel_id = model.id,
+                channel = model.channel,
+                "skipping unsupported notification channel"
+            );
+            return None;
+        };
+
+        let metadata: HashMap<String, String> =
+            serde_json::from_value(model.metadata.clone()).unwrap_or_default();
+        Some(metadata.into_iter().fold(
+            NotificationAddress::new(channel, model.destination.as_str()).with_id(model.id),
+            |address, (key, value)| address.insert_metadata(key, value),
+        ))
+    }
+
+    fn changed_message(
+        streamer: &StreamerModel,
+        changes: &[&(ChangedField, &StreamerChangeModel)],
+    ) -> NotificationMessage {
+        let title = match changes {
+            [(ChangedField::Title, _)] => format!("{} changed the title", streamer.name),
+            [(ChangedField::Category, _)] => format!("{} switched category", streamer.name),
+            [(ChangedField::Announcement, _)] => {
+                format!("{} posted a new announcement", streamer.name)
+            }
+            _ => format!("{} updated the stream", streamer.name),
+        };
+        let body = changes
+            .iter()
+            .map(|(field, change)| match field {
+                // Announcements are often long schedules; the old one adds noise.
+                ChangedField::Announcement => change.new_value.clone(),
+                _ => format!("{} → {}", change.old_value, change.new_value),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let fields = changes
+            .iter()
+            .map(|(field, _)| field.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let mut message = NotificationMessage::new(title, body);
+        message
+            .set_url(streamer.room_url.as_str())
+            .insert_metadata("event", "change")
+            .insert_metadata("fields", fields)
+            .insert_metadata("streamer_id", streamer.id.to_string())
+            .insert_metadata("streamer_name", streamer.name.as_str())
+            .insert_metadata("platform", streamer.platform.as_str())
+            .insert_metadata("room_id", streamer.room_id.as_str())
+            .insert_metadata("title", streamer.title.as_str())
+            .insert_metadata("category", streamer.category.as_str())
+            .insert_metadata("cover", streamer.cover_image.as_str());
+        message
+    }
+
+    fn went_live_message(streamer: &StreamerModel) -> NotificationMessage {
+        let mut message = NotificationMessage::new(
+            format!("{} is live", streamer.name),
+            if streamer.category.is_empty() {
+                streamer.title.clone()
+            } else {
+                format!("{}\n{}", streamer.title, streamer.category)
+            },
+        );
+        message
+            .set_url(streamer.room_url.as_str())
+            .insert_metadata("event", "live")
+            .insert_metadata("streamer_id", streamer.id.to_string())
+            .insert_metadata("streamer_name", streamer.name.as_str())
+            .insert_metadata("platform", streamer.platform.as_str())
+            .insert_metadata("room_id", streamer.room_id.as_str())
+            .insert_metadata("title", streamer.title.as_str())
+            .insert_metadata("category", streamer.category.as_str())
+            .insert_metadata("viewers", streamer.viewer_count.to_string())
+            .insert_metadata("cover", streamer.cover_image.as_str());
+        if let Some(creator_id) = streamer.creator_id {
+            message.insert_metadata("creator_id", creator_id.to_string());
+        }
+        message
+    }
+}
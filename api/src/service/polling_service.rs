@@ -0,0 +1,198 @@
+use crate::config::PollingConfig;
+use crate::domain::live_state::LiveTracker;
+use crate::domain::polling::{self, PollingInput};
+use crate::domain::schedule::PastSession;
+use crate::error::AppResult;
+use crate::repository::{LiveSessionRepository, StreamerRepository, SubscriptionRepository};
+use chrono::{Duration, NaiveDateTime};
+use entity::streamer::Model as StreamerModel;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Decides which rooms the live check polls and when each is polled next.
+pub struct PollingService {
+    streamer_repo: Arc<StreamerRepository>,
+    subscription_repo: Arc<SubscriptionRepository>,
+    live_session_repo: Arc<LiveSessionRepository>,
+    config: PollingConfig,
+}
+
+/// Rooms to poll in this run.
+pub struct DueStreamers {
+    pub streamers: Vec<StreamerModel>,
+    /// Due rooms left for a later run because their platform is out of budget.
+    pub deferred: usize,
+}
+
+impl PollingService {
+    pub fn new(
+        streamer_repo: Arc<StreamerRepository>,
+        subscription_repo: Arc<SubscriptionRepository>,
+        live_session_repo: Arc<LiveSessionRepository>,
+        config: PollingConfig,
+    ) -> Self {
+        Self {
+            streamer_repo,
+            subscription_repo,
+            live_session_repo,
+            config,
+        }
+    }
+
+    /// Rooms whose next poll is due, most overdue first, limited to what each
+    /// platform has left of its budget for the past minute.
+    pub async fn due(&self, now: NaiveDateTime) -> AppResult<DueStreamers> {
+        let mut used: HashMap<String, i64> = self
+            .streamer_repo
+            .count_checked_since(now - Duration::minutes(1))
+            .await?
+            .into_iter()
+            .collect();
+
+        let mut due = DueStreamers {
+            streamers: Vec::new(),
+            deferred: 0,
+        };
+        for streamer in self.streamer_repo.find_due(now).await? {
+            let budget = i64::from(self.config.budget_per_minute(&streamer.platform));
+            let used = used.entry(streamer.platform.clone()).or_default();
+            if *used < budget {
+                *used += 1;
+                due.streamers.push(streamer);
+            } else {
+                due.deferred += 1;
+            }
+        }
+        Ok(due)
+    }
+
+    /// Stores when each of the polled rooms is due again.
+    ///
+    /// The stored interval is the one the room asks for before the platform
+    /// budget is applied, so the demand on a platform does not shrink just
+    /// because its rooms were stretched in an earlier run.
+    pub async fn schedule(&self, streamers: &[StreamerModel], now: NaiveDateTime) -> AppResult<()> {
+        if streamers.is_empty() {
+            return Ok(());
+        }
+
+        let settings = self.config.settings();
+        let budget_factors = self.budget_factors().await?;
+        let subscribers = self.subscriber_counts(streamers).await?;
+        let sessions = self.past_sessions(streamers, now).await?;
+
+        for streamer in streamers {
+            let input = PollingInput {
+                subscribers: subscribers.get(&streamer.id).copied().unwrap_or(0),
+                live_likelihood: polling::live_likelihood(
+                    sessions.get(&streamer.id).map_or(&[], Vec::as_slice),
+                    now,
+                    self.config.lookback_weeks,
+                ),
+                is_live: streamer.is_live,
+                confirming: LiveTracker::of(streamer).state.is_pending(),
+            };
+            let wanted = polling::interval_secs(&input, &settings, 1.0);
+            let interval = polling::interval_secs(
+                &input,
+                &settings,
+                budget_factors
+                    .get(&streamer.platform)
+                    .copied()
+                    .unwrap_or(1.0),
+            );
+            self.streamer_repo
+                .schedule_check(
+                    streamer.id,
+                    now + Duration::seconds(i64::from(interval)),
+                    i32::try_from(wanted).unwrap_or(i32::MAX),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Stretch factor per platform, from the polls per minute all its rooms ask
+    /// for. Rooms that were never scheduled count at the minimum interval.
+    async fn budget_factors(&self) -> AppResult<HashMap<String, f64>> {
+        let min_interval = f64::from(self.config.min_interval_secs);
+        let mut demand: HashMap<String, f64> = HashMap::new();
+        for (platform, interval) in self.streamer_repo.find_poll_intervals().await? {
+            let interval = interval.map_or(min_interval, |secs| f64::from(secs.max(1)));
+            *demand.entry(platform).or_default() += 60.0 / interval;
+        }
+
+        Ok(demand
+            .into_iter()
+            .map(|(platform, demand)| {
+                let budget = self.config.budget_per_minute(&platform);
+                (platform, polling::budget_factor(demand, budget))
+            })
+            .collect())
+    }
+
+    /// Subscribers per room. Rooms of the same creator share their audience, so
+    /// each of them counts the subscribers of all of them.
+    async fn subscriber_counts(&self, streamers: &[StreamerModel]) -> AppResult<HashMap<i32, u64>> {
+        let creator_ids: Vec<i32> = streamers.iter().filter_map(|s| s.creator_id).collect();
+        let siblings = self.streamer_repo.find_by_creators(creator_ids).await?;
+        let mut ids: Vec<i32> = streamers.iter().map(|streamer| streamer.id).collect();
+        ids.extend(siblings.iter().map(|streamer| streamer.id));
+        ids.sort_unstable();
+        ids.dedup();
+
+        let counts: HashMap<i32, u64> = self
+            .subscription_repo
+            .count_by_streamers(ids)
+            .await?
+            .into_iter()
+            .map(|(id, count)| (id, u64::try_from(count).unwrap_or(0)))
+            .collect();
+        let mut by_creator: HashMap<i32, u64> = HashMap::new();
+        for sibling in &siblings {
+            if let Some(creator_id) = sibling.creator_id {
+                *by_creator.entry(creator_id).or_default() +=
+                    counts.get(&sibling.id).copied().unwrap_or(0);
+            }
+        }
+
+        Ok(streamers
+            .iter()
+            .map(|streamer| {
+                let count = match streamer.creator_id {
+                    Some(creator_id) => by_creator.get(&creator_id).copied(),
+                    None => counts.get(&streamer.id).copied(),
+                };
+                (streamer.id, count.unwrap_or(0))
+            })
+            .collect())
+    }
+
+    async fn past_sessions(
+        &self,
+        streamers: &[StreamerModel],
+        now: NaiveDateTime,
+    ) -> AppResult<HashMap<i32, Vec<PastSession>>> {
+        // A day of slack keeps long sessions that started before the lookback.
+        let since =
+            now - Duration::weeks(i64::from(self.config.lookback_weeks)) - Duration::days(1);
+        let mut sessions: HashMap<i32, Vec<PastSession>> = HashMap::new();
+        for session in self
+            .live_session_repo
+            .find_started_since(
+                streamers.iter().map(|streamer| streamer.id).collect(),
+                since,
+            )
+            .await?
+        {
+            sessions
+                .entry(session.streamer_id)
+                .or_default()
+                .push(PastSession {
+                    started_at: session.started_at,
+                    ended_at: session.ended_at,
+                });
+        }
+        Ok(sessions)
+    }
+}
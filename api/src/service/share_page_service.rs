@@ -0,0 +1,182 @@
+use crate::config::SharesConfig;
+use crate::domain::media::{self, MediaKind};
+use crate::domain::share_page::{self, SharedPage, SharedStreamer};
+use crate::dto::{CreateSharePageRequest, SharePageDto, SubscriptionFilter};
+use crate::error::{AppError, AppResult, Entity};
+use crate::repository::{SharePageRepository, SubscriptionGroupRepository, SubscriptionRepository};
+use chrono::{NaiveDateTime, Utc};
+use entity::share_page::Model;
+use entity::subscription_group::Model as SubscriptionGroupModel;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Publishes read-only pages listing the streamers of a subscription group.
+pub struct SharePageService {
+    repo: Arc<SharePageRepository>,
+    group_repo: Arc<SubscriptionGroupRepository>,
+    subscription_repo: Arc<SubscriptionRepository>,
+    config: SharesConfig,
+}
+
+/// A rendered share page together with how long it may be cached.
+pub struct RenderedSharePage {
+    pub page: SharedPage,
+    pub max_age_secs: u64,
+}
+
+impl SharePageService {
+    pub fn new(
+        repo: Arc<SharePageRepository>,
+        group_repo: Arc<SubscriptionGroupRepository>,
+        subscription_repo: Arc<SubscriptionRepository>,
+        config: SharesConfig,
+    ) -> Self {
+        Self {
+            repo,
+            group_repo,
+            subscription_repo,
+            config,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        data: CreateSharePageRequest,
+        base_url: &str,
+    ) -> AppResult<SharePageDto> {
+        if let Some(expires_at) = data.expires_at
+            && expires_at <= Utc::now().naive_utc()
+        {
+            return Err(AppError::BadRequest(
+                "Expiry must be in the future".to_string(),
+            ));
+        }
+
+        let group = self
+            .group_repo
+            .find_for_user(data.group_id, user_id)
+            .await?
+            .ok_or_else(|| SubscriptionGroupModel::not_found_by("id", data.group_id))?;
+        let title = match data.title.as_deref().map(str::trim) {
+            Some("") => {
+                return Err(AppError::BadRequest("Title must not be blank".to_string()));
+            }
+            Some(title) => title,
+            None => group.name.as_str(),
+        };
+
+        let page = self
+            .repo
+            .create(user_id, group.id, &Self::slug(), title, data.expires_at)
+            .await?;
+        Ok(SharePageDto::new(page, base_url))
+    }
+
+    pub async fn list(&self, user_id: i32, base_url: &str) -> AppResult<Vec<SharePageDto>> {
+        Ok(self
+            .repo
+            .list_for_user(user_id)
+            .await?
+            .into_iter()
+            .map(|page| SharePageDto::new(page, base_url))
+            .collect())
+    }
+
+    pub async fn find(&self, id: i32, user_id: i32, base_url: &str) -> AppResult<SharePageDto> {
+        let page = self.find_for_user(id, user_id).await?;
+        Ok(SharePageDto::new(page, base_url))
+    }
+
+    /// Replaces the slug of the page, invalidating previously shared URLs.
+    pub async fn rotate_slug(
+        &self,
+        id: i32,
+        user_id: i32,
+        base_url: &str,
+    ) -> AppResult<SharePageDto> {
+        let page = self.find_for_user(id, user_id).await?;
+        let page = self.repo.set_slug(page, &Self::slug()).await?;
+        Ok(SharePageDto::new(page, base_url))
+    }
+
+    pub async fn delete(&self, id: i32, user_id: i32) -> AppResult<()> {
+        match self.repo.delete_for_user(id, user_id).await? {
+            0 => Err(Model::not_found_by("id", id)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Builds the public view of a page. Unknown and expired slugs are both
+    /// reported as not found, so the response does not reveal which is which.
+    pub async fn render(&self, slug: &str, live_only: bool) -> AppResult<RenderedSharePage> {
+        let now = Utc::now().naive_utc();
+        let page = self
+            .repo
+            .find_by_slug(slug)
+            .await?
+            .filter(|page| !Self::is_expired(page, now))
+            .ok_or_else(|| Model::not_found_by("slug", slug))?;
+
+        let mut streamers: Vec<SharedStreamer> = self
+            .subscription_repo
+            .find_all_for_user(
+                page.user_id,
+                &SubscriptionFilter {
+                    group_id: Some(page.group_id),
+                    tag: None,
+                },
+            )
+            .await?
+            .into_iter()
+            .filter_map(|(_, streamer)| streamer)
+            .filter(|streamer| streamer.is_live || !live_only)
+            .map(|streamer| SharedStreamer {
+                live_started_at: streamer.live_started_at.filter(|_| streamer.is_live),
+                name: streamer.name,
+                platform: streamer.platform,
+                room_url: streamer.room_url,
+                avatar_proxy_url: media::proxy_path(MediaKind::Avatar, &streamer.avatar),
+                avatar: streamer.avatar,
+                is_live: streamer.is_live,
+                title: streamer.title,
+                category: streamer.category,
+                cover_image_proxy_url: media::proxy_path(MediaKind::Cover, &streamer.cover_image),
+                cover_image: streamer.cover_image,
+                viewer_count: streamer.viewer_count,
+            })
+            .collect();
+        streamers.sort_by(|a, b| {
+            b.is_live
+                .cmp(&a.is_live)
+                .then(b.viewer_count.cmp(&a.viewer_count))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        Ok(RenderedSharePage {
+            max_age_secs: share_page::max_age(self.config.cache_max_age_secs, now, page.expires_at),
+            page: SharedPage {
+                title: page.title,
+                generated_at: now,
+                expires_at: page.expires_at,
+                live_count: streamers.iter().filter(|streamer| streamer.is_live).count(),
+                streamers,
+            },
+        })
+    }
+
+    async fn find_for_user(&self, id: i32, user_id: i32) -> AppResult<Model> {
+        self.repo
+            .find_for_user(id, user_id)
+            .await?
+            .ok_or_else(|| Model::not_found_by("id", id))
+    }
+
+    fn is_expired(page: &Model, now: NaiveDateTime) -> bool {
+        page.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    fn slug() -> String {
+        Uuid::new_v4().simple().to_string()
+    }
+}
@@ -0,0 +1,275 @@
+use crate::config::LiveStateConfig;
+use crate::domain::live_state::{LiveEdge, LiveTracker};
+use crate::domain::media::MediaKind;
+use crate::domain::streamer_changes::{self, ChangedField};
+use crate::dto::{
+    CreateStreamerRequest, LiveStateTransitionDto, PagedResponse, StreamerChangeDto, StreamerDto,
+};
+use crate::error::{AppError, AppResult, Entity, IntoAppResult};
+use crate::repository::{
+    LiveStateTransitionRepository, MediaAssetRepository, StreamerChangeRepository,
+    StreamerRepository,
+};
+use anyhow::Context;
+use chrono::{NaiveDateTime, Utc};
+use entity::live_session::Model as LiveSessionModel;
+use entity::streamer::Model;
+use entity::streamer_change::Model as StreamerChangeModel;
+use live_platform::{LivePlatformProvider, Platform};
+use std::num::NonZeroU64;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Change of a streamer's live state detected by a poll.
+#[derive(Debug, Clone)]
+pub enum LiveTransition {
+    Unchanged,
+    WentLive(LiveSessionModel),
+    /// Offline since the first poll that saw the room offline.
+    WentOffline(NaiveDateTime),
+}
+
+/// Outcome of a live status poll.
+#[derive(Debug, Clone)]
+pub struct LiveCheck {
+    pub streamer: Model,
+    pub transition: LiveTransition,
+    /// Metadata changes recorded by this poll.
+    pub changes: Vec<StreamerChangeModel>,
+}
+
+pub struct StreamerService {
+    repo: Arc<StreamerRepository>,
+    change_repo: Arc<StreamerChangeRepository>,
+    transition_repo: Arc<LiveStateTransitionRepository>,
+    media_repo: Arc<MediaAssetRepository>,
+    live_platform_provider: Arc<LivePlatformProvider>,
+    live_state_config: LiveStateConfig,
+}
+
+impl StreamerService {
+    pub fn new(
+        repo: Arc<StreamerRepository>,
+        change_repo: Arc<StreamerChangeRepository>,
+        transition_repo: Arc<LiveStateTransitionRepository>,
+        media_repo: Arc<MediaAssetRepository>,
+        live_platform_provider: Arc<LivePlatformProvider>,
+        live_state_config: LiveStateConfig,
+    ) -> Self {
+        Self {
+            repo,
+            change_repo,
+            transition_repo,
+            media_repo,
+            live_platform_provider,
+            live_state_config,
+        }
+    }
+
+    /// Resolves the room on its platform and stores (or refreshes) the streamer profile.
+    pub async fn create(&self, data: CreateStreamerRequest) -> AppResult<StreamerDto> {
+        let platform = Platform::from_str(&data.platform).map_err(|_| {
+            AppError::BadRequest(format!("Unsupported platform: {}", data.platform))
+        })?;
+
+        let info = self
+            .live_platform_provider
+            .fetch_streamer_info(platform, data.room_id.trim())
+            .await
+            .map_err(|err| {
+                AppError::BadRequest(format!(
+                    "Failed to resolve {} room {}: {}",
+                    platform, data.room_id, err
+                ))
+            })?;
+
+        let streamer = self.repo.upsert_info(&info).await?;
+        self.media_repo
+            .register(MediaKind::Avatar, &streamer.avatar)
+            .await?;
+        Ok(streamer.into())
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> AppResult<StreamerDto> {
+        self.repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| Model::not_found_by("id", id))
+            .map(|model| model.into())
+            .into_app_result()
+    }
+
+    pub async fn list(
+        &self,
+        page: u64,
+        page_size: NonZeroU64,
+    ) -> AppResult<PagedResponse<StreamerDto>> {
+        let (total, items) = self.repo.list(page - 1, page_size.get()).await?;
+        let items = items.into_iter().map(StreamerDto::from).collect();
+        Ok(PagedResponse::new(items, total, page, page_size))
+    }
+
+    pub async fn find_all(&self) -> AppResult<Vec<Model>> {
+        self.repo.find_all().await
+    }
+
+    pub async fn changes(
+        &self,
+        id: i32,
+        field: Option<ChangedField>,
+        page: u64,
+        page_size: NonZeroU64,
+    ) -> AppResult<PagedResponse<StreamerChangeDto>> {
+        let streamer = self
+            .repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| Model::not_found_by("id", id))?;
+        let (total, items) = self
+            .change_repo
+            .list_for_streamer(streamer.id, field, page - 1, page_size.get())
+            .await?;
+        let items = items.into_iter().map(StreamerChangeDto::from).collect();
+        Ok(PagedResponse::new(items, total, page, page_size))
+    }
+
+    pub async fn live_states(
+        &self,
+        id: i32,
+        page: u64,
+        page_size: NonZeroU64,
+    ) -> AppResult<PagedResponse<LiveStateTransitionDto>> {
+        let streamer = self
+            .repo
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| Model::not_found_by("id", id))?;
+        let (total, items) = self
+            .transition_repo
+            .list_for_streamer(streamer.id, page - 1, page_size.get())
+            .await?;
+        let items = items
+            .into_iter()
+            .map(LiveStateTransitionDto::from)
+            .collect();
+        Ok(PagedResponse::new(items, total, page, page_size))
+    }
+
+    /// Fetches the streamer profile again and records name or avatar changes.
+    pub async fn refresh_profile(&self, streamer: Model) -> AppResult<Vec<StreamerChangeModel>> {
+        let platform = Self::platform_of(&streamer)?;
+        let info = self
+            .live_platform_provider
+            .fetch_streamer_info(platform, &streamer.room_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to fetch profile of {} room {}",
+                    platform, streamer.room_id
+                )
+            })?;
+
+        let updated = self.repo.upsert_info(&info).await?;
+        if updated.avatar != streamer.avatar {
+            self.media_repo
+                .register(MediaKind::Avatar, &updated.avatar)
+                .await?;
+        }
+        self.record_changes(&streamer, &updated, Utc::now().naive_utc())
+            .await
+    }
+
+    /// Fetches the room announcement and records it when it changed.
+    pub async fn refresh_announcement(
+        &self,
+        streamer: Model,
+    ) -> AppResult<Vec<StreamerChangeModel>> {
+        let platform = Self::platform_of(&streamer)?;
+        let announcement = self
+            .live_platform_provider
+            .fetch_announcement(platform, &streamer.room_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to fetch announcement of {} room {}",
+                    platform, streamer.room_id
+                )
+            })?;
+        if streamer.announcement.as_deref() == Some(announcement.as_str()) {
+            return Ok(Vec::new());
+        }
+
+        let updated = self
+            .repo
+            .set_announcement(streamer.clone(), announcement)
+            .await?;
+        self.record_changes(&streamer, &updated, Utc::now().naive_utc())
+            .await
+    }
+
+    /// Polls the platform for the current live status, persists it, records metadata
+    /// changes and opens or closes the live session when the streamer went live or offline.
+    pub async fn refresh_live_status(&self, streamer: Model) -> AppResult<LiveCheck> {
+        let platform = Self::platform_of(&streamer)?;
+
+        let status = self
+            .live_platform_provider
+            .check_live_status(platform, &streamer.room_id)
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to check live status of {} room {}",
+                    platform, streamer.room_id
+                )
+            })?;
+
+        let previous_cover = streamer.cover_image.clone();
+        let checked_at = Utc::now().naive_utc();
+        let step = LiveTracker::of(&streamer).observe(
+            status.is_live,
+            checked_at,
+            &self.live_state_config.thresholds(&streamer.platform),
+        );
+        let (streamer, session, changes) = self
+            .repo
+            .update_live_status(streamer, &status, &step, checked_at)
+            .await?;
+        if streamer.cover_image != previous_cover {
+            self.media_repo
+                .register(MediaKind::Cover, &streamer.cover_image)
+                .await?;
+        }
+
+        let transition = match (step.edge, session) {
+            (Some(LiveEdge::WentLive { .. }), Some(session)) => LiveTransition::WentLive(session),
+            (Some(LiveEdge::WentOffline { since }), _) => LiveTransition::WentOffline(since),
+            _ => LiveTransition::Unchanged,
+        };
+
+        Ok(LiveCheck {
+            streamer,
+            transition,
+            changes,
+        })
+    }
+
+    async fn record_changes(
+        &self,
+        old: &Model,
+        new: &Model,
+        changed_at: NaiveDateTime,
+    ) -> AppResult<Vec<StreamerChangeModel>> {
+        let changes = streamer_changes::diff(old, new);
+        if changes.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.change_repo
+            .record(new.id, &changes, old.is_live && new.is_live, changed_at)
+            .await
+    }
+
+    fn platform_of(streamer: &Model) -> AppResult<Platform> {
+        Platform::from_str(&streamer.platform)
+            .map_err(|_| anyhow::anyhow!("Unsupported platform: {}", streamer.platform).into())
+    }
+}
@@ -0,0 +1,124 @@
+use crate::domain::notification_rules::NotificationRules;
+use crate::dto::{
+    CreateSubscriptionGroupRequest, SubscriptionGroupDto, UpdateSubscriptionGroupRequest,
+};
+use crate::error::{AppError, AppResult, Entity};
+use crate::repository::{SubscriptionGroupRepository, SubscriptionRepository};
+use anyhow::Context;
+use entity::subscription_group::Model;
+use sea_orm::JsonValue;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+pub struct SubscriptionGroupService {
+    repo: Arc<SubscriptionGroupRepository>,
+    subscription_repo: Arc<SubscriptionRepository>,
+}
+
+impl SubscriptionGroupService {
+    pub fn new(
+        repo: Arc<SubscriptionGroupRepository>,
+        subscription_repo: Arc<SubscriptionRepository>,
+    ) -> Self {
+        Self {
+            repo,
+            subscription_repo,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        data: CreateSubscriptionGroupRequest,
+    ) -> AppResult<SubscriptionGroupDto> {
+        let name = Self::name(&data.name)?;
+        let rules = Self::rules_to_json(&data.rules)?;
+        let group = self.repo.create(user_id, name, rules).await?;
+        Ok(SubscriptionGroupDto::new(group, 0))
+    }
+
+    pub async fn list(&self, user_id: i32) -> AppResult<Vec<SubscriptionGroupDto>> {
+        let counts = self.counts(user_id).await?;
+        Ok(self
+            .repo
+            .list_for_user(user_id)
+            .await?
+            .into_iter()
+            .map(|group| {
+                let count = counts.get(&group.id).copied().unwrap_or_default();
+                SubscriptionGroupDto::new(group, count)
+            })
+            .collect())
+    }
+
+    pub async fn find(&self, id: i32, user_id: i32) -> AppResult<SubscriptionGroupDto> {
+        let group = self.find_for_user(id, user_id).await?;
+        let count = self
+            .counts(user_id)
+            .await?
+            .get(&group.id)
+            .copied()
+            .unwrap_or_default();
+        Ok(SubscriptionGroupDto::new(group, count))
+    }
+
+    pub async fn update(
+        &self,
+        id: i32,
+        user_id: i32,
+        data: UpdateSubscriptionGroupRequest,
+    ) -> AppResult<SubscriptionGroupDto> {
+        let name = data.name.as_deref().map(Self::name).transpose()?;
+        let rules = data.rules.as_ref().map(Self::rules_to_json).transpose()?;
+        let group = self.find_for_user(id, user_id).await?;
+        let group = self.repo.update(group, name, rules).await?;
+        let count = self
+            .counts(user_id)
+            .await?
+            .get(&group.id)
+            .copied()
+            .unwrap_or_default();
+        Ok(SubscriptionGroupDto::new(group, count))
+    }
+
+    /// Deletes the group; its subscriptions are kept and become ungrouped.
+    pub async fn delete(&self, id: i32, user_id: i32) -> AppResult<()> {
+        match self.repo.delete_for_user(id, user_id).await? {
+            0 => Err(Model::not_found_by("id", id)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn find_for_user(&self, id: i32, user_id: i32) -> AppResult<Model> {
+        self.repo
+            .find_for_user(id, user_id)
+            .await?
+            .ok_or_else(|| Model::not_found_by("id", id))
+    }
+
+    async fn counts(&self, user_id: i32) -> AppResult<HashMap<i32, u64>> {
+        Ok(self
+            .subscription_repo
+            .count_by_group(user_id)
+            .await?
+            .into_iter()
+            .filter_map(|(group_id, count)| {
+                Some((group_id?, u64::try_from(count).unwrap_or_default()))
+            })
+            .collect())
+    }
+
+    fn name(name: &str) -> AppResult<&str> {
+        match name.trim() {
+            "" => Err(AppError::BadRequest(
+                "Group name must not be blank".to_string(),
+            )),
+            name => Ok(name),
+        }
+    }
+
+    fn rules_to_json(rules: &NotificationRules) -> AppResult<JsonValue> {
+        rules.validate().map_err(AppError::BadRequest)?;
+        Ok(serde_json::to_value(rules).context("Failed to serialize notification rules")?)
+    }
+}
@@ -0,0 +1,333 @@
+use crate::domain::notification_rules::NotificationRules;
+use crate::domain::tags;
+use crate::dto::{
+    AssignSubscriptionGroupRequest, CreateSubscriptionRequest, LiveSort, LiveStreamerDto,
+    PagedResponse, SubscriptionDto, SubscriptionFilter, TagDto, UpdateSubscriptionRequest,
+    UpdateSubscriptionRulesRequest, UpdateSubscriptionTagsRequest,
+};
+use crate::error::{AppError, AppResult, Entity};
+use crate::repository::{
+    ChangeAlerts, LiveSessionRepository, StreamerRepository, SubscriptionGroupRepository,
+    SubscriptionRepository,
+};
+use anyhow::Context;
+use chrono::Utc;
+use entity::streamer::Model as StreamerModel;
+use entity::subscription::Model;
+use entity::subscription_group::Model as SubscriptionGroupModel;
+use sea_orm::JsonValue;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU64;
+use std::sync::Arc;
+
+pub struct SubscriptionService {
+    repo: Arc<SubscriptionRepository>,
+    streamer_repo: Arc<StreamerRepository>,
+    live_session_repo: Arc<LiveSessionRepository>,
+    group_repo: Arc<SubscriptionGroupRepository>,
+}
+
+impl SubscriptionService {
+    pub fn new(
+        repo: Arc<SubscriptionRepository>,
+        streamer_repo: Arc<StreamerRepository>,
+        live_session_repo: Arc<LiveSessionRepository>,
+        group_repo: Arc<SubscriptionGroupRepository>,
+    ) -> Self {
+        Self {
+            repo,
+            streamer_repo,
+            live_session_repo,
+            group_repo,
+        }
+    }
+
+    pub async fn create(
+        &self,
+        user_id: i32,
+        data: CreateSubscriptionRequest,
+    ) -> AppResult<SubscriptionDto> {
+        let rules = Self::rules_to_json(&data.rules)?;
+        let tags = tags::normalize(&data.tags).map_err(AppError::BadRequest)?;
+        if let Some(group_id) = data.group_id {
+            self.ensure_group(group_id, user_id).await?;
+        }
+        let streamer = self
+            .streamer_repo
+            .find_by_id(data.streamer_id)
+            .await?
+            .ok_or_else(|| StreamerModel::not_found_by("id", data.streamer_id))?;
+        // A creator's rooms share one subscription, so the user is notified once
+        // however many platforms they stream on.
+        if let Some(creator_id) = streamer.creator_id
+            && self
+                .repo
+                .find_for_user_by_creator(user_id, creator_id)
+                .await?
+                .is_some()
+        {
+            return Err(Model::duplicated_by("creator_id", creator_id));
+        }
+
+        let subscription = self
+            .repo
+            .create(
+                user_id,
+                streamer.id,
+                rules,
+                ChangeAlerts {
+                    title: data.notify_title_change,
+                    category: data.notify_category_change,
+                    announcement: data.notify_announcement_change,
+                },
+                data.group_id,
+            )
+            .await?;
+        self.repo.replace_tags(subscription.id, &tags).await?;
+        Ok(SubscriptionDto::new(subscription, streamer, tags))
+    }
+
+    pub async fn find(&self, id: i32, user_id: i32) -> AppResult<SubscriptionDto> {
+        let (subscription, streamer) = self.find_with_streamer(id, user_id).await?;
+        self.to_dto(subscription, streamer).await
+    }
+
+    pub async fn list(
+        &self,
+        user_id: i32,
+        filter: &SubscriptionFilter,
+        page: u64,
+        page_size: NonZeroU64,
+    ) -> AppResult<PagedResponse<SubscriptionDto>> {
+        let (total, items) = self
+            .repo
+            .list_for_user(user_id, filter, page - 1, page_size.get())
+            .await?;
+        let mut tags = self
+            .tags_by_subscription(
+                items
+                    .iter()
+                    .map(|(subscription, _)| subscription.id)
+                    .collect(),
+            )
+            .await?;
+        let items = items
+            .into_iter()
+            .filter_map(|(subscription, streamer)| {
+                let tags = tags.remove(&subscription.id).unwrap_or_default();
+                streamer.map(|streamer| SubscriptionDto::new(subscription, streamer, tags))
+            })
+            .collect();
+        Ok(PagedResponse::new(items, total, page, page_size))
+    }
+
+    /// Tags of the user's subscriptions with how often each is used.
+    pub async fn tags(&self, user_id: i32) -> AppResult<Vec<TagDto>> {
+        Ok(self
+            .repo
+            .count_tags(user_id)
+            .await?
+            .into_iter()
+            .map(|(tag, count)| TagDto {
+                tag,
+                subscription_count: u64::try_from(count).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    /// Currently live subscribed streamers, built from the state persisted by the
+    /// live status job. A subscription to a room of a creator also lists the
+    /// creator's other live rooms.
+    pub async fn live(
+        &self,
+        user_id: i32,
+        filter: &SubscriptionFilter,
+        sort: LiveSort,
+    ) -> AppResult<Vec<LiveStreamerDto>> {
+        let subscribed: Vec<_> = self
+            .repo
+            .find_all_for_user(user_id, filter)
+            .await?
+            .into_iter()
+            .filter_map(|(subscription, streamer)| streamer.map(|s| (subscription, s)))
+            .collect();
+
+        let mut creator_ids: Vec<i32> = subscribed
+            .iter()
+            .filter_map(|(_, streamer)| streamer.creator_id)
+            .collect();
+        creator_ids.sort_unstable();
+        creator_ids.dedup();
+        let mut rooms: HashMap<i32, Vec<StreamerModel>> = HashMap::new();
+        for room in self.streamer_repo.find_by_creators(creator_ids).await? {
+            if let Some(creator_id) = room.creator_id {
+                rooms.entry(creator_id).or_default().push(room);
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut live = Vec::new();
+        for (subscription, streamer) in subscribed {
+            let candidates = match streamer.creator_id.and_then(|id| rooms.get(&id)) {
+                Some(rooms) => rooms.clone(),
+                None => vec![streamer],
+            };
+            for room in candidates {
+                if room.is_live && seen.insert(room.id) {
+                    live.push((subscription.clone(), room));
+                }
+            }
+        }
+        if live.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let started_at: HashMap<i32, _> = self
+            .live_session_repo
+            .find_open(live.iter().map(|(_, streamer)| streamer.id).collect())
+            .await?
+            .into_iter()
+            .map(|session| (session.streamer_id, session.started_at))
+            .collect();
+
+        let now = Utc::now().naive_utc();
+        let mut items: Vec<LiveStreamerDto> = live
+            .into_iter()
+            .map(|(subscription, streamer)| {
+                let started_at = started_at
+                    .get(&streamer.id)
+                    .copied()
+                    .or(streamer.live_started_at)
+                    .or(streamer.last_checked_at)
+                    .unwrap_or(now);
+                LiveStreamerDto::new(&subscription, streamer, started_at, now)
+            })
+            .collect();
+
+        match sort {
+            LiveSort::Viewers => items.sort_by_key(|item| Reverse(item.status.viewer_count)),
+            LiveSort::StartedAt => items.sort_by_key(|item| Reverse(item.status.started_at)),
+            LiveSort::Platform => items.sort_by(|a, b| {
+                a.streamer
+                    .platform
+                    .cmp(&b.streamer.platform)
+                    .then(b.status.viewer_count.cmp(&a.status.viewer_count))
+            }),
+        }
+        Ok(items)
+    }
+
+    pub async fn update_rules(
+        &self,
+        id: i32,
+        user_id: i32,
+        data: UpdateSubscriptionRulesRequest,
+    ) -> AppResult<SubscriptionDto> {
+        let rules = Self::rules_to_json(&data.rules)?;
+        let (subscription, streamer) = self.find_with_streamer(id, user_id).await?;
+        let subscription = self.repo.update_rules(subscription, rules).await?;
+        self.to_dto(subscription, streamer).await
+    }
+
+    pub async fn update_group(
+        &self,
+        id: i32,
+        user_id: i32,
+        data: AssignSubscriptionGroupRequest,
+    ) -> AppResult<SubscriptionDto> {
+        if let Some(group_id) = data.group_id {
+            self.ensure_group(group_id, user_id).await?;
+        }
+        let (subscription, streamer) = self.find_with_streamer(id, user_id).await?;
+        let subscription = self.repo.update_group(subscription, data.group_id).await?;
+        self.to_dto(subscription, streamer).await
+    }
+
+    pub async fn update_tags(
+        &self,
+        id: i32,
+        user_id: i32,
+        data: UpdateSubscriptionTagsRequest,
+    ) -> AppResult<SubscriptionDto> {
+        let tags = tags::normalize(&data.tags).map_err(AppError::BadRequest)?;
+        let (subscription, streamer) = self.find_with_streamer(id, user_id).await?;
+        self.repo.replace_tags(subscription.id, &tags).await?;
+        Ok(SubscriptionDto::new(subscription, streamer, tags))
+    }
+
+    pub async fn update(
+        &self,
+        id: i32,
+        user_id: i32,
+        data: UpdateSubscriptionRequest,
+    ) -> AppResult<SubscriptionDto> {
+        let (subscription, streamer) = self.find_with_streamer(id, user_id).await?;
+        let subscription = self
+            .repo
+            .update_change_alerts(
+                subscription,
+                data.notify_title_change,
+                data.notify_category_change,
+                data.notify_announcement_change,
+            )
+            .await?;
+        self.to_dto(subscription, streamer).await
+    }
+
+    /// Ids of the users subscribed to the streamer.
+    pub async fn subscriber_ids(&self, streamer_id: i32) -> AppResult<Vec<i32>> {
+        self.repo.find_user_ids_by_streamer(streamer_id).await
+    }
+
+    pub async fn delete(&self, id: i32, user_id: i32) -> AppResult<()> {
+        match self.repo.delete_for_user(id, user_id).await? {
+            0 => Err(Model::not_found_by("id", id)),
+            _ => Ok(()),
+        }
+    }
+
+    async fn find_with_streamer(&self, id: i32, user_id: i32) -> AppResult<(Model, StreamerModel)> {
+        match self.repo.find_for_user(id, user_id).await? {
+            Some((subscription, Some(streamer))) => Ok((subscription, streamer)),
+            _ => Err(Model::not_found_by("id", id)),
+        }
+    }
+
+    async fn ensure_group(&self, group_id: i32, user_id: i32) -> AppResult<()> {
+        self.group_repo
+            .find_for_user(group_id, user_id)
+            .await?
+            .map(|_| ())
+            .ok_or_else(|| SubscriptionGroupModel::not_found_by("id", group_id))
+    }
+
+    async fn tags_by_subscription(
+        &self,
+        subscription_ids: Vec<i32>,
+    ) -> AppResult<HashMap<i32, Vec<String>>> {
+        let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+        for tag in self.repo.find_tags(subscription_ids).await? {
+            tags.entry(tag.subscription_id).or_default().push(tag.tag);
+        }
+        Ok(tags)
+    }
+
+    async fn to_dto(
+        &self,
+        subscription: Model,
+        streamer: StreamerModel,
+    ) -> AppResult<SubscriptionDto> {
+        let tags = self
+            .tags_by_subscription(vec![subscription.id])
+            .await?
+            .remove(&subscription.id)
+            .unwrap_or_default();
+        Ok(SubscriptionDto::new(subscription, streamer, tags))
+    }
+
+    fn rules_to_json(rules: &NotificationRules) -> AppResult<JsonValue> {
+        rules.validate().map_err(AppError::BadRequest)?;
+        Ok(serde_json::to_value(rules).context("Failed to serialize notification rules")?)
+    }
+}
@@ -0,0 +1,489 @@
+use crate::domain::media::MediaKind;
+use crate::domain::notification_rules::NotificationRules;
+use crate::domain::tags;
+use crate::domain::transfer::{
+    self, ChannelEntry, FORMAT_VERSION, GroupEntry, SubscriptionEntry, TransferDocument,
+};
+use crate::dto::{
+    ChannelImportResult, GroupImportResult, ImportReportDto, ImportStatus, SubscriptionFilter,
+    SubscriptionImportResult,
+};
+use crate::error::{AppError, AppResult, Entity};
+use crate::notification::NotificationChannel;
+use crate::repository::{
+    ChangeAlerts, MediaAssetRepository, NotificationChannelRepository, StreamerRepository,
+    SubscriptionGroupRepository, SubscriptionRepository, UserRepository,
+};
+use anyhow::Context;
+use chrono::Utc;
+use entity::notification_channel::Model as ChannelModel;
+use entity::subscription::Model as SubscriptionModel;
+use entity::subscription_group::Model as SubscriptionGroupModel;
+use entity::user::Model as UserModel;
+use live_platform::{LivePlatformProvider, Platform};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::Arc;
+
+pub struct TransferService {
+    user_repo: Arc<UserRepository>,
+    subscription_repo: Arc<SubscriptionRepository>,
+    group_repo: Arc<SubscriptionGroupRepository>,
+    streamer_repo: Arc<StreamerRepository>,
+    channel_repo: Arc<NotificationChannelRepository>,
+    media_repo: Arc<MediaAssetRepository>,
+    live_platform_provider: Arc<LivePlatformProvider>,
+}
+
+/// State shared by the subscription entries of one import.
+struct SubscriptionImport {
+    user_id: i32,
+    dry_run: bool,
+    /// Existing subscriptions of the user by streamer id, with their tags.
+    existing: HashMap<i32, (SubscriptionModel, Vec<String>)>,
+    /// Group ids by name; `None` for groups that a dry run would create.
+    groups: HashMap<String, Option<i32>>,
+    seen: HashSet<(Platform, String)>,
+}
+
+impl TransferService {
+    pub fn new(
+        user_repo: Arc<UserRepository>,
+        subscription_repo: Arc<SubscriptionRepository>,
+        group_repo: Arc<SubscriptionGroupRepository>,
+        streamer_repo: Arc<StreamerRepository>,
+        channel_repo: Arc<NotificationChannelRepository>,
+        media_repo: Arc<MediaAssetRepository>,
+        live_platform_provider: Arc<LivePlatformProvider>,
+    ) -> Self {
+        Self {
+            user_repo,
+            subscription_repo,
+            group_repo,
+            streamer_repo,
+            channel_repo,
+            media_repo,
+            live_platform_provider,
+        }
+    }
+
+    /// Id of the user with the given username; used by the command line tools.
+    pub async fn find_user_id(&self, username: &str) -> AppResult<i32> {
+        self.user_repo
+            .find_by_username(username)
+            .await?
+            .map(|user| user.id)
+            .ok_or_else(|| UserModel::not_found_by("username", username))
+    }
+
+    pub async fn export(&self, user_id: i32) -> AppResult<TransferDocument> {
+        let groups = self.group_repo.list_for_user(user_id).await?;
+        let group_names: HashMap<i32, String> = groups
+            .iter()
+            .map(|group| (group.id, group.name.clone()))
+            .collect();
+        let groups = groups
+            .into_iter()
+            .map(|group| GroupEntry {
+                name: group.name,
+                rules: serde_json::from_value(group.rules).unwrap_or_default(),
+            })
+            .collect();
+
+        let subscriptions = self
+            .subscription_repo
+            .find_all_for_user(user_id, &SubscriptionFilter::default())
+            .await?;
+        let mut tags = self.tags_by_subscription(&subscriptions).await?;
+        let subscriptions = subscriptions
+            .into_iter()
+            .filter_map(|(subscription, streamer)| {
+                let streamer = streamer?;
+                Some(SubscriptionEntry {
+                    platform: streamer.platform,
+                    room_id: streamer.room_id,
+                    room_url: streamer.room_url,
+                    name: streamer.name,
+                    rules: Some(serde_json::from_value(subscription.rules).unwrap_or_default()),
+                    notify_title_change: Some(subscription.notify_title_change),
+                    notify_category_change: Some(subscription.notify_category_change),
+                    notify_announcement_change: Some(subscription.notify_announcement_change),
+                    group: subscription
+                        .group_id
+                        .and_then(|id| group_names.get(&id).cloned()),
+                    tags: Some(tags.remove(&subscription.id).unwrap_or_default()),
+                })
+            })
+            .collect();
+        let channels = self
+            .channel_repo
+            .list_for_user(user_id)
+            .await?
+            .into_iter()
+            .map(|channel| ChannelEntry {
+                channel: channel.channel,
+                destination: channel.destination,
+                metadata: serde_json::from_value(channel.metadata).unwrap_or_default(),
+                enabled: channel.enabled,
+            })
+            .collect();
+
+        Ok(TransferDocument {
+            version: FORMAT_VERSION,
+            exported_at: Some(Utc::now().naive_utc()),
+            groups,
+            subscriptions,
+            channels,
+        })
+    }
+
+    /// Imports an export document or a plain-text list of room URLs.
+    ///
+    /// Unknown rooms are resolved on their platform. Existing groups, subscriptions
+    /// and channels are never modified: identical ones are reported as `exists`,
+    /// differing ones as `conflict`. With `dry_run` nothing is written.
+    pub async fn import(
+        &self,
+        user_id: i32,
+        input: &str,
+        dry_run: bool,
+    ) -> AppResult<ImportReportDto> {
+        let document = transfer::parse(input).map_err(AppError::BadRequest)?;
+
+        let existing_groups = self.group_repo.list_for_user(user_id).await?;
+        let mut group_ids: HashMap<String, Option<i32>> = existing_groups
+            .iter()
+            .map(|group| (group.name.clone(), Some(group.id)))
+            .collect();
+        let mut groups = Vec::with_capacity(document.groups.len());
+        for entry in &document.groups {
+            groups.push(
+                self.import_group(user_id, entry, &existing_groups, &mut group_ids, dry_run)
+                    .await?,
+            );
+        }
+
+        let existing = self
+            .subscription_repo
+            .find_all_for_user(user_id, &SubscriptionFilter::default())
+            .await?;
+        let mut tags = self.tags_by_subscription(&existing).await?;
+        let mut import = SubscriptionImport {
+            user_id,
+            dry_run,
+            existing: existing
+                .into_iter()
+                .map(|(subscription, _)| {
+                    let tags = tags.remove(&subscription.id).unwrap_or_default();
+                    (subscription.streamer_id, (subscription, tags))
+                })
+                .collect(),
+            groups: group_ids,
+            seen: HashSet::new(),
+        };
+        let mut subscriptions = Vec::with_capacity(document.subscriptions.len());
+        for entry in &document.subscriptions {
+            subscriptions.push(self.import_subscription(entry, &mut import).await?);
+        }
+
+        let existing_channels = self.channel_repo.list_for_user(user_id).await?;
+        let mut seen = HashSet::new();
+        let mut channels = Vec::with_capacity(document.channels.len());
+        for entry in &document.channels {
+            channels.push(
+                self.import_channel(user_id, entry, &existing_channels, &mut seen, dry_run)
+                    .await?,
+            );
+        }
+
+        Ok(ImportReportDto::new(
+            dry_run,
+            groups,
+            subscriptions,
+            channels,
+        ))
+    }
+
+    async fn import_group(
+        &self,
+        user_id: i32,
+        entry: &GroupEntry,
+        existing: &[SubscriptionGroupModel],
+        group_ids: &mut HashMap<String, Option<i32>>,
+        dry_run: bool,
+    ) -> AppResult<GroupImportResult> {
+        let name = entry.name.trim();
+        let mut result = GroupImportResult {
+            name: name.to_string(),
+            status: ImportStatus::Invalid,
+            message: None,
+        };
+        if name.is_empty() || name.chars().count() > 64 {
+            result.message = Some("Group name must be 1 to 64 characters".to_string());
+            return Ok(result);
+        }
+        if let Err(message) = entry.rules.validate() {
+            result.message = Some(message);
+            return Ok(result);
+        }
+
+        if let Some(current) = existing.iter().find(|group| group.name == name) {
+            let rules: NotificationRules =
+                serde_json::from_value(current.rules.clone()).unwrap_or_default();
+            if rules == entry.rules {
+                result.status = ImportStatus::Exists;
+            } else {
+                result.status = ImportStatus::Conflict;
+                result.message = Some("Group already exists with different rules".to_string());
+            }
+            return Ok(result);
+        }
+        if group_ids.contains_key(name) {
+            result.status = ImportStatus::Exists;
+            result.message = Some("Listed more than once".to_string());
+            return Ok(result);
+        }
+
+        let id = if dry_run {
+            None
+        } else {
+            let rules = serde_json::to_value(&entry.rules)
+                .context("Failed to serialize notification rules")?;
+            Some(self.group_repo.create(user_id, name, rules).await?.id)
+        };
+        group_ids.insert(name.to_string(), id);
+        result.status = ImportStatus::Created;
+        Ok(result)
+    }
+
+    async fn import_subscription(
+        &self,
+        entry: &SubscriptionEntry,
+        import: &mut SubscriptionImport,
+    ) -> AppResult<SubscriptionImportResult> {
+        let mut result = SubscriptionImportResult {
+            input: entry.label(),
+            platform: None,
+            room_id: None,
+            name: (!entry.name.is_empty()).then(|| entry.name.clone()),
+            status: ImportStatus::Invalid,
+            message: None,
+        };
+
+        let (platform, room_id) = match entry.room() {
+            Ok(room) => room,
+            Err(message) => {
+                result.message = Some(message);
+                return Ok(result);
+            }
+        };
+        result.platform = Some(platform.to_string());
+        result.room_id = Some(room_id.clone());
+        if let Some(Err(message)) = entry.rules.as_ref().map(NotificationRules::validate) {
+            result.message = Some(message);
+            return Ok(result);
+        }
+        let tags = match entry.tags.as_deref().map(tags::normalize).transpose() {
+            Ok(tags) => tags,
+            Err(message) => {
+                result.message = Some(message);
+                return Ok(result);
+            }
+        };
+        let group = match entry.group.as_deref().map(str::trim) {
+            Some(name) => match import.groups.get(name) {
+                Some(id) => Some(*id),
+                None => {
+                    result.message = Some(format!("Unknown group: {name}"));
+                    return Ok(result);
+                }
+            },
+            None => None,
+        };
+        if !import.seen.insert((platform, room_id.clone())) {
+            result.status = ImportStatus::Exists;
+            result.message = Some("Listed more than once".to_string());
+            return Ok(result);
+        }
+
+        let streamer = match self.streamer_repo.find_by_room(platform, &room_id).await? {
+            Some(streamer) => Some(streamer),
+            None => {
+                let info = match self
+                    .live_platform_provider
+                    .fetch_streamer_info(platform, &room_id)
+                    .await
+                {
+                    Ok(info) => info,
+                    Err(err) => {
+                        result.status = ImportStatus::Unresolved;
+                        result.message = Some(format!("Failed to resolve room: {err}"));
+                        return Ok(result);
+                    }
+                };
+                result.name = Some(info.name.clone());
+                if import.dry_run {
+                    None
+                } else {
+                    let streamer = self.streamer_repo.upsert_info(&info).await?;
+                    self.media_repo
+                        .register(MediaKind::Avatar, &streamer.avatar)
+                        .await?;
+                    Some(streamer)
+                }
+            }
+        };
+
+        if let Some(streamer) = &streamer {
+            result.name = Some(streamer.name.clone());
+            if let Some((subscription, existing_tags)) = import.existing.get(&streamer.id) {
+                let rules: NotificationRules =
+                    serde_json::from_value(subscription.rules.clone()).unwrap_or_default();
+                let mut differences = Vec::new();
+                if entry.rules.as_ref().is_some_and(|wanted| *wanted != rules) {
+                    differences.push("rules");
+                }
+                if entry
+                    .notify_title_change
+                    .is_some_and(|wanted| wanted != subscription.notify_title_change)
+                {
+                    differences.push("notify_title_change");
+                }
+                if entry
+                    .notify_category_change
+                    .is_some_and(|wanted| wanted != subscription.notify_category_change)
+                {
+                    differences.push("notify_category_change");
+                }
+                if entry
+                    .notify_announcement_change
+                    .is_some_and(|wanted| wanted != subscription.notify_announcement_change)
+                {
+                    differences.push("notify_announcement_change");
+                }
+                if group.is_some_and(|id| id.is_none() || id != subscription.group_id) {
+                    differences.push("group");
+                }
+                if tags.as_ref().is_some_and(|tags| {
+                    tags.iter().collect::<HashSet<_>>() != existing_tags.iter().collect()
+                }) {
+                    differences.push("tags");
+                }
+                if differences.is_empty() {
+                    result.status = ImportStatus::Exists;
+                } else {
+                    result.status = ImportStatus::Conflict;
+                    result.message = Some(format!(
+                        "Already subscribed with different {}",
+                        differences.join(", ")
+                    ));
+                }
+                return Ok(result);
+            }
+        }
+
+        if let (Some(streamer), false) = (streamer, import.dry_run) {
+            let rules = serde_json::to_value(entry.rules.clone().unwrap_or_default())
+                .context("Failed to serialize notification rules")?;
+            let subscription = self
+                .subscription_repo
+                .create(
+                    import.user_id,
+                    streamer.id,
+                    rules,
+                    ChangeAlerts {
+                        title: entry.notify_title_change.unwrap_or_default(),
+                        category: entry.notify_category_change.unwrap_or_default(),
+                        announcement: entry.notify_announcement_change.unwrap_or_default(),
+                    },
+                    group.flatten(),
+                )
+                .await?;
+            if let Some(tags) = &tags {
+                self.subscription_repo
+                    .replace_tags(subscription.id, tags)
+                    .await?;
+            }
+        }
+        result.status = ImportStatus::Created;
+        Ok(result)
+    }
+
+    async fn import_channel(
+        &self,
+        user_id: i32,
+        entry: &ChannelEntry,
+        existing: &[ChannelModel],
+        seen: &mut HashSet<(NotificationChannel, String)>,
+        dry_run: bool,
+    ) -> AppResult<ChannelImportResult> {
+        let destination = entry.destination.trim();
+        let mut result = ChannelImportResult {
+            channel: entry.channel.clone(),
+            destination: destination.to_string(),
+            status: ImportStatus::Invalid,
+            message: None,
+        };
+
+        let Ok(channel) = NotificationChannel::from_str(&entry.channel) else {
+            result.message = Some(format!(
+                "Unsupported notification channel: {}",
+                entry.channel
+            ));
+            return Ok(result);
+        };
+        if destination.is_empty() || destination.len() > 2048 {
+            result.message = Some("Destination must be 1 to 2048 characters".to_string());
+            return Ok(result);
+        }
+        if !seen.insert((channel, destination.to_string())) {
+            result.status = ImportStatus::Exists;
+            result.message = Some("Listed more than once".to_string());
+            return Ok(result);
+        }
+        let metadata = serde_json::to_value(&entry.metadata)
+            .context("Failed to serialize channel metadata")?;
+
+        if let Some(current) = existing
+            .iter()
+            .find(|model| model.channel == channel.as_str() && model.destination == destination)
+        {
+            if current.metadata == metadata && current.enabled == entry.enabled {
+                result.status = ImportStatus::Exists;
+            } else {
+                result.status = ImportStatus::Conflict;
+                result.message = Some("Channel already exists with different settings".to_string());
+            }
+            return Ok(result);
+        }
+
+        if !dry_run {
+            self.channel_repo
+                .create(
+                    user_id,
+                    channel.as_str(),
+                    destination,
+                    metadata,
+                    entry.enabled,
+                )
+                .await?;
+        }
+        result.status = ImportStatus::Created;
+        Ok(result)
+    }
+
+    async fn tags_by_subscription<T>(
+        &self,
+        subscriptions: &[(SubscriptionModel, T)],
+    ) -> AppResult<HashMap<i32, Vec<String>>> {
+        let ids = subscriptions
+            .iter()
+            .map(|(subscription, _)| subscription.id)
+            .collect();
+        let mut tags: HashMap<i32, Vec<String>> = HashMap::new();
+        for tag in self.subscription_repo.find_tags(ids).await? {
+            tags.entry(tag.subscription_id).or_default().push(tag.tag);
+        }
+        Ok(tags)
+    }
+}
@@ -0,0 +1,143 @@
+use crate::config::ViewerStatsConfig;
+use crate::domain::live_state::{LiveState, LiveTracker};
+use crate::domain::viewer_series::{self, ViewerBucket};
+use crate::dto::{ViewerPointDto, ViewerSeriesDto, ViewerSeriesQuery};
+use crate::error::{AppError, AppResult};
+use crate::repository::ViewerSampleRepository;
+use chrono::{Duration, NaiveDateTime, Utc};
+use entity::streamer::Model as StreamerModel;
+use std::sync::Arc;
+
+const DEFAULT_SERIES_RANGE_HOURS: i64 = 24;
+const DEFAULT_SERIES_BUCKET_SECS: u32 = 300;
+const MAX_SERIES_POINTS: i64 = 2000;
+
+pub struct ViewerStatsService {
+    repo: Arc<ViewerSampleRepository>,
+    config: ViewerStatsConfig,
+}
+
+impl ViewerStatsService {
+    pub fn new(repo: Arc<ViewerSampleRepository>, config: ViewerStatsConfig) -> Self {
+        Self { repo, config }
+    }
+
+    /// Records the current viewer count of a live streamer at the configured resolution.
+    pub async fn record_sample(&self, streamer: &StreamerModel) -> AppResult<()> {
+        // An unconfirmed drop keeps the last live viewer count; don't sample it again.
+        if LiveTracker::of(streamer).state != LiveState::Live {
+            return Ok(());
+        }
+
+        let sampled_at = streamer
+            .last_checked_at
+            .unwrap_or_else(|| Utc::now().naive_utc());
+        let resolution = self.config.sample_resolution_secs;
+        let bucket = ViewerBucket::single(
+            viewer_series::align(sampled_at, i64::from(resolution)),
+            streamer.viewer_count,
+        );
+
+        self.repo
+            .record(streamer.id, resolution as i32, &bucket)
+            .await
+    }
+
+    pub async fn series(
+        &self,
+        streamer_id: i32,
+        query: ViewerSeriesQuery,
+    ) -> AppResult<ViewerSeriesDto> {
+        let to = query.to.unwrap_or_else(|| Utc::now().naive_utc());
+        let from = query
+            .from
+            .unwrap_or(to - Duration::hours(DEFAULT_SERIES_RANGE_HOURS));
+        if from >= to {
+            return Err(AppError::BadRequest("from must be before to".into()));
+        }
+
+        let bucket_secs = query.bucket_secs.unwrap_or(DEFAULT_SERIES_BUCKET_SECS);
+        if bucket_secs < self.config.sample_resolution_secs {
+            return Err(AppError::BadRequest(format!(
+                "bucket_secs must be >= {}",
+                self.config.sample_resolution_secs
+            )));
+        }
+        if (to - from).num_seconds() / i64::from(bucket_secs) > MAX_SERIES_POINTS {
+            return Err(AppError::BadRequest(format!(
+                "range is too large for bucket_secs={}, at most {} points are returned",
+                bucket_secs, MAX_SERIES_POINTS
+            )));
+        }
+
+        let samples = self.repo.find_range(streamer_id, from, to).await?;
+        let points = viewer_series::rebucket(
+            samples.iter().map(ViewerBucket::from),
+            i64::from(bucket_secs),
+        )
+        .into_iter()
+        .map(|bucket| ViewerPointDto {
+            bucket_start: bucket.start,
+            samples: bucket.sample_count,
+            min: bucket.min,
+            max: bucket.max,
+            avg: bucket.avg(),
+        })
+        .collect();
+
+        Ok(ViewerSeriesDto {
+            streamer_id,
+            from,
+            to,
+            bucket_secs,
+            points,
+        })
+    }
+
+    /// Folds raw samples older than the configured age into coarse buckets.
+    pub async fn downsample(&self, now: NaiveDateTime) -> AppResult<()> {
+        let from_resolution = self.config.sample_resolution_secs as i32;
+        let to_resolution = self.config.downsample_resolution_secs as i32;
+        if from_resolution == to_resolution {
+            return Ok(());
+        }
+
+        let cutoff = viewer_series::align(
+            now - Duration::hours(i64::from(self.config.downsample_after_hours)),
+            i64::from(to_resolution),
+        );
+
+        let streamer_ids = self
+            .repo
+            .find_streamers_with_samples_before(from_resolution, cutoff)
+            .await?;
+
+        for streamer_id in streamer_ids {
+            let samples = self
+                .repo
+                .find_before(streamer_id, from_resolution, cutoff)
+                .await?;
+            let buckets = viewer_series::rebucket(
+                samples.iter().map(ViewerBucket::from),
+                i64::from(to_resolution),
+            );
+            self.repo
+                .replace_with_downsampled(
+                    streamer_id,
+                    from_resolution,
+                    cutoff,
+                    to_resolution,
+                    &buckets,
+                )
+                .await?;
+            tracing::debug!(
+                streamer_id,
+                samples = samples.len(),
+                buckets = buckets.len(),
+                "downsampled viewer samples"
+            );
+        }
+
+        Ok(())
+    }
+}
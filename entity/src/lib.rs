@@ -2,4 +2,17 @@
 
 pub mod prelude;
 
+pub mod creator;
+pub mod live_session;
+pub mod live_state_transition;
+pub mod media_asset;
+pub mod notification_channel;
+pub mod notification_outbox;
+pub mod share_page;
+pub mod streamer;
+pub mod streamer_change;
+pub mod subscription;
+pub mod subscription_group;
+pub mod subscription_tag;
 pub mod user;
+pub mod viewer_sample;
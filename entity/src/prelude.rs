@@ -1,3 +1,16 @@
 //! `SeaORM` Entity, @generated by sea-orm-codegen 2.0.0-rc.19
 
+pub use super::creator::Entity as Creator;
+pub use super::live_session::Entity as LiveSession;
+pub use super::live_state_transition::Entity as LiveStateTransition;
+pub use super::media_asset::Entity as MediaAsset;
+pub use super::notification_channel::Entity as NotificationChannel;
+pub use super::notification_outbox::Entity as NotificationOutbox;
+pub use super::share_page::Entity as SharePage;
+pub use super::streamer::Entity as Streamer;
+pub use super::streamer_change::Entity as StreamerChange;
+pub use super::subscription::Entity as Subscription;
+pub use super::subscription_group::Entity as SubscriptionGroup;
+pub use super::subscription_tag::Entity as SubscriptionTag;
 pub use super::user::Entity as User;
+pub use super::viewer_sample::Entity as ViewerSample;
@@ -13,8 +13,18 @@ pub struct Model {
     #[sea_orm(unique)]
     pub email: String,
     pub password: String,
+    #[sea_orm(unique)]
+    pub feed_token: Option<String>,
     pub created_at: DateTime,
     pub updated_at: DateTime,
+    #[sea_orm(has_many)]
+    pub notification_channels: HasMany<super::notification_channel::Entity>,
+    #[sea_orm(has_many)]
+    pub share_pages: HasMany<super::share_page::Entity>,
+    #[sea_orm(has_many)]
+    pub subscription_groups: HasMany<super::subscription_group::Entity>,
+    #[sea_orm(has_many)]
+    pub subscriptions: HasMany<super::subscription::Entity>,
 }
 
 impl ActiveModelBehavior for ActiveModel {}
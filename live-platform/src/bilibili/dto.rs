@@ -31,3 +31,8 @@ pub struct MasterInfo {
     pub uname: String,
     pub face: String,
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RoomNewsResp {
+    pub content: String,
+}
@@ -1,10 +1,10 @@
 mod dto;
 
-use crate::bilibili::dto::{MasterInfo, MasterInfoResp, RespWrapper, RoomInfoResp};
+use crate::bilibili::dto::{MasterInfo, MasterInfoResp, RespWrapper, RoomInfoResp, RoomNewsResp};
 use crate::types::{LivePlatform, LiveStatus, Platform, StreamerInfo};
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
-use chrono::NaiveDateTime;
+use chrono::{FixedOffset, NaiveDateTime, TimeZone};
 use log::error;
 
 const BASE_URL: &str = "https://live.bilibili.com";
@@ -70,6 +70,31 @@ impl Bilibili {
         }
         Ok(resp.data.info)
     }
+
+    async fn fetch_room_news(&self, platform_streamer_id: &str) -> Result<RoomNewsResp> {
+        let resp = self
+            .client
+            .get("https://api.live.bilibili.com/room_ex/v1/RoomNews/get")
+            .query(&[("roomid", platform_streamer_id)])
+            .send()
+            .await
+            .context("Failed to query bilibili room news")?
+            .json::<RespWrapper<RoomNewsResp>>()
+            .await
+            .context("Failed to parse room news response")?;
+        if resp.code != 0 {
+            error!(
+                "Failed to query bilibili room news, resp code: {}, message: {}",
+                resp.code, resp.message
+            );
+            return Err(anyhow!(
+                "Failed to query bilibili room news, resp code: {}, message: {}",
+                resp.code,
+                resp.message
+            ));
+        }
+        Ok(resp.data)
+    }
 }
 
 #[async_trait]
@@ -96,8 +121,16 @@ impl LivePlatform for Bilibili {
         let resp = self.fetch_room_info(platform_streamer_id).await?;
         let mut start_time: Option<NaiveDateTime> = None;
         if resp.live_status == 1 && resp.live_time != "0000-00-00 00:00:00" {
+            // `live_time` is local Beijing time (UTC+8); everything else works in UTC.
             start_time =
-                NaiveDateTime::parse_from_str(resp.live_time.as_str(), "%Y-%m-%d %H:%M:%S").ok();
+                NaiveDateTime::parse_from_str(resp.live_time.as_str(), "%Y-%m-%d %H:%M:%S")
+                    .ok()
+                    .and_then(|local| {
+                        FixedOffset::east_opt(8 * 3600)?
+                            .from_local_datetime(&local)
+                            .single()
+                    })
+                    .map(|time| time.naive_utc());
         }
         Ok(LiveStatus {
             is_live: resp.live_status == 1,
@@ -108,6 +141,11 @@ impl LivePlatform for Bilibili {
             cover_image: resp.user_cover,
         })
     }
+
+    async fn fetch_announcement(&self, platform_streamer_id: &str) -> Result<String> {
+        let news = self.fetch_room_news(platform_streamer_id).await?;
+        Ok(news.content.trim().to_string())
+    }
 }
 
 #[cfg(test)]
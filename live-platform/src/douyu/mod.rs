@@ -113,6 +113,11 @@ impl LivePlatform for Douyu {
             cover_image: response.room.cover_src,
         })
     }
+
+    async fn fetch_announcement(&self, platform_streamer_id: &str) -> Result<String> {
+        let response = self.fetch_betard_info(platform_streamer_id).await?;
+        Ok(response.room.show_details.trim().to_string())
+    }
 }
 
 #[cfg(test)]
@@ -63,4 +63,13 @@ impl LivePlatformProvider {
         let provider = self.provider(platform)?;
         provider.check_live_status(room_id.as_ref()).await
     }
+
+    pub async fn fetch_announcement(
+        &self,
+        platform: Platform,
+        room_id: impl AsRef<str>,
+    ) -> Result<String> {
+        let provider = self.provider(platform)?;
+        provider.fetch_announcement(room_id.as_ref()).await
+    }
 }
@@ -29,6 +29,50 @@ impl FromStr for Platform {
     }
 }
 
+impl Platform {
+    /// Extracts the platform and room id from a room URL such as
+    /// `https://live.bilibili.com/7734200` or `douyu.com/60937`.
+    pub fn parse_room_url(input: &str) -> Option<(Platform, String)> {
+        let input = input.trim();
+        let without_scheme = input.split_once("://").map_or(input, |(_, rest)| rest);
+        let (authority, rest) = without_scheme
+            .split_once('/')
+            .unwrap_or((without_scheme, ""));
+        let host = authority.rsplit('@').next()?.split(':').next()?;
+        let host = host.to_ascii_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host);
+        let (path, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let path = path.split('#').next().unwrap_or_default();
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let (platform, room_id) = match host {
+            "live.bilibili.com" => {
+                let room_id = match segments.as_slice() {
+                    ["h5" | "blanc", room_id, ..] | [room_id, ..] => *room_id,
+                    [] => return None,
+                };
+                (Platform::Bilibili, room_id)
+            }
+            "douyu.com" | "m.douyu.com" => {
+                let rid = query.split('&').find_map(|pair| pair.strip_prefix("rid="));
+                let room_id = match (segments.as_slice(), rid) {
+                    (["topic", ..], Some(rid)) => rid,
+                    (["beta", room_id, ..], _) | ([room_id, ..], _) => *room_id,
+                    ([], _) => return None,
+                };
+                (Platform::Douyu, room_id)
+            }
+            _ => return None,
+        };
+
+        let valid = match platform {
+            Platform::Bilibili => room_id.chars().all(|c| c.is_ascii_digit()),
+            Platform::Douyu => room_id.chars().all(|c| c.is_ascii_alphanumeric()),
+        };
+        (valid && !room_id.is_empty()).then(|| (platform, room_id.to_string()))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct StreamerInfo {
     pub platform: Platform,
@@ -56,4 +100,60 @@ pub trait LivePlatform: Send + Sync {
     async fn fetch_streamer_info(&self, platform_streamer_id: &str) -> Result<StreamerInfo>;
 
     async fn check_live_status(&self, platform_streamer_id: &str) -> Result<LiveStatus>;
+
+    /// Announcement the streamer posts on the room page, often their schedule.
+    /// Empty when the room has none.
+    async fn fetch_announcement(&self, platform_streamer_id: &str) -> Result<String>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_room_urls() {
+        let cases = [
+            (
+                "https://live.bilibili.com/7734200",
+                Platform::Bilibili,
+                "7734200",
+            ),
+            (
+                "https://live.bilibili.com/h5/7734200?spm_id_from=333",
+                Platform::Bilibili,
+                "7734200",
+            ),
+            (
+                "live.bilibili.com/21452505/",
+                Platform::Bilibili,
+                "21452505",
+            ),
+            ("https://www.douyu.com/60937", Platform::Douyu, "60937"),
+            (
+                "https://www.douyu.com/topic/s15?rid=9999",
+                Platform::Douyu,
+                "9999",
+            ),
+            ("http://m.douyu.com/lpl#top", Platform::Douyu, "lpl"),
+        ];
+        for (url, platform, room_id) in cases {
+            assert_eq!(
+                Platform::parse_room_url(url),
+                Some((platform, room_id.to_string())),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_urls() {
+        for url in [
+            "https://www.youtube.com/watch?v=1",
+            "https://live.bilibili.com/",
+            "https://live.bilibili.com/p/eden",
+            "not a url",
+        ] {
+            assert_eq!(Platform::parse_room_url(url), None, "{url}");
+        }
+    }
 }
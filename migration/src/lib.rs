@@ -1,12 +1,44 @@
 pub use sea_orm_migration::prelude::*;
 
 mod m20251130_070455_create_user_table;
+mod m20251206_091512_create_streamer_table;
+mod m20251206_093044_create_viewer_sample_table;
+mod m20251213_102233_create_live_session_table;
+mod m20251213_104517_create_subscription_table;
+mod m20251213_110902_create_notification_channel_table;
+mod m20251220_083125_create_streamer_change_table;
+mod m20251227_061408_add_feed_token;
+mod m20260103_094215_create_subscription_group_table;
+mod m20260110_081730_create_share_page_table;
+mod m20260117_090412_create_creator_table;
+mod m20260124_071532_add_streamer_polling_schedule;
+mod m20260131_102245_create_live_state_transition_table;
+mod m20260207_083010_create_media_asset_table;
+mod m20260214_091835_add_streamer_announcement;
+mod m20260221_084512_create_notification_outbox_table;
 
 pub struct Migrator;
 
 #[async_trait::async_trait]
 impl MigratorTrait for Migrator {
     fn migrations() -> Vec<Box<dyn MigrationTrait>> {
-        vec![Box::new(m20251130_070455_create_user_table::Migration)]
+        vec![
+            Box::new(m20251130_070455_create_user_table::Migration),
+            Box::new(m20251206_091512_create_streamer_table::Migration),
+            Box::new(m20251206_093044_create_viewer_sample_table::Migration),
+            Box::new(m20251213_102233_create_live_session_table::Migration),
+            Box::new(m20251213_104517_create_subscription_table::Migration),
+            Box::new(m20251213_110902_create_notification_channel_table::Migration),
+            Box::new(m20251220_083125_create_streamer_change_table::Migration),
+            Box::new(m20251227_061408_add_feed_token::Migration),
+            Box::new(m20260103_094215_create_subscription_group_table::Migration),
+            Box::new(m20260110_081730_create_share_page_table::Migration),
+            Box::new(m20260117_090412_create_creator_table::Migration),
+            Box::new(m20260124_071532_add_streamer_polling_schedule::Migration),
+            Box::new(m20260131_102245_create_live_state_transition_table::Migration),
+            Box::new(m20260207_083010_create_media_asset_table::Migration),
+            Box::new(m20260214_091835_add_streamer_announcement::Migration),
+            Box::new(m20260221_084512_create_notification_outbox_table::Migration),
+        ]
     }
 }
@@ -0,0 +1,52 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("streamer")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(string("platform"))
+                    .col(string("room_id"))
+                    .col(string("name"))
+                    .col(text("avatar"))
+                    .col(text("description"))
+                    .col(text("room_url"))
+                    .col(boolean("is_live").default(false))
+                    .col(text("title").default(""))
+                    .col(string("category").default(""))
+                    .col(text("cover_image").default(""))
+                    .col(big_integer("viewer_count").default(0))
+                    .col(timestamp_null("live_started_at"))
+                    .col(timestamp_null("last_checked_at"))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .col(timestamp("updated_at").default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_streamer_platform_room_id")
+                    .table("streamer")
+                    .col("platform")
+                    .col("room_id")
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("streamer").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,52 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("viewer_sample")
+                    .if_not_exists()
+                    .col(big_pk_auto("id"))
+                    .col(integer("streamer_id"))
+                    .col(timestamp("bucket_start"))
+                    .col(integer("resolution_secs"))
+                    .col(integer("sample_count"))
+                    .col(big_integer("min_viewers"))
+                    .col(big_integer("max_viewers"))
+                    .col(big_integer("sum_viewers"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_viewer_sample_streamer_id")
+                            .from("viewer_sample", "streamer_id")
+                            .to("streamer", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_viewer_sample_bucket")
+                    .table("viewer_sample")
+                    .col("streamer_id")
+                    .col("resolution_secs")
+                    .col("bucket_start")
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("viewer_sample").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("live_session")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(integer("streamer_id"))
+                    .col(timestamp("started_at"))
+                    .col(timestamp_null("ended_at"))
+                    .col(text("title"))
+                    .col(string("category"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_live_session_streamer_id")
+                            .from("live_session", "streamer_id")
+                            .to("streamer", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_live_session_streamer_started_at")
+                    .table("live_session")
+                    .col("streamer_id")
+                    .col("started_at")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("live_session").to_owned())
+            .await
+    }
+}
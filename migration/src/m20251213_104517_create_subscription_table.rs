@@ -0,0 +1,57 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("subscription")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(integer("user_id"))
+                    .col(integer("streamer_id"))
+                    .col(json_binary("rules").default(Expr::cust("'{}'::jsonb")))
+                    .col(timestamp_null("last_notified_at"))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .col(timestamp("updated_at").default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_subscription_user_id")
+                            .from("subscription", "user_id")
+                            .to("user", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_subscription_streamer_id")
+                            .from("subscription", "streamer_id")
+                            .to("streamer", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_subscription_user_streamer")
+                    .table("subscription")
+                    .col("user_id")
+                    .col("streamer_id")
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("subscription").to_owned())
+            .await
+    }
+}
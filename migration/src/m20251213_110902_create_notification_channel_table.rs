@@ -0,0 +1,39 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("notification_channel")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(integer("user_id"))
+                    .col(string("channel"))
+                    .col(text("destination"))
+                    .col(json_binary("metadata").default(Expr::cust("'{}'::jsonb")))
+                    .col(boolean("enabled").default(true))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .col(timestamp("updated_at").default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_channel_user_id")
+                            .from("notification_channel", "user_id")
+                            .to("user", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("notification_channel").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("streamer_change")
+                    .if_not_exists()
+                    .col(big_pk_auto("id"))
+                    .col(integer("streamer_id"))
+                    .col(string("field"))
+                    .col(text("old_value"))
+                    .col(text("new_value"))
+                    .col(boolean("while_live"))
+                    .col(timestamp("changed_at").default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_streamer_change_streamer_id")
+                            .from("streamer_change", "streamer_id")
+                            .to("streamer", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_streamer_change_streamer_changed_at")
+                    .table("streamer_change")
+                    .col("streamer_id")
+                    .col("changed_at")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("subscription")
+                    .add_column(boolean("notify_title_change").default(false))
+                    .add_column(boolean("notify_category_change").default(false))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("subscription")
+                    .drop_column("notify_title_change")
+                    .drop_column("notify_category_change")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("streamer_change").to_owned())
+            .await
+    }
+}
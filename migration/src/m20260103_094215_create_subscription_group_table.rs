@@ -0,0 +1,110 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("subscription_group")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(integer("user_id"))
+                    .col(string_len("name", 64))
+                    .col(json_binary("rules").default(Expr::cust("'{}'::jsonb")))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .col(timestamp("updated_at").default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_subscription_group_user_id")
+                            .from("subscription_group", "user_id")
+                            .to("user", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_subscription_group_user_name")
+                    .table("subscription_group")
+                    .col("user_id")
+                    .col("name")
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("subscription")
+                    .add_column(integer_null("group_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_subscription_group_id")
+                            .from_tbl("subscription")
+                            .from_col("group_id")
+                            .to_tbl("subscription_group")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("subscription_tag")
+                    .if_not_exists()
+                    .col(integer("subscription_id"))
+                    .col(string_len("tag", 32))
+                    .primary_key(Index::create().col("subscription_id").col("tag"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_subscription_tag_subscription_id")
+                            .from("subscription_tag", "subscription_id")
+                            .to("subscription", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_subscription_tag_tag")
+                    .table("subscription_tag")
+                    .col("tag")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("subscription_tag").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("subscription")
+                    .drop_foreign_key("fk_subscription_group_id")
+                    .drop_column("group_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("subscription_group").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,67 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("share_page")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(integer("user_id"))
+                    .col(integer("group_id"))
+                    .col(string_len("slug", 64))
+                    .col(string_len("title", 100))
+                    .col(timestamp_null("expires_at"))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .col(timestamp("updated_at").default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_page_user_id")
+                            .from("share_page", "user_id")
+                            .to("user", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_share_page_group_id")
+                            .from("share_page", "group_id")
+                            .to("subscription_group", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_share_page_slug")
+                    .table("share_page")
+                    .col("slug")
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_share_page_user_id")
+                    .table("share_page")
+                    .col("user_id")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("share_page").to_owned())
+            .await
+    }
+}
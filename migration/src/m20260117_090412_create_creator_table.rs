@@ -0,0 +1,75 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("creator")
+                    .if_not_exists()
+                    .col(pk_auto("id"))
+                    .col(string_len("name", 64))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .col(timestamp("updated_at").default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("streamer")
+                    .add_column(integer_null("creator_id"))
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk_streamer_creator_id")
+                            .from_tbl("streamer")
+                            .from_col("creator_id")
+                            .to_tbl("creator")
+                            .to_col("id")
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_streamer_creator_id")
+                    .table("streamer")
+                    .col("creator_id")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_streamer_creator_id")
+                    .table("streamer")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("streamer")
+                    .drop_foreign_key("fk_streamer_creator_id")
+                    .drop_column("creator_id")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .drop_table(Table::drop().table("creator").to_owned())
+            .await
+    }
+}
@@ -0,0 +1,50 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("streamer")
+                    .add_column(timestamp_null("next_check_at"))
+                    .add_column(integer_null("poll_interval_secs"))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_streamer_next_check_at")
+                    .table("streamer")
+                    .col("next_check_at")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_streamer_next_check_at")
+                    .table("streamer")
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("streamer")
+                    .drop_column("next_check_at")
+                    .drop_column("poll_interval_secs")
+                    .to_owned(),
+            )
+            .await
+    }
+}
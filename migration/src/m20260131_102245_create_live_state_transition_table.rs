@@ -0,0 +1,80 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("streamer")
+                    .add_column(string_len("live_state", 16).default("offline"))
+                    .add_column(timestamp_null("live_state_since"))
+                    .add_column(integer("live_state_polls").default(0))
+                    .to_owned(),
+            )
+            .await?;
+
+        // Rooms live before the upgrade continue as confirmed live.
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table("streamer")
+                    .value("live_state", "live")
+                    .and_where(Expr::col("is_live").eq(true))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table("live_state_transition")
+                    .if_not_exists()
+                    .col(big_pk_auto("id"))
+                    .col(integer("streamer_id"))
+                    .col(string_len("from_state", 16))
+                    .col(string_len("to_state", 16))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_live_state_transition_streamer_id")
+                            .from("live_state_transition", "streamer_id")
+                            .to("streamer", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_live_state_transition_streamer_created_at")
+                    .table("live_state_transition")
+                    .col("streamer_id")
+                    .col("created_at")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("live_state_transition").to_owned())
+            .await?;
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table("streamer")
+                    .drop_column("live_state")
+                    .drop_column("live_state_since")
+                    .drop_column("live_state_polls")
+                    .to_owned(),
+            )
+            .await
+    }
+}
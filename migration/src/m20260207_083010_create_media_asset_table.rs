@@ -0,0 +1,60 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("media_asset")
+                    .if_not_exists()
+                    .col(big_pk_auto("id"))
+                    .col(string_len("kind", 16))
+                    .col(string_len("hash", 64))
+                    .col(text("source_url"))
+                    .col(timestamp_null("fetched_at"))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_media_asset_kind_hash")
+                    .table("media_asset")
+                    .col("kind")
+                    .col("hash")
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        // Images known before the upgrade, hashed the same way as `domain::media::hash`.
+        let db = manager.get_connection();
+        for (kind, table, column) in [
+            ("avatar", "streamer", "avatar"),
+            ("cover", "streamer", "cover_image"),
+            ("cover", "live_session", "cover_image"),
+        ] {
+            db.execute_unprepared(&format!(
+                "INSERT INTO media_asset (kind, hash, source_url) \
+                 SELECT DISTINCT '{kind}', encode(sha256(convert_to({column}, 'UTF8')), 'hex'), {column} \
+                 FROM {table} WHERE {column} <> '' \
+                 ON CONFLICT (kind, hash) DO NOTHING"
+            ))
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("media_asset").to_owned())
+            .await
+    }
+}
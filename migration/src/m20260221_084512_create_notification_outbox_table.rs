@@ -0,0 +1,62 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table("notification_outbox")
+                    .if_not_exists()
+                    .col(big_pk_auto("id"))
+                    .col(integer("streamer_id"))
+                    .col(string_len("event", 16))
+                    .col(integer_null("live_session_id"))
+                    .col(json_binary("change_ids").default(Expr::cust("'[]'::jsonb")))
+                    .col(json_binary_null("recipients"))
+                    .col(string_len("status", 16).default("pending"))
+                    .col(integer("attempts").default(0))
+                    .col(timestamp("next_attempt_at").default(Expr::current_timestamp()))
+                    .col(text_null("last_error"))
+                    .col(timestamp("created_at").default(Expr::current_timestamp()))
+                    .col(timestamp("updated_at").default(Expr::current_timestamp()))
+                    .col(timestamp_null("delivered_at"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_outbox_streamer_id")
+                            .from("notification_outbox", "streamer_id")
+                            .to("streamer", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_notification_outbox_live_session_id")
+                            .from("notification_outbox", "live_session_id")
+                            .to("live_session", "id")
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_notification_outbox_status_next_attempt_at")
+                    .table("notification_outbox")
+                    .col("status")
+                    .col("next_attempt_at")
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table("notification_outbox").to_owned())
+            .await
+    }
+}
@@ -1,4 +1,5 @@
 use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
 
 #[derive(Debug, Parser)]
 #[command(name = "fusion")]
@@ -16,6 +17,15 @@ pub enum Commands {
     #[command(arg_required_else_help = true, about = "Run database migrations")]
     Migrate(MigrateArgs),
 
+    #[command(
+        arg_required_else_help = true,
+        about = "Export or import the subscriptions of a user"
+    )]
+    Subscriptions(SubscriptionsArgs),
+
+    #[command(about = "Generate a VAPID key pair for Web Push")]
+    VapidKeys,
+
     #[command(about = "Print build metadata")]
     Version,
 }
@@ -37,6 +47,33 @@ pub enum MigrateCommands {
     Version,
 }
 
+#[derive(Debug, Args)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct SubscriptionsArgs {
+    #[command(subcommand)]
+    pub command: SubscriptionsCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum SubscriptionsCommands {
+    #[command(about = "Export subscriptions and notification channels as JSON")]
+    Export {
+        #[arg(long, short, help = "Username of the account to export")]
+        user: String,
+        #[arg(long, short, help = "Write to this file instead of stdout")]
+        output: Option<PathBuf>,
+    },
+    #[command(about = "Import an export document or a list of room URLs")]
+    Import {
+        #[arg(long, short, help = "Username of the account to import into")]
+        user: String,
+        #[arg(long, help = "Resolve and validate without writing anything")]
+        dry_run: bool,
+        #[arg(help = "File to import, or - for stdin")]
+        file: PathBuf,
+    },
+}
+
 #[derive(Debug, Args)]
 pub struct ServeArgs {
     #[arg(long, short, help = "Override server port from config", value_parser = clap::value_parser!(u16).range(1..=65535))]
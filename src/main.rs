@@ -1,8 +1,9 @@
-use crate::cli::{Cli, Commands, MigrateCommands};
+use crate::cli::{Cli, Commands, MigrateCommands, SubscriptionsCommands};
 use clap::Parser;
 use migration::Migrator;
 use migration::sea_orm::{ConnectOptions, Database};
-use std::env;
+use std::io::Read;
+use std::{env, fs, io, process};
 
 mod cli;
 
@@ -60,6 +61,46 @@ async fn main() {
                 }
             }
         }
+        Commands::Subscriptions(subscriptions_args) => {
+            let result = match subscriptions_args.command {
+                SubscriptionsCommands::Export { user, output } => api::export_subscriptions(&user)
+                    .await
+                    .and_then(|document| match output {
+                        Some(path) => Ok(fs::write(path, document)?),
+                        None => {
+                            println!("{document}");
+                            Ok(())
+                        }
+                    }),
+                SubscriptionsCommands::Import {
+                    user,
+                    dry_run,
+                    file,
+                } => {
+                    let input = if file.as_os_str() == "-" {
+                        let mut input = String::new();
+                        io::stdin().read_to_string(&mut input).map(|_| input)
+                    } else {
+                        fs::read_to_string(&file)
+                    };
+                    match input {
+                        Ok(input) => api::import_subscriptions(&user, &input, dry_run)
+                            .await
+                            .map(|report| println!("{report}")),
+                        Err(err) => Err(err.into()),
+                    }
+                }
+            };
+            if let Err(err) = result {
+                eprintln!("Error: {err:#}");
+                process::exit(1);
+            }
+        }
+        Commands::VapidKeys => {
+            let (private_key, public_key) = api::generate_vapid_keys();
+            println!("vapid_private_key = \"{private_key}\"");
+            println!("# public key: {public_key}");
+        }
         Commands::Version => {
             println!("version        : {}", BUILD_GIT_TAG);
             println!("git commit     : {}", BUILD_GIT_COMMIT);